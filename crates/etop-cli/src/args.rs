@@ -8,6 +8,10 @@ pub struct Cli {
     #[clap()]
     pub dataset: Option<String>,
 
+    /// additional datasets to open as views, comma-separated
+    #[clap(long, value_delimiter = ',')]
+    pub views: Vec<String>,
+
     /// block
     #[clap(short, long)]
     pub block: Option<u32>,
@@ -24,7 +28,31 @@ pub struct Cli {
     #[clap(short, long)]
     pub rpc: Option<String>,
 
+    /// navigate with vim keys, moving live window from l to ctrl+l
+    #[clap(long)]
+    pub vim: bool,
+
+    /// number of times to retry fetches failing with timeouts, rate limits, or server errors
+    #[clap(long, default_value_t = 3)]
+    pub max_retries: u32,
+
+    /// delay in milliseconds before first retry, doubled for each further retry
+    #[clap(long, default_value_t = 500)]
+    pub retry_delay: u64,
+
+    /// when live views refresh: manual (press r), adaptive, or an interval in seconds
+    #[clap(long, default_value = "1")]
+    pub refresh: String,
+
+    /// maximum number of rows to fetch or render, asking before fetches that would exceed it
+    #[clap(long, default_value_t = etop_core::DEFAULT_MAX_ROWS)]
+    pub max_rows: usize,
+
     /// print formatted data without interactive interface
     #[clap(short, long)]
     pub print: bool,
+
+    /// check that the rpc provides the columns the dataset needs, fetching a single block
+    #[clap(long)]
+    pub check: bool,
 }
@@ -0,0 +1,19 @@
+use crate::{Cli, EtopError};
+
+pub(crate) async fn check_command(args: Cli) -> Result<(), EtopError> {
+    let etop_state = super::tui_command::create_etop_state(
+        args.dataset,
+        args.block,
+        args.window,
+        args.rpc,
+        args.data_dir,
+    )
+    .await?;
+    let source = etop_state
+        .rpc_source
+        .clone()
+        .ok_or_else(|| EtopError::ArgumentError("--check requires an rpc provider".to_string()))?;
+    etop_state.validate(&source).await?;
+    println!("{} ok", etop_state.dataset);
+    Ok(())
+}
@@ -1,2 +1,3 @@
+pub(crate) mod check_command;
 pub(crate) mod print_command;
 pub(crate) mod tui_command;
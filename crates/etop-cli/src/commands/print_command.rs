@@ -9,18 +9,22 @@ pub(crate) async fn print_command(args: Cli) -> Result<(), EtopError> {
         args.data_dir,
     )
     .await?;
+    etop_state.max_rows = Some(args.max_rows);
     let queries = etop_state.create_missing_queries()?;
+    etop_state.check_row_limit(&queries)?;
     for query in queries.into_iter() {
         let result = etop_state.query(query.clone()).await?;
         etop_state.warehouse.add_dataset(query.dataset(), result)?;
     }
     let queries = etop_state.create_missing_queries()?;
+    etop_state.check_row_limit(&queries)?;
     for query in queries.into_iter() {
         let result = etop_state.query(query.clone()).await?;
         etop_state.warehouse.add_dataset(query.dataset(), result)?;
     }
 
     let queries = etop_state.create_missing_queries()?;
+    etop_state.check_row_limit(&queries)?;
     for query in queries.into_iter() {
         let result = etop_state.query(query.clone()).await?;
         etop_state.warehouse.add_dataset(query.dataset(), result)?;
@@ -29,7 +33,8 @@ pub(crate) async fn print_command(args: Cli) -> Result<(), EtopError> {
     println!("ETOP_STATE {:?}", etop_state.warehouse);
 
     let (render_width, render_height) = term_size::dimensions().unwrap_or((80, 20));
-    let s = etop_state.format_window(render_width, render_height)?;
+    // leave a row for the shell prompt
+    let s = etop_state.format_window(render_height.saturating_sub(1), render_width)?;
     println!("{}", s);
 
     Ok(())
@@ -1,14 +1,32 @@
 use crate::Cli;
-use etop_core::{EtopError, EtopState, Window, WindowSize};
+use etop_core::{
+    EtopError, EtopState, NavMode, RefreshMode, RetryPolicy, Views, Window, WindowSize,
+};
 
 const DEFAULT_DATASET: &str = "transactions_by_to_address";
 
 pub(crate) async fn tui_command(args: Cli) -> Result<(), EtopError> {
-    let etop_state =
+    let refresh: RefreshMode = args.refresh.parse()?;
+    let mut etop_state =
         create_etop_state(args.dataset, args.block, args.window, args.rpc, args.data_dir).await?;
+    etop_state.max_rows = Some(args.max_rows);
+
+    // open each additional dataset as its own view
+    let mut views = vec![etop_state.clone()];
+    for dataset in args.views.into_iter() {
+        views.push(EtopState { dataset, ..etop_state.clone() });
+    }
+    let views = Views::new(views)?;
+
+    let nav_mode = if args.vim { NavMode::Vim } else { NavMode::Default };
+    let retry = RetryPolicy {
+        max_retries: args.max_retries,
+        base_delay: std::time::Duration::from_millis(args.retry_delay),
+        ..Default::default()
+    };
 
     // run main function
-    etop_tui::tokio_main(Some(etop_state))
+    etop_tui::tokio_main(Some(views), nav_mode, retry, refresh)
         .await
         .map_err(|e| EtopError::TuiError(format!("{:?}", e)))
         .ok();
@@ -11,7 +11,7 @@ mod commands;
 
 pub use args::Cli;
 use clap::Parser;
-use commands::{print_command, tui_command};
+use commands::{check_command, print_command, tui_command};
 pub use etop_core::EtopError;
 
 #[tokio::main]
@@ -21,7 +21,9 @@ async fn main() -> Result<(), EtopError> {
 
 pub(crate) async fn run_cli() -> Result<(), EtopError> {
     let args = Cli::parse();
-    if args.print {
+    if args.check {
+        check_command::check_command(args).await
+    } else if args.print {
         print_command::print_command(args).await
     } else {
         tui_command::tui_command(args).await
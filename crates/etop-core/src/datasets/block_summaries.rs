@@ -0,0 +1,112 @@
+use crate::{DataSpec, DataWarehouse, EtopError, Granularity, InputDataset};
+use etop_format::{ColumnFormatShorthand, NumberFormat};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// name of pane summarizing transactions of each block
+const PER_BLOCK: &str = "per_block";
+
+/// name of pane summarizing transactions of each sender
+const PER_SENDER: &str = "per_sender";
+
+/// summaries of transactions per block and per sender, shown side by side
+#[derive(Clone)]
+pub struct BlockSummaries;
+
+impl BlockSummaries {
+    fn summarize(
+        &self,
+        warehouse: &DataWarehouse,
+        start_block: Option<u32>,
+        end_block: Option<u32>,
+        by: &str,
+    ) -> Result<DataFrame, EtopError> {
+        let txs = warehouse.get_dataset("transactions")?;
+        let txs = crate::filter_by_block_number(txs, start_block, end_block)?;
+        txs.lazy()
+            .group_by([by])
+            .agg([count().alias("n_txs"), col("gas_used").sum().alias("gas_used")])
+            .sort_by_exprs(vec![col("n_txs"), col(by)], [true, false], true, false)
+            .collect()
+            .map_err(EtopError::PolarsError)
+    }
+}
+
+impl DataSpec for BlockSummaries {
+    fn name(&self) -> String {
+        "block_summaries".into()
+    }
+
+    fn row_noun(&self) -> String {
+        "summaries".into()
+    }
+
+    fn inputs(&self) -> Vec<InputDataset> {
+        vec![InputDataset::Raw("transactions".into())]
+    }
+
+    fn transform(
+        &self,
+        warehouse: &DataWarehouse,
+        start_block: Option<u32>,
+        end_block: Option<u32>,
+    ) -> Result<DataFrame, EtopError> {
+        self.summarize(warehouse, start_block, end_block, "block_number")
+    }
+
+    fn transform_multi(
+        &self,
+        warehouse: &DataWarehouse,
+        start_block: Option<u32>,
+        end_block: Option<u32>,
+    ) -> Result<HashMap<String, DataFrame>, EtopError> {
+        let per_block = self.transform(warehouse, start_block, end_block)?;
+        let per_sender = self.summarize(warehouse, start_block, end_block, "from_address")?;
+        Ok([(PER_BLOCK.to_string(), per_block), (PER_SENDER.to_string(), per_sender)]
+            .into_iter()
+            .collect())
+    }
+
+    fn pane_names(&self) -> Vec<String> {
+        vec![PER_BLOCK.to_string(), PER_SENDER.to_string()]
+    }
+
+    fn required_columns(&self) -> HashMap<String, Vec<String>> {
+        let columns = ["block_number", "from_address", "gas_used"];
+        let columns = columns.iter().map(|s| s.to_string()).collect();
+        [("transactions".to_string(), columns)].into_iter().collect()
+    }
+
+    fn default_columns(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    fn default_column_formats(&self) -> Option<HashMap<String, ColumnFormatShorthand>> {
+        let integer_oom = NumberFormat::new().integer_oom().precision(1);
+        let formats = vec![
+            ColumnFormatShorthand::new().name("block_number").newline_underscores(),
+            ColumnFormatShorthand::new().name("from_address").newline_underscores(),
+            ColumnFormatShorthand::new()
+                .name("n_txs")
+                .newline_underscores()
+                .set_format(integer_oom.clone()),
+            ColumnFormatShorthand::new()
+                .name("gas_used")
+                .newline_underscores()
+                .set_format(integer_oom),
+        ]
+        .into_iter()
+        .map(|column| (column.name.clone(), column))
+        .collect();
+
+        Some(formats)
+    }
+
+    fn key_column(&self) -> Option<String> {
+        Some("block_number".to_string())
+    }
+
+    fn granularity(&self) -> Granularity {
+        Granularity::Transaction
+    }
+}
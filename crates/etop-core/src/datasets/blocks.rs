@@ -1,4 +1,4 @@
-use crate::{DataSpec, DataWarehouse, EtopError, InputDataset};
+use crate::{DataSpec, DataWarehouse, EtopError, InputDataset, MetricSpec};
 use etop_format::ColumnFormatShorthand;
 use polars::prelude::*;
 use std::collections::HashMap;
@@ -50,7 +50,6 @@ impl DataSpec for Blocks {
             .get_dataset("blocks")?
             .clone()
             .lazy()
-            .with_column(col("base_fee_per_gas") / lit(1e9))
             .sort("block_number", sort)
             .collect()
             .map_err(EtopError::PolarsError)?;
@@ -65,6 +64,28 @@ impl DataSpec for Blocks {
             .map_err(EtopError::PolarsError)
     }
 
+    fn required_columns(&self) -> HashMap<String, Vec<String>> {
+        let block_number = vec!["block_number".to_string()];
+        [("blocks".to_string(), block_number.clone()), ("transactions".to_string(), block_number)]
+            .into_iter()
+            .collect()
+    }
+
+    fn available_columns(&self) -> Option<Vec<String>> {
+        let columns = [
+            "block_number",
+            "block_hash",
+            "timestamp",
+            "n_txs",
+            "gas_used",
+            "base_fee_per_gas",
+            "author",
+            "extra_data",
+            "chain_id",
+        ];
+        Some(columns.iter().map(|s| s.to_string()).collect())
+    }
+
     fn default_columns(&self) -> Option<Vec<String>> {
         let columns =
             ["block_number", "timestamp", "n_txs", "gas_used", "base_fee_per_gas", "author"]
@@ -91,7 +112,7 @@ impl DataSpec for Blocks {
             ColumnFormatShorthand::new()
                 .name("base_fee_per_gas")
                 .display_name("base_fee")
-                .set_format(float_oom)
+                .set_format(float_oom.scale(1e-9))
                 .min_width(5)
                 .newline_underscores(),
             ColumnFormatShorthand::new().name("author"),
@@ -102,4 +123,26 @@ impl DataSpec for Blocks {
 
         Some(formats)
     }
+
+    fn key_column(&self) -> Option<String> {
+        Some("block_number".to_string())
+    }
+
+    fn metrics(&self) -> Vec<MetricSpec> {
+        let integer_oom = etop_format::NumberFormat::new().integer_oom().precision(1);
+        let gwei = etop_format::NumberFormat::new().si().precision(3).scale(1e-9).suffix(" gwei");
+        vec![
+            MetricSpec::new("total gas", col("gas_used").sum()).set_format(integer_oom.clone()),
+            MetricSpec::new("tx count", col("n_txs").sum()).set_format(integer_oom),
+            MetricSpec::new("avg base fee", col("base_fee_per_gas").mean()).set_format(gwei),
+        ]
+    }
+
+    fn column_presets(&self) -> HashMap<String, Vec<String>> {
+        let compact = ["block_number", "n_txs", "gas_used"].iter().map(|s| s.to_string()).collect();
+        let mut presets = HashMap::new();
+        presets.insert("compact".to_string(), compact);
+        presets.insert("full".to_string(), self.default_columns().unwrap_or_default());
+        presets
+    }
 }
@@ -1,8 +1,11 @@
-use crate::{DataSpec, DataWarehouse, EtopError, InputDataset};
+use crate::{DataSpec, DataWarehouse, EtopError, Granularity, InputDataset};
 use etop_format::ColumnFormatShorthand;
 use polars::prelude::*;
 use std::collections::HashMap;
 
+/// style of rows of failed transactions
+const FAILED_ROW_STYLE: &str = "red";
+
 /// cryo dataset
 #[derive(Clone)]
 pub struct CryoDataset {
@@ -19,6 +22,21 @@ impl DataSpec for CryoDataset {
         self.name.to_string()
     }
 
+    fn granularity(&self) -> Granularity {
+        match self.name.as_str() {
+            "transactions" => Granularity::Transaction,
+            "logs" | "erc20_transfers" | "erc721_transfers" => Granularity::Log,
+            _ => Granularity::Block,
+        }
+    }
+
+    fn key_column(&self) -> Option<String> {
+        match self.name.as_str() {
+            "transactions" => Some("transaction_hash".to_string()),
+            _ => None,
+        }
+    }
+
     fn inputs(&self) -> Vec<InputDataset> {
         vec![InputDataset::Raw(self.name.to_string())]
     }
@@ -35,7 +53,7 @@ impl DataSpec for CryoDataset {
         for (name, dtype) in df.schema().iter() {
             if !dtype.is_integer() & !dtype.is_float() {
                 match dtype {
-                    DataType::Utf8 => {}
+                    DataType::Utf8 | DataType::Boolean => {}
                     _ => {
                         df = df
                             .lazy()
@@ -58,4 +76,14 @@ impl DataSpec for CryoDataset {
     fn default_column_formats(&self) -> Option<HashMap<String, ColumnFormatShorthand>> {
         None
     }
+
+    fn row_style(&self, df: &DataFrame, row: usize) -> Option<String> {
+        if self.name != "transactions" {
+            return None
+        }
+        match df.column("success").ok()?.bool().ok()?.get(row) {
+            Some(false) => Some(FAILED_ROW_STYLE.to_string()),
+            _ => None,
+        }
+    }
 }
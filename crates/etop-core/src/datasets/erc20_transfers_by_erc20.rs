@@ -1,4 +1,4 @@
-use crate::{AddressQueryArgument, DataSpec, DataWarehouse, EtopError, InputDataset};
+use crate::{AddressQueryArgument, DataSpec, DataWarehouse, EtopError, Granularity, InputDataset};
 use etop_format::ColumnFormatShorthand;
 use polars::prelude::*;
 use std::collections::HashMap;
@@ -75,6 +75,25 @@ impl DataSpec for Erc20TransfersByErc20 {
         df.map_err(EtopError::PolarsError)
     }
 
+    fn required_columns(&self) -> HashMap<String, Vec<String>> {
+        let transfers = [
+            "block_number",
+            "erc20",
+            "from_address",
+            "to_address",
+            "transaction_hash",
+            "value_f64",
+        ];
+        let metadata = ["erc20", "symbol", "decimals"];
+        let columns = |columns: &[&str]| columns.iter().map(|s| s.to_string()).collect();
+        [
+            ("erc20_transfers".to_string(), columns(&transfers)),
+            ("erc20_metadata".to_string(), columns(&metadata)),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn default_columns(&self) -> Option<Vec<String>> {
         let columns = [
             "symbol",
@@ -117,4 +136,12 @@ impl DataSpec for Erc20TransfersByErc20 {
 
         Some(formats)
     }
+
+    fn key_column(&self) -> Option<String> {
+        Some("erc20".to_string())
+    }
+
+    fn granularity(&self) -> Granularity {
+        Granularity::Log
+    }
 }
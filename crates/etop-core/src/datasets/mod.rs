@@ -1,9 +1,13 @@
+mod block_summaries;
 mod blocks;
 mod cryo_dataset;
 mod erc20_transfers_by_erc20;
+mod sql_dataset;
 mod transactions_by_to_address;
 
+pub use block_summaries::BlockSummaries;
 pub use blocks::Blocks;
 pub use cryo_dataset::CryoDataset;
 pub use erc20_transfers_by_erc20::Erc20TransfersByErc20;
+pub use sql_dataset::{sql_table_names, SqlDataset};
 pub use transactions_by_to_address::TransactionsByToAddress;
@@ -0,0 +1,166 @@
+#[cfg(test)]
+#[path = "sql_dataset_tests.rs"]
+mod tests;
+
+use crate::{DataSpec, DataWarehouse, EtopError, InputDataset};
+use etop_format::ColumnFormatShorthand;
+use polars::{prelude::*, sql::SQLContext};
+use std::collections::HashMap;
+
+/// dataset defined by a polars sql query over input datatypes, each registered as a table
+/// named after its datatype, e.g. `SELECT * FROM blocks JOIN transactions USING (block_number)`
+#[derive(Clone)]
+pub struct SqlDataset {
+    /// name
+    pub name: String,
+    /// sql query
+    pub query: String,
+}
+
+impl SqlDataset {
+    /// create dataset from name and sql query
+    pub fn new<T: Into<String>, U: Into<String>>(name: T, query: U) -> SqlDataset {
+        SqlDataset { name: name.into(), query: query.into() }
+    }
+}
+
+impl DataSpec for SqlDataset {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn row_noun(&self) -> String {
+        "rows".to_string()
+    }
+
+    fn inputs(&self) -> Vec<InputDataset> {
+        sql_table_names(&self.query).into_iter().map(InputDataset::Raw).collect()
+    }
+
+    fn transform(
+        &self,
+        warehouse: &DataWarehouse,
+        start_block: Option<u32>,
+        end_block: Option<u32>,
+    ) -> Result<DataFrame, EtopError> {
+        let mut context = SQLContext::new();
+        for input in self.inputs() {
+            let name = input.name();
+            context.register(name.as_str(), warehouse.get_dataset(name.as_str())?.lazy());
+        }
+        let invalid = |e: PolarsError| {
+            EtopError::InvalidSpecification(format!("invalid query of {}: {}", self.name, e))
+        };
+        let df =
+            context.execute(self.query.as_str()).and_then(|lf| lf.collect()).map_err(invalid)?;
+        if df.column("block_number").is_ok() {
+            crate::filter_by_block_number(df, start_block, end_block)
+        } else {
+            Ok(df)
+        }
+    }
+
+    fn default_columns(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    fn default_column_formats(&self) -> Option<HashMap<String, ColumnFormatShorthand>> {
+        None
+    }
+}
+
+/// keywords that can precede a parenthesized subquery or list, rather than name a function
+const SQL_KEYWORDS: [&str; 19] = [
+    "all", "and", "any", "as", "else", "exists", "from", "in", "join", "lateral", "not", "on",
+    "or", "select", "then", "union", "using", "when", "where",
+];
+
+/// names of tables that sql query reads from, those following `FROM` or `JOIN`, in order of
+/// first reference
+///
+/// subqueries, names of common table expressions, and the `FROM` of function arguments, such as
+/// `EXTRACT(year FROM timestamp)`, are not tables
+pub fn sql_table_names(query: &str) -> Vec<String> {
+    let tokens = sql_tokens(query);
+    let unquote = |token: &str| token.trim_matches(|c| c == '"' || c == '`').to_string();
+    let is_name = |token: &str| {
+        !token.is_empty() &&
+            token.chars().all(|c| c.is_alphanumeric() || "_.\"`".contains(c)) &&
+            !SQL_KEYWORDS.contains(&token.to_ascii_lowercase().as_str())
+    };
+
+    // for each open parenthesis, whether it holds the arguments of a function call, and for
+    // each closed parenthesis, the position of its open parenthesis
+    let mut open: Vec<(usize, bool)> = Vec::new();
+    let mut opened_at: HashMap<usize, usize> = HashMap::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut ctes: Vec<String> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match *token {
+            "(" => {
+                let call = i > 0 && is_name(tokens[i - 1]);
+                open.push((i, call));
+            }
+            ")" => {
+                if let Some((start, _)) = open.pop() {
+                    opened_at.insert(i, start);
+                }
+            }
+            _ if token.eq_ignore_ascii_case("as") && tokens.get(i + 1) == Some(&"(") => {
+                // common table expression, e.g. `name AS (...)` or `name (a, b) AS (...)`
+                let name = match i.checked_sub(1).map(|j| (j, tokens[j])) {
+                    Some((j, ")")) => opened_at.get(&j).and_then(|start| start.checked_sub(1)),
+                    Some((j, _)) => Some(j),
+                    None => None,
+                };
+                if let Some(name) = name.map(|j| tokens[j]).filter(|name| is_name(name)) {
+                    ctes.push(unquote(name));
+                }
+            }
+            _ if token.eq_ignore_ascii_case("from") || token.eq_ignore_ascii_case("join") => {
+                let in_call = matches!(open.last(), Some((_, true)));
+                match tokens.get(i + 1) {
+                    Some(name) if !in_call && is_name(name) => {
+                        let name = unquote(name);
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    names.retain(|name| !ctes.contains(name));
+    names
+}
+
+/// split sql query into words, parentheses, and separators, dropping string literals
+fn sql_tokens(query: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut in_string = false;
+    for (i, c) in query.char_indices() {
+        if in_string {
+            in_string = c != '\'';
+            continue
+        }
+        if c.is_whitespace() || "(),;'".contains(c) {
+            if let Some(start) = start.take() {
+                tokens.push(&query[start..i]);
+            }
+            match c {
+                '(' | ')' | ',' | ';' => tokens.push(&query[i..i + 1]),
+                '\'' => in_string = true,
+                _ => {}
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(&query[start..]);
+    }
+    tokens
+}
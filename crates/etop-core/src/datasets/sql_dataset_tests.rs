@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod sql_dataset_tests {
+    use crate::{sql_table_names, DataSpec, DataWarehouse, EtopError, SqlDataset};
+    use polars::prelude::*;
+
+    fn warehouse() -> DataWarehouse {
+        let mut warehouse = DataWarehouse::default();
+        let blocks = df!("block_number" => [1u32, 2, 3], "gas_used" => [10u64, 20, 30]).unwrap();
+        let transactions = df!("block_number" => [1u32, 1, 3], "value" => [5u64, 6, 7]).unwrap();
+        warehouse.data.insert("blocks".to_string(), blocks);
+        warehouse.data.insert("transactions".to_string(), transactions);
+        warehouse
+    }
+
+    #[test]
+    fn table_names() {
+        let query =
+            "select * from blocks b\njoin \"transactions\" t on b.block_number = t.block_number \
+                     where b.block_number in (select block_number from blocks)";
+        assert_eq!(sql_table_names(query), vec!["blocks", "transactions"]);
+        assert!(sql_table_names("select 1").is_empty());
+    }
+
+    #[test]
+    fn table_names_skip_ctes() {
+        let query = "WITH recent AS (SELECT * FROM blocks WHERE block_number > 1), \
+                     totals (n) AS (SELECT count(*) FROM recent) \
+                     SELECT * FROM recent JOIN totals ON true JOIN transactions USING (block_number)";
+        assert_eq!(sql_table_names(query), vec!["blocks", "transactions"]);
+    }
+
+    #[test]
+    fn table_names_skip_function_arguments() {
+        let query = "SELECT EXTRACT(year FROM timestamp), SUBSTRING(hash FROM 3), 'from x' \
+                     FROM blocks WHERE gas_used IN (SELECT gas_used FROM transactions)";
+        assert_eq!(sql_table_names(query), vec!["blocks", "transactions"]);
+    }
+
+    #[test]
+    fn join_query() {
+        let dataset = SqlDataset::new(
+            "tx_gas",
+            "SELECT t.block_number, t.value, b.gas_used FROM transactions t \
+             JOIN blocks b ON t.block_number = b.block_number",
+        );
+        let inputs: Vec<String> = dataset.inputs().iter().map(|input| input.name()).collect();
+        assert_eq!(inputs, vec!["transactions", "blocks"]);
+
+        let df = dataset.transform(&warehouse(), None, None).unwrap();
+        let df = df.sort(["value"], false, false).unwrap();
+        let expected = df!(
+            "block_number" => [1u32, 1, 3],
+            "value" => [5u64, 6, 7],
+            "gas_used" => [10u64, 10, 30],
+        )
+        .unwrap();
+        assert!(df.frame_equal(&expected));
+
+        // window of blocks is applied to the result
+        let df = dataset.transform(&warehouse(), Some(2), Some(3)).unwrap();
+        assert_eq!(df.height(), 1);
+    }
+
+    #[test]
+    fn invalid_query() {
+        let dataset = SqlDataset::new("bad", "SELECT missing FROM blocks");
+        let result = dataset.transform(&warehouse(), None, None);
+        assert!(matches!(result, Err(EtopError::InvalidSpecification(_))));
+
+        // input datatypes must be in the warehouse
+        let dataset = SqlDataset::new("bad", "SELECT * FROM logs");
+        assert!(dataset.transform(&warehouse(), None, None).is_err());
+    }
+}
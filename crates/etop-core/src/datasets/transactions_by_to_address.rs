@@ -1,4 +1,4 @@
-use crate::{DataSpec, DataWarehouse, EtopError, InputDataset};
+use crate::{DataSpec, DataWarehouse, EtopError, Granularity, InputDataset, MetricSpec};
 use etop_format::{ColumnFormatShorthand, NumberFormat};
 use polars::prelude::*;
 use std::collections::HashMap;
@@ -33,8 +33,8 @@ impl DataSpec for TransactionsByToAddress {
             .group_by(["to_address"])
             .agg([
                 count().alias("n_txs"),
-                col("value_f64").sum().alias("eth_sent") / lit(1e18),
-                col("gas_price").mean().alias("mean_gas_price") / lit(1e9),
+                col("value_f64").sum().alias("eth_sent"),
+                col("gas_price").mean().alias("mean_gas_price"),
                 col("gas_used").mean().alias("mean_gas_used"),
             ])
             .sort_by_exprs(vec![col("n_txs"), col("to_address")], [true, true], true, false)
@@ -42,6 +42,12 @@ impl DataSpec for TransactionsByToAddress {
             .map_err(EtopError::PolarsError)
     }
 
+    fn required_columns(&self) -> HashMap<String, Vec<String>> {
+        let columns = ["block_number", "to_address", "value_f64", "gas_price", "gas_used"];
+        let columns = columns.iter().map(|s| s.to_string()).collect();
+        [("transactions".to_string(), columns)].into_iter().collect()
+    }
+
     fn default_columns(&self) -> Option<Vec<String>> {
         let columns = ["to_address", "n_txs", "eth_sent", "mean_gas_price", "mean_gas_used"]
             .iter()
@@ -64,12 +70,12 @@ impl DataSpec for TransactionsByToAddress {
             ColumnFormatShorthand::new()
                 .name("eth_sent")
                 .newline_underscores()
-                .set_format(oom_float_format.clone())
+                .set_format(oom_float_format.clone().scale(1e-18))
                 .min_width(6),
             ColumnFormatShorthand::new()
                 .name("mean_gas_price")
                 .newline_underscores()
-                .set_format(float_format),
+                .set_format(float_format.scale(1e-9)),
             ColumnFormatShorthand::new()
                 .name("mean_gas_used")
                 .newline_underscores()
@@ -82,4 +88,22 @@ impl DataSpec for TransactionsByToAddress {
 
         Some(formats)
     }
+
+    fn key_column(&self) -> Option<String> {
+        Some("to_address".to_string())
+    }
+
+    fn metrics(&self) -> Vec<MetricSpec> {
+        let integer_oom = NumberFormat::new().integer_oom().precision(1);
+        let eth = NumberFormat::new().float_oom().precision(1).scale(1e-18).suffix(" ETH");
+        vec![
+            MetricSpec::new("tx count", col("n_txs").sum()).set_format(integer_oom.clone()),
+            MetricSpec::new("to addresses", count()).set_format(integer_oom),
+            MetricSpec::new("eth sent", col("eth_sent").sum()).set_format(eth),
+        ]
+    }
+
+    fn granularity(&self) -> Granularity {
+        Granularity::Transaction
+    }
 }
@@ -0,0 +1,115 @@
+#[cfg(test)]
+#[path = "annotations_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// note attached to a cell
+///
+/// the row is identified by the value of its key column, so that the note follows the row
+/// through sorting and scrolling
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// value of the row's key column
+    pub row_key: String,
+    /// column of annotated cell
+    pub column: String,
+    /// note, may be empty to only mark the cell
+    pub note: String,
+}
+
+impl EtopState {
+    /// column identifying rows of the view's data, the dataset's key column
+    pub fn key_column(&self) -> Result<String, EtopError> {
+        match self.dataspec()?.key_column() {
+            Some(key_column) => Ok(key_column),
+            None => Err(EtopError::MissingData(format!("{} has no key column", self.dataset))),
+        }
+    }
+
+    /// annotate cell, replacing any previous note of the cell
+    pub fn annotate(&mut self, row_key: String, column: String, note: String) {
+        self.remove_annotation(&row_key, &column);
+        self.annotations.push(Annotation { row_key, column, note });
+    }
+
+    /// remove note of cell, returning whether there was one
+    pub fn remove_annotation(&mut self, row_key: &str, column: &str) -> bool {
+        let n_annotations = self.annotations.len();
+        self.annotations.retain(|a| a.row_key != row_key || a.column != column);
+        self.annotations.len() != n_annotations
+    }
+
+    /// annotate the inspected cell
+    pub fn annotate_selected(&mut self, note: String) -> Result<(), EtopError> {
+        let (row, column) =
+            self.selected_cell.ok_or(EtopError::MissingData("no cell selected".to_string()))?;
+        let (row_key, column) = self.cell_key(row, column)?;
+        self.annotate(row_key, column, note);
+        Ok(())
+    }
+
+    /// annotation of cell of the cached frame
+    pub fn annotation(&self, row: usize, column: usize) -> Option<&Annotation> {
+        let (row_key, column) = self.cell_key(row, column).ok()?;
+        self.annotations.iter().find(|a| a.row_key == row_key && a.column == column)
+    }
+
+    /// rows of frame annotated in each of its columns
+    pub(crate) fn annotated_rows(&self, df: &DataFrame) -> Result<Vec<Vec<usize>>, EtopError> {
+        let mut rows = vec![vec![]; df.width()];
+        if self.annotations.is_empty() {
+            return Ok(rows)
+        }
+        // keys are read from the unscrolled frame, whose rows are those of df
+        let keys = match self.key_column() {
+            Ok(key_column) => match self.cache_frame.column(key_column.as_str()) {
+                Ok(keys) => keys,
+                Err(_) => return Ok(rows),
+            },
+            Err(_) => return Ok(rows),
+        };
+        for row in 0..df.height().min(keys.len()) {
+            let row_key = crate::cell_value(keys, row)?;
+            for annotation in self.annotations.iter().filter(|a| a.row_key == row_key) {
+                if let Some(c) = df.get_column_names().iter().position(|c| *c == annotation.column)
+                {
+                    rows[c].push(row);
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// write annotations to a json file
+    pub fn export_annotations(&self, path: &str) -> Result<(), EtopError> {
+        let contents = serde_json::to_string_pretty(&self.annotations)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// key of row and name of column of cell of the cached frame
+    ///
+    /// the key is read from the unscrolled frame, so that it does not depend on the key column
+    /// being shown
+    pub(crate) fn cell_key(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> Result<(String, String), EtopError> {
+        let df = self.cache_df.as_ref().ok_or(EtopError::MissingData("no data".to_string()))?;
+        let column = df
+            .get_column_names()
+            .get(column)
+            .ok_or(EtopError::ColumnMissing(format!("column index {} out of bounds", column)))?
+            .to_string();
+        if self.layout.show_row_numbers && column == crate::ROW_NUMBER_COLUMN {
+            return Err(EtopError::ArgumentError("row numbers are not data".to_string()))
+        }
+        let key_column = self.key_column()?;
+        let row_key = crate::cell_value(self.cache_frame.column(key_column.as_str())?, row)?;
+        Ok((row_key, column))
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod annotations_tests {
+    use crate::{parse_command, Annotation, EtopState, SortKey};
+    use etop_format::CELL_MARKER;
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!("transaction_hash" => ["a", "b", "c"], "value" => [30u64, 10, 20]).unwrap();
+        let mut state = EtopState { dataset: "transactions".to_string(), ..Default::default() };
+        state.warehouse.data.insert("transactions".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        state
+    }
+
+    /// rendered data lines containing the cell marker
+    fn marked_lines(state: &EtopState) -> Vec<String> {
+        let render = state.cache_df_render.clone().unwrap();
+        render
+            .lines()
+            .skip(2)
+            .filter(|line| line.contains(CELL_MARKER))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect()
+    }
+
+    #[test]
+    fn annotation_follows_row() {
+        let mut state = state();
+        state.selected_cell = Some((1, 1));
+        state.execute_command(parse_command("note too small").unwrap()).unwrap();
+        let annotation = Annotation {
+            row_key: "b".to_string(),
+            column: "value".to_string(),
+            note: "too small".to_string(),
+        };
+        assert_eq!(state.annotations, vec![annotation.clone()]);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(marked_lines(&state), vec![format!("b {}10", CELL_MARKER)]);
+
+        // re-sorting moves row b last, and its note with it
+        state.layout.sort = vec![SortKey { column: "value".to_string(), descending: true }];
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.annotation(2, 1), Some(&annotation));
+        assert_eq!(state.annotation(1, 1), None);
+        assert_eq!(state.annotation(2, 0), None);
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.lines().last().unwrap().contains(&format!("{}10", CELL_MARKER)));
+
+        // note is shown with the inspected cell
+        state.selected_cell = Some((2, 1));
+        assert_eq!(state.inspect_status(), Some("[2, 1] value: 10  • too small".to_string()));
+
+        // replacing and removing notes
+        state.annotate("b".to_string(), "value".to_string(), "".to_string());
+        assert_eq!(state.annotations.len(), 1);
+        assert_eq!(state.inspect_status(), Some("[2, 1] value: 10".to_string()));
+        assert!(state.remove_annotation("b", "value"));
+        assert!(!state.remove_annotation("b", "value"));
+        state.cache_window(10, 40).unwrap();
+        assert!(marked_lines(&state).is_empty());
+    }
+
+    #[test]
+    fn annotation_keyed_by_hidden_column() {
+        let mut state = state();
+        state.layout.frozen_columns = 0;
+        state.column_offset = 1;
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.cache_df.as_ref().unwrap().get_column_names(), vec!["value"]);
+
+        // the row is keyed by its hash while the hash column is scrolled off
+        state.selected_cell = Some((2, 0));
+        state.annotate_selected("check".to_string()).unwrap();
+        assert_eq!(state.annotations[0].row_key, "c");
+        assert_eq!(state.annotations[0].column, "value");
+        assert!(state.annotation(2, 0).is_some());
+
+        // datasets without a key column cannot be annotated
+        let df = df!("hash" => ["a"], "value" => [1u64]).unwrap();
+        state.dataset = "test_blocks".to_string();
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        state.selected_cell = Some((0, 1));
+        assert!(state.annotate_selected("x".to_string()).is_err());
+    }
+
+    #[test]
+    fn export_annotations() {
+        let mut state = state();
+        assert!(state.execute_command(parse_command("note x").unwrap()).is_err());
+        state.annotate("c".to_string(), "transaction_hash".to_string(), "check".to_string());
+
+        let path = std::env::temp_dir().join(format!("etop_notes_{}.json", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        state.execute_command(parse_command(&format!("export-notes {}", path)).unwrap()).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let annotations: Vec<Annotation> = serde_json::from_str(&contents).unwrap();
+        assert_eq!(annotations, state.annotations);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
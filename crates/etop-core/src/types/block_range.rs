@@ -0,0 +1,121 @@
+#[cfg(test)]
+#[path = "block_range_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState, WindowSize};
+
+/// inclusive range of blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRange {
+    /// first block of range
+    pub start: u64,
+    /// last block of range
+    pub end: u64,
+}
+
+impl BlockRange {
+    /// number of blocks in range
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// whether range has no blocks, never the case for a parsed range
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+/// parse block range relative to head, the latest block
+///
+/// a range is either a single block or `start:end`, with each block given as a number such as
+/// `18_000_000`, as `latest`, or as `latest-N`. blocks after head and ranges that end before
+/// they start are errors
+pub fn parse_block_range(range: &str, head: u64) -> Result<BlockRange, EtopError> {
+    let range = range.trim();
+    let (start, end) = match range.split_once(':') {
+        Some((start, end)) => (parse_block(start, head)?, parse_block(end, head)?),
+        None => {
+            let block = parse_block(range, head)?;
+            (block, block)
+        }
+    };
+    if start > end {
+        return Err(EtopError::ParseError(format!("block range {} ends before it starts", range)))
+    }
+    Ok(BlockRange { start, end })
+}
+
+/// parse a single block of a block range
+fn parse_block(block: &str, head: u64) -> Result<u64, EtopError> {
+    let block = block.trim();
+    let number = |s: &str| {
+        s.replace('_', "")
+            .parse::<u64>()
+            .map_err(|_| EtopError::ParseError(format!("invalid block number: {}", block)))
+    };
+    let value = if block.is_empty() {
+        return Err(EtopError::ParseError("empty block number".to_string()))
+    } else if block == "latest" {
+        head
+    } else if let Some(offset) = block.strip_prefix("latest-") {
+        head.checked_sub(number(offset)?).ok_or(EtopError::ParseError(format!(
+            "{} is before the first block, latest is {}",
+            block, head
+        )))?
+    } else {
+        number(block)?
+    };
+    if value > head {
+        return Err(EtopError::ParseError(format!(
+            "block {} is after the latest block {}",
+            value, head
+        )))
+    }
+    Ok(value)
+}
+
+/// parse number of blocks of a `+N` or `-N` goto
+pub(crate) fn parse_block_offset(offset: &str) -> Result<u32, EtopError> {
+    offset
+        .replace('_', "")
+        .parse()
+        .map_err(|_| EtopError::ParseError(format!("invalid block offset: {}", offset)))
+}
+
+impl EtopState {
+    /// move window to target, either `+N` or `-N` blocks from its end block, or a block range,
+    /// in which case the window ends at its end and, for a `start:end` range, spans it
+    pub fn goto(&mut self, target: &str) -> Result<(), EtopError> {
+        if let Some(sign) = target.chars().next().filter(|c| *c == '+' || *c == '-') {
+            let offset = parse_block_offset(&target[1..])?;
+            let end_block = self
+                .window
+                .end_block
+                .ok_or(EtopError::MissingData("window has no end block".to_string()))?;
+            let end_block = match sign {
+                '+' => end_block.checked_add(offset),
+                _ => end_block.checked_sub(offset),
+            };
+            let end_block = end_block
+                .ok_or(EtopError::ArgumentError(format!("{} moves window out of range", target)))?;
+            self.set_end_block(end_block);
+            return Ok(())
+        }
+
+        let head = match self.latest_block {
+            Some(latest_block) => latest_block as u64,
+            None if target.contains("latest") => {
+                return Err(EtopError::MissingData("latest block not known yet".to_string()))
+            }
+            None => u32::MAX as u64,
+        };
+        let range = parse_block_range(target, head)?;
+        let too_large = |_| EtopError::ArgumentError(format!("block range too large: {}", target));
+        let end_block = u32::try_from(range.end).map_err(too_large)?;
+        if target.contains(':') {
+            self.window.size = WindowSize::Block(u32::try_from(range.len()).map_err(too_large)?);
+        }
+        self.set_end_block(end_block);
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod block_range_tests {
+    use crate::{parse_block_range, BlockRange, EtopState};
+
+    fn range(start: u64, end: u64) -> BlockRange {
+        BlockRange { start, end }
+    }
+
+    fn error(range: &str) -> String {
+        format!("{:?}", parse_block_range(range, 1_000).unwrap_err())
+    }
+
+    #[test]
+    fn absolute_blocks() {
+        assert_eq!(parse_block_range("500", 1_000).unwrap(), range(500, 500));
+        assert_eq!(parse_block_range(" 1_000 ", 1_000).unwrap(), range(1_000, 1_000));
+        assert_eq!(parse_block_range("0", 1_000).unwrap(), range(0, 0));
+        assert_eq!(parse_block_range("100:200", 1_000).unwrap(), range(100, 200));
+        assert_eq!(parse_block_range("100:200", 1_000).unwrap().len(), 101);
+    }
+
+    #[test]
+    fn latest_blocks() {
+        assert_eq!(parse_block_range("latest", 1_000).unwrap(), range(1_000, 1_000));
+        assert_eq!(parse_block_range("latest-100", 1_000).unwrap(), range(900, 900));
+        assert_eq!(parse_block_range("latest-100:latest", 1_000).unwrap(), range(900, 1_000));
+        assert_eq!(parse_block_range("950:latest-10", 1_000).unwrap(), range(950, 990));
+        assert_eq!(parse_block_range("latest-1000", 1_000).unwrap(), range(0, 0));
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        // empty
+        assert!(error("").contains("empty block number"));
+        assert!(error(":").contains("empty block number"));
+        assert!(error("100:").contains("empty block number"));
+
+        // reversed
+        assert!(error("200:100").contains("ends before it starts"));
+        assert!(error("latest:latest-1").contains("ends before it starts"));
+
+        // out of bounds
+        assert!(error("1001").contains("after the latest block 1000"));
+        assert!(error("latest-1001").contains("before the first block"));
+        assert!(error("99999999999999999999").contains("invalid block number"));
+        assert!(error("latest-99999999999999999999").contains("invalid block number"));
+
+        // malformed
+        assert!(error("abc").contains("invalid block number: abc"));
+        assert!(error("latest+5").contains("invalid block number"));
+        assert!(error("-5").contains("invalid block number"));
+        assert!(error("1:2:3").contains("invalid block number"));
+    }
+
+    #[test]
+    fn goto() {
+        let mut state = EtopState::default();
+        state.set_end_block(100);
+
+        // offsets are relative to the end of the window
+        state.goto("+50").unwrap();
+        assert_eq!(state.window.end_block, Some(150));
+        state.goto("-25").unwrap();
+        assert_eq!(state.window.end_block, Some(125));
+        assert!(state.goto("-200").is_err());
+
+        // latest is only known once a block is seen
+        assert!(state.goto("latest-10").is_err());
+        state.goto("110").unwrap();
+        assert_eq!(state.window.end_block, Some(110));
+        state.latest_block = Some(1_000);
+        state.goto("latest-10").unwrap();
+        assert_eq!(state.window.end_block, Some(990));
+
+        // ranges set the window size
+        state.goto("latest-100:latest-1").unwrap();
+        assert_eq!((state.window.start_block, state.window.end_block), (Some(900), Some(999)));
+        assert!(state.goto("2000").is_err());
+    }
+}
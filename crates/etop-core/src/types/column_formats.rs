@@ -0,0 +1,179 @@
+#[cfg(test)]
+#[path = "column_formats_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+use etop_format::{CellFormatShorthand, ColumnFormatShorthand, FormatType, NumberFormat};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// most digits of precision that can be set with adjust_precision
+pub const MAX_PRECISION: usize = 12;
+
+/// format types stepped through by cycle_number_format, in order
+pub const NUMBER_FORMAT_CYCLE: [FormatType; 4] =
+    [FormatType::FixedPoint, FormatType::SI, FormatType::Exponent, FormatType::Hex];
+
+impl EtopState {
+    /// format of column, the view's override if any, else the dataset's format, else the format
+    /// inferred from the column's name, else the default format of the column's dtype
+    pub(crate) fn resolve_column_format(
+        &self,
+        dataset_formats: &HashMap<String, ColumnFormatShorthand>,
+        column: &str,
+        dtype: &DataType,
+    ) -> Result<ColumnFormatShorthand, EtopError> {
+        match self.column_formats.get(column).or_else(|| dataset_formats.get(column)) {
+            Some(column_format) => Ok(column_format.clone()),
+            None => match infer_format_from_name(column, dtype) {
+                Some(column_format) if !self.disable_format_inference => Ok(column_format),
+                _ => super::ui::get_default_format(column.to_string(), dtype.clone()),
+            },
+        }
+    }
+
+    /// current format of column
+    pub fn column_format(&self, column: &str) -> Result<ColumnFormatShorthand, EtopError> {
+        let dataspec = crate::load_dataspec(self.dataset.clone())?;
+        let dataset_formats = dataspec.default_column_formats().unwrap_or_default();
+        let df = self.window_frame()?;
+        let series = df
+            .column(column)
+            .map_err(|_| EtopError::ColumnMissing(format!("no column named {}", column)))?;
+        self.resolve_column_format(&dataset_formats, column, series.dtype())
+    }
+
+    /// override format of column, layered over the dataset's formats so that the other columns
+    /// keep their formats
+    pub fn override_column_format(
+        &mut self,
+        column: &str,
+        column_format: ColumnFormatShorthand,
+    ) -> Result<(), EtopError> {
+        self.require_column(&column.to_string())?;
+        self.raw_units.remove(column);
+        self.column_formats.insert(column.to_string(), column_format.name(column));
+        Ok(())
+    }
+
+    /// remove override of column's format, returning whether it had one
+    pub fn clear_column_format(&mut self, column: &str) -> bool {
+        self.raw_units.remove(column);
+        self.column_formats.remove(column).is_some()
+    }
+
+    /// name of column of the inspected cell
+    pub fn selected_column(&self) -> Option<String> {
+        let (_, column) = self.selected_cell?;
+        let df = self.cache_df.as_ref()?;
+        df.get_column_names().get(column).map(|s| s.to_string())
+    }
+
+    /// change digits of precision of numeric column by delta, clamped to [0, MAX_PRECISION]
+    ///
+    /// non-numeric columns are left unchanged
+    pub fn adjust_precision(&mut self, column: &str, delta: i32) -> Result<(), EtopError> {
+        let mut column_format = self.column_format(column)?;
+        if let CellFormatShorthand::Number(number_format) = &mut column_format.format {
+            let precision = number_format.precision as i64 + delta as i64;
+            number_format.precision = precision.clamp(0, MAX_PRECISION as i64) as usize;
+            self.column_formats.insert(column.to_string(), column_format);
+        }
+        Ok(())
+    }
+
+    /// switch numeric column to the next format type of NUMBER_FORMAT_CYCLE, returning the new
+    /// format type
+    ///
+    /// columns of a format type outside the cycle start over at fixed point, precision and width
+    /// are kept, except that SI shows at least one significant digit, and non-numeric columns are
+    /// left unchanged
+    pub fn cycle_number_format(&mut self, column: &str) -> Result<Option<FormatType>, EtopError> {
+        let mut column_format = self.column_format(column)?;
+        let number_format = match &mut column_format.format {
+            CellFormatShorthand::Number(number_format) => number_format,
+            _ => return Ok(None),
+        };
+        let position = NUMBER_FORMAT_CYCLE.iter().position(|t| *t == number_format.format_type);
+        let format_type = match position {
+            Some(i) => NUMBER_FORMAT_CYCLE[(i + 1) % NUMBER_FORMAT_CYCLE.len()].clone(),
+            None => FormatType::FixedPoint,
+        };
+        if format_type == FormatType::SI {
+            number_format.precision = number_format.precision.max(1);
+        }
+        number_format.format_type = format_type.clone();
+        self.column_formats.insert(column.to_string(), column_format);
+        Ok(Some(format_type))
+    }
+
+    /// toggle numeric column between its unit-converted format and the raw integer, such as wei
+    /// instead of gwei, returning whether the column now shows raw units
+    ///
+    /// the column's previous format is remembered and restored when toggled back, non-numeric
+    /// columns are left unchanged
+    pub fn toggle_raw_units(&mut self, column: &str) -> Result<bool, EtopError> {
+        if let Some(original) = self.raw_units.remove(column) {
+            match original {
+                Some(original) => self.column_formats.insert(column.to_string(), original),
+                None => self.column_formats.remove(column),
+            };
+            return Ok(false)
+        }
+        let column_format = self.column_format(column)?;
+        if !matches!(column_format.format, CellFormatShorthand::Number(_)) {
+            return Ok(false)
+        }
+        let raw = column_format.set_format(NumberFormat::new().precision(0));
+        let original = self.column_formats.insert(column.to_string(), raw);
+        self.raw_units.insert(column.to_string(), original);
+        Ok(true)
+    }
+
+    /// fix the width of each column to its current width, so that columns do not resize as the
+    /// data changes, until unlock_widths
+    pub fn lock_widths(&mut self) -> Result<(), EtopError> {
+        self.locked_widths = None;
+        let df = self.window_frame()?;
+        let fmt = self.frame_format(&df, df.height(), usize::MAX)?;
+        self.locked_widths = Some(fmt.column_widths(&df)?.into_iter().collect());
+        Ok(())
+    }
+
+    /// let columns resize to fit their data again
+    pub fn unlock_widths(&mut self) {
+        self.locked_widths = None;
+    }
+}
+
+/// format of numeric column implied by the suffix of its name
+///
+/// - `*_wei` as ether
+/// - `*_gwei` and `*_per_gas` as gwei
+/// - `*_gas` as a count of gas
+/// - `*_seconds` as a duration
+/// - `timestamp` and `*_timestamp` as a date and time
+pub fn infer_format_from_name(name: &str, dtype: &DataType) -> Option<ColumnFormatShorthand> {
+    if !dtype.is_numeric() {
+        return None
+    }
+    let float_oom = NumberFormat::new().float_oom().precision(1);
+    let number_format = if name == "timestamp" || name.ends_with("_timestamp") {
+        NumberFormat::new().timestamp()
+    } else if name.ends_with("_gwei") {
+        float_oom.suffix(" gwei")
+    } else if name.ends_with("_wei") {
+        float_oom.scale(1e-18).suffix(" ETH")
+    } else if name.ends_with("_per_gas") {
+        float_oom.scale(1e-9).suffix(" gwei")
+    } else if name.ends_with("_gas") {
+        NumberFormat::new().integer_oom().precision(1)
+    } else if name.ends_with("_seconds") {
+        NumberFormat::new().precision(1).suffix("s")
+    } else {
+        return None
+    };
+    let column_format =
+        ColumnFormatShorthand::new().name(name).newline_underscores().set_format(number_format);
+    Some(column_format)
+}
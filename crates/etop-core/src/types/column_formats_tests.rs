@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod column_formats_tests {
+    use crate::{infer_format_from_name, EtopError, EtopState, MAX_PRECISION};
+    use etop_format::{CellFormatShorthand, ColumnFormatShorthand, FormatType, NumberFormat};
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!("name" => ["a", "b"], "value" => [1.23456, 2.5]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    fn precision(state: &EtopState, column: &str) -> usize {
+        match state.column_format(column).unwrap().format {
+            CellFormatShorthand::Number(number_format) => number_format.precision,
+            _ => panic!("not a number format"),
+        }
+    }
+
+    #[test]
+    fn adjust_precision() {
+        let mut state = state();
+        assert_eq!(precision(&state, "value"), 1);
+        state.adjust_precision("value", 2).unwrap();
+        assert_eq!(precision(&state, "value"), 3);
+        state.cache_window(10, 40).unwrap();
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.contains("1.235"));
+
+        // clamped to [0, MAX_PRECISION]
+        state.adjust_precision("value", 100).unwrap();
+        assert_eq!(precision(&state, "value"), MAX_PRECISION);
+        state.cache_window(10, 80).unwrap();
+        state.adjust_precision("value", -100).unwrap();
+        assert_eq!(precision(&state, "value"), 0);
+
+        // non-numeric columns are ignored
+        state.adjust_precision("name", 1).unwrap();
+        assert!(!state.column_formats.contains_key("name"));
+        assert!(state.adjust_precision("missing", 1).is_err());
+    }
+
+    #[test]
+    fn cycle_number_format() {
+        let df = df!("name" => ["a"], "value" => [255u32]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let cycle = |state: &mut EtopState| {
+            let format_type = state.cycle_number_format("value").unwrap().unwrap();
+            state.cache_window(10, 40).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            (format_type, render.split_whitespace().last().unwrap().to_string())
+        };
+
+        // formats outside the cycle start at fixed point, then advance and wrap around
+        assert_eq!(cycle(&mut state), (FormatType::FixedPoint, "255.0".to_string()));
+        assert_eq!(cycle(&mut state), (FormatType::SI, "300".to_string()));
+        assert_eq!(cycle(&mut state), (FormatType::Exponent, "2.6e+02".to_string()));
+        assert_eq!(cycle(&mut state), (FormatType::Hex, "ff".to_string()));
+        assert_eq!(cycle(&mut state), (FormatType::FixedPoint, "255.0".to_string()));
+
+        // non-numeric columns are skipped
+        assert_eq!(state.cycle_number_format("name").unwrap(), None);
+        assert!(!state.column_formats.contains_key("name"));
+    }
+
+    #[test]
+    fn override_column_format() {
+        let df = df!("a" => [1.23456], "b" => [2.34567]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+
+        // only the targeted column changes
+        let three_digits =
+            ColumnFormatShorthand::new().set_format(NumberFormat::new().precision(3));
+        state.override_column_format("a", three_digits).unwrap();
+        assert_eq!(precision(&state, "a"), 3);
+        assert_eq!(state.column_format("a").unwrap().name, "a");
+        assert_eq!(precision(&state, "b"), 1);
+        assert!(!state.column_formats.contains_key("b"));
+        state.cache_window(10, 40).unwrap();
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.contains("1.235") && render.contains("2.3"));
+
+        // unknown columns are rejected
+        let result = state.override_column_format("missing", ColumnFormatShorthand::new());
+        assert!(matches!(result, Err(EtopError::ColumnMissing(_))));
+
+        assert!(state.clear_column_format("a"));
+        assert!(!state.clear_column_format("a"));
+        assert_eq!(precision(&state, "a"), 1);
+    }
+
+    #[test]
+    fn selected_column() {
+        let mut state = state();
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.selected_column(), None);
+        state.toggle_inspect();
+        state.move_selected_cell(0, 1);
+        assert_eq!(state.selected_column(), Some("value".to_string()));
+    }
+
+    #[test]
+    fn toggle_raw_units() {
+        let df = df!("gas_price" => [25_000_000_000u64, 1_500_000_000]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let gwei = NumberFormat::new().scale(1e-9).precision(1).suffix(" gwei");
+        let gwei = ColumnFormatShorthand::new().name("gas_price").set_format(gwei);
+        state.column_formats.insert("gas_price".to_string(), gwei);
+        let number_format = |state: &EtopState| match state.column_format("gas_price").unwrap() {
+            ColumnFormatShorthand { format: CellFormatShorthand::Number(fmt), .. } => fmt,
+            _ => panic!("not a number format"),
+        };
+        let cells = |state: &mut EtopState| -> Vec<String> {
+            state.cache_window(10, 40).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            render.lines().skip(2).map(|line| line.trim().to_string()).collect()
+        };
+        let original = number_format(&state);
+        assert_eq!(cells(&mut state), vec!["25.0 gwei", "1.5 gwei"]);
+
+        assert!(state.toggle_raw_units("gas_price").unwrap());
+        assert_eq!(number_format(&state), NumberFormat::new().precision(0));
+        assert_eq!(cells(&mut state), vec!["25000000000", "1500000000"]);
+
+        assert!(!state.toggle_raw_units("gas_price").unwrap());
+        assert_eq!(number_format(&state), original);
+        assert_eq!(cells(&mut state), vec!["25.0 gwei", "1.5 gwei"]);
+
+        // columns without an override return to the default format
+        let mut state = self::state();
+        assert!(state.toggle_raw_units("value").unwrap());
+        assert!(!state.toggle_raw_units("value").unwrap());
+        assert!(state.column_formats.is_empty());
+        assert!(!state.toggle_raw_units("name").unwrap());
+    }
+
+    #[test]
+    fn lock_widths() {
+        let mut state = state();
+        let widths = |state: &mut EtopState| {
+            state.cache_window(10, 80).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            render.lines().next().unwrap().chars().count()
+        };
+        let refresh = |state: &mut EtopState, value: f64| {
+            let df = df!("name" => ["a", "b"], "value" => [value, 2.5]).unwrap();
+            state.warehouse.data.insert("test_blocks".to_string(), df);
+        };
+        let unlocked = widths(&mut state);
+
+        // widths stay fixed across refreshes while locked
+        state.lock_widths().unwrap();
+        assert_eq!(state.locked_widths.as_ref().unwrap().len(), 2);
+        refresh(&mut state, 123_456.5);
+        assert_eq!(widths(&mut state), unlocked);
+
+        // horizontal scrolling keeps the locked widths
+        let value_width = state.locked_widths.as_ref().unwrap()["value"];
+        state.scroll_columns(1);
+        assert_eq!(widths(&mut state), value_width);
+        state.scroll_columns(-1);
+
+        // columns resize again once unlocked
+        state.unlock_widths();
+        assert!(widths(&mut state) > unlocked);
+    }
+
+    #[test]
+    fn infer_formats() {
+        let number_format = |name: &str, dtype: DataType| match infer_format_from_name(name, &dtype)
+            .map(|column_format| column_format.format)
+        {
+            Some(CellFormatShorthand::Number(number_format)) => Some(number_format),
+            _ => None,
+        };
+        let float_oom = NumberFormat::new().float_oom().precision(1);
+        assert_eq!(
+            number_format("value_wei", DataType::UInt64),
+            Some(float_oom.clone().scale(1e-18).suffix(" ETH"))
+        );
+        assert_eq!(
+            number_format("priority_fee_gwei", DataType::Float64),
+            Some(float_oom.clone().suffix(" gwei"))
+        );
+        assert_eq!(
+            number_format("base_fee_per_gas", DataType::UInt64),
+            Some(float_oom.scale(1e-9).suffix(" gwei"))
+        );
+        assert_eq!(
+            number_format("blob_gas", DataType::UInt64),
+            Some(NumberFormat::new().integer_oom().precision(1))
+        );
+        assert_eq!(
+            number_format("block_timestamp", DataType::UInt32),
+            Some(NumberFormat::new().timestamp())
+        );
+        assert_eq!(
+            number_format("wait_seconds", DataType::Float64),
+            Some(NumberFormat::new().precision(1).suffix("s"))
+        );
+
+        // other names and non-numeric columns are not inferred
+        assert_eq!(number_format("gas_used", DataType::UInt64), None);
+        assert_eq!(number_format("value_wei", DataType::Utf8), None);
+        assert_eq!(
+            infer_format_from_name("value_wei", &DataType::UInt64).unwrap().name,
+            "value_wei"
+        );
+    }
+
+    #[test]
+    fn inferred_format_fallback() {
+        let df = df!("fee_gwei" => [1.5]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let render = |state: &mut EtopState| {
+            state.cache_window(10, 40).unwrap();
+            state.cache_df_render.clone().unwrap().lines().last().unwrap().trim().to_string()
+        };
+        assert_eq!(render(&mut state), "1.5 gwei");
+
+        // overrides take precedence over inferred formats
+        let fmt = ColumnFormatShorthand::new().set_format(NumberFormat::new().precision(2));
+        state.override_column_format("fee_gwei", fmt).unwrap();
+        assert_eq!(render(&mut state), "1.50");
+        state.clear_column_format("fee_gwei");
+
+        // inference can be disabled
+        state.disable_format_inference = true;
+        assert_eq!(render(&mut state), "1.5");
+    }
+}
@@ -0,0 +1,319 @@
+#[cfg(test)]
+#[path = "command_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState, FilterExpr, SortKey};
+use etop_format::BorderStyle;
+use polars::prelude::*;
+
+/// names of commands, for listing in error messages
+const COMMAND_NAMES: [&str; 14] = [
+    "sort",
+    "filter",
+    "hide",
+    "show",
+    "export",
+    "goto",
+    "limit",
+    "highlight",
+    "pin",
+    "unpin",
+    "preset",
+    "note",
+    "export-notes",
+    "border",
+];
+
+/// command entered at the command prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// sort by column, e.g. `sort gas_used desc`
+    Sort(SortKey),
+    /// add filter, e.g. `filter value > 0`
+    Filter(String),
+    /// hide column, e.g. `hide gas_price`
+    Hide(String),
+    /// show column, e.g. `show gas_price`
+    Show(String),
+    /// export displayed data to a csv, json, or parquet file, e.g. `export out.csv`, or
+    /// `export out.csv row-numbers display-names` to include a leading column of row numbers and
+    /// name columns as in the table's header
+    Export(String, ExportOpts),
+    /// move window to end at block, e.g. `goto 18000000`, `goto latest-100`, or `goto +50`, or
+    /// to span a range of blocks, e.g. `goto latest-100:latest`
+    Goto(String),
+    /// limit number of rows shown, e.g. `limit 20`, or `limit none` to remove the limit
+    Limit(Option<usize>),
+    /// highlight top ranked rows of column, e.g. `highlight gas_used`, or `highlight none`
+    Highlight(Option<String>),
+    /// always show column at the right of the table, e.g. `pin value`
+    Pin(String),
+    /// stop pinning column, e.g. `unpin value`
+    Unpin(String),
+    /// show the columns of a named preset, e.g. `preset compact`
+    Preset(String),
+    /// annotate inspected cell, e.g. `note suspicious`, or `note` to only mark it
+    Note(String),
+    /// export annotations to a json file, e.g. `export-notes notes.json`
+    ExportNotes(String),
+    /// draw lines between and around columns, e.g. `border unicode`, `border unicode ascii-only`
+    /// to draw it with ascii characters, or `border default` for the default separators
+    Border(Option<BorderStyle>, bool),
+}
+
+impl Command {
+    /// whether command acts on the table, rather than on the window or annotations
+    pub fn acts_on_table(&self) -> bool {
+        !matches!(self, Command::Goto(_) | Command::ExportNotes(_))
+    }
+}
+
+/// names given to the columns of exported data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameStyle {
+    /// names of the columns of the data
+    #[default]
+    Raw,
+    /// names shown in the table's header, joined onto one line
+    Display,
+}
+
+/// options of export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExportOpts {
+    /// include a leading column of row numbers
+    pub row_numbers: bool,
+    /// names of exported columns
+    pub name_style: NameStyle,
+}
+
+/// parse command, e.g. `sort gas_used desc`
+pub fn parse_command(command: &str) -> Result<Command, EtopError> {
+    let command = command.trim().trim_start_matches(':').trim_start();
+    let (name, args) = match command.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (command, ""),
+    };
+    let usage = |usage: &str| EtopError::ParseError(format!("usage: {} {}", name, usage));
+    let single = |usage_str: &str| {
+        if args.is_empty() || args.contains(char::is_whitespace) {
+            Err(usage(usage_str))
+        } else {
+            Ok(args.to_string())
+        }
+    };
+    match name {
+        "sort" => Ok(Command::Sort(args.parse()?)),
+        "filter" if args.is_empty() => Err(usage("<expression>")),
+        "filter" => {
+            args.parse::<FilterExpr>()?;
+            Ok(Command::Filter(args.to_string()))
+        }
+        "hide" => Ok(Command::Hide(single("<column>")?)),
+        "show" => Ok(Command::Show(single("<column>")?)),
+        "export" => {
+            let usage = || usage("<path> [row-numbers] [display-names]");
+            let mut tokens = args.split_whitespace();
+            let path = tokens.next().ok_or_else(usage)?;
+            let mut opts = ExportOpts::default();
+            for token in tokens {
+                match token {
+                    "row-numbers" => opts.row_numbers = true,
+                    "display-names" => opts.name_style = NameStyle::Display,
+                    _ => return Err(usage()),
+                }
+            }
+            Ok(Command::Export(path.to_string(), opts))
+        }
+        "goto" => {
+            let target = single("<block>|<start>:<end>|+<blocks>|-<blocks>")?;
+            // blocks are checked against the latest block when executed
+            match target.strip_prefix(['+', '-']) {
+                Some(offset) => {
+                    crate::parse_block_offset(offset)?;
+                }
+                None => {
+                    crate::parse_block_range(&target, u64::MAX)?;
+                }
+            }
+            Ok(Command::Goto(target))
+        }
+        "limit" => match single("<rows>|none")?.as_str() {
+            "none" => Ok(Command::Limit(None)),
+            rows => match rows.parse() {
+                Ok(rows) => Ok(Command::Limit(Some(rows))),
+                Err(_) => Err(EtopError::ParseError(format!("invalid row count: {}", rows))),
+            },
+        },
+        "highlight" => match single("<column>|none")?.as_str() {
+            "none" => Ok(Command::Highlight(None)),
+            column => Ok(Command::Highlight(Some(column.to_string()))),
+        },
+        "pin" => Ok(Command::Pin(single("<column>")?)),
+        "unpin" => Ok(Command::Unpin(single("<column>")?)),
+        "preset" => Ok(Command::Preset(single("<name>")?)),
+        "note" => Ok(Command::Note(args.to_string())),
+        "export-notes" => Ok(Command::ExportNotes(single("<path>")?)),
+        "border" => {
+            let usage = || usage("<none|ascii|unicode|minimal|default> [ascii-only]");
+            let style = match args.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [style] => (*style, false),
+                [style, "ascii-only"] => (*style, true),
+                _ => return Err(usage()),
+            };
+            match style {
+                ("default", ascii) => Ok(Command::Border(None, ascii)),
+                (style, ascii) => match style.parse() {
+                    Ok(style) => Ok(Command::Border(Some(style), ascii)),
+                    Err(_) => Err(usage()),
+                },
+            }
+        }
+        "" => Err(EtopError::ParseError("empty command".to_string())),
+        name => Err(EtopError::ParseError(format!(
+            "unknown command: {} (expected one of {})",
+            name,
+            COMMAND_NAMES.join(", ")
+        ))),
+    }
+}
+
+impl EtopState {
+    /// apply command to current view
+    ///
+    /// commands acting on the table are refused while panes are shown in its place
+    pub fn execute_command(&mut self, command: Command) -> Result<(), EtopError> {
+        if self.panes_shown() && command.acts_on_table() {
+            return Err(EtopError::ArgumentError(
+                "command not available while panes are shown".to_string(),
+            ))
+        }
+        match command {
+            Command::Sort(key) => {
+                self.require_column(&key.column)?;
+                self.layout.sort = vec![key];
+            }
+            Command::Filter(filter) => self.layout.filters.push(filter),
+            Command::Hide(column) => {
+                let mut columns = self.visible_columns()?;
+                if !columns.contains(&column) {
+                    return Err(EtopError::ColumnMissing(format!("column {} not shown", column)))
+                }
+                columns.retain(|c| c != &column);
+                self.layout.columns = Some(columns);
+            }
+            Command::Show(column) => {
+                self.require_column(&column)?;
+                let mut columns = self.visible_columns()?;
+                if !columns.contains(&column) {
+                    columns.push(column);
+                }
+                self.layout.columns = Some(columns);
+            }
+            Command::Export(path, opts) => self.export(&path, opts)?,
+            Command::Goto(target) => self.goto(&target)?,
+            Command::Limit(limit) => self.layout.limit = limit,
+            Command::Highlight(column) => {
+                if let Some(column) = column.as_ref() {
+                    self.require_column(column)?;
+                }
+                self.layout.highlight = column;
+            }
+            Command::Pin(column) => self.pin_column(column)?,
+            Command::Unpin(column) => {
+                if !self.unpin_column(&column) {
+                    return Err(EtopError::ColumnMissing(format!("column {} not pinned", column)))
+                }
+            }
+            Command::Preset(name) => self.apply_preset(&name)?,
+            Command::Note(note) => self.annotate_selected(note)?,
+            Command::ExportNotes(path) => self.export_annotations(&path)?,
+            Command::Border(style, ascii) => {
+                self.layout.border_style = style;
+                self.layout.ascii_borders = ascii;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn require_column(&self, column: &String) -> Result<(), EtopError> {
+        if self.available_columns()?.contains(column) {
+            Ok(())
+        } else {
+            Err(EtopError::ColumnMissing(format!("no column named {}", column)))
+        }
+    }
+
+    fn visible_columns(&self) -> Result<Vec<String>, EtopError> {
+        match self.displayed_columns()? {
+            Some(columns) => Ok(columns),
+            None => self.available_columns(),
+        }
+    }
+
+    /// write displayed data of current window to file, with format chosen by file extension
+    ///
+    /// row numbers are only written when requested, even if shown in the table
+    pub fn export(&self, path: &str, opts: ExportOpts) -> Result<(), EtopError> {
+        let mut df = self.window_frame()?;
+        if opts.name_style == NameStyle::Display {
+            let names = self.export_names(&df, opts.row_numbers)?;
+            df.set_column_names(&names)?;
+        }
+        if opts.row_numbers {
+            df = super::ui::with_row_numbers(df)?;
+        }
+        let extension = std::path::Path::new(path).extension().and_then(|e| e.to_str());
+        let file = |path: &str| {
+            std::fs::File::create(path).map_err(|e| EtopError::CouldNotOpenFile(e.to_string()))
+        };
+        match extension {
+            Some("csv") => CsvWriter::new(file(path)?).finish(&mut df)?,
+            Some("json") => JsonWriter::new(file(path)?).finish(&mut df)?,
+            Some("parquet") => {
+                ParquetWriter::new(file(path)?).finish(&mut df)?;
+            }
+            _ => {
+                return Err(EtopError::ArgumentError(format!(
+                    "cannot export to {}, use a .csv, .json, or .parquet path",
+                    path
+                )))
+            }
+        };
+        Ok(())
+    }
+
+    /// display names of columns of frame, on one line, for headers of exported data
+    ///
+    /// whitespace, such as the newlines of multiline headers, is collapsed into single spaces.
+    /// a column keeps its raw name if its display name is empty or already taken, and a name
+    /// that is still taken, including the row number column when exported, gets a numeric
+    /// suffix, e.g. `gas_used_2`
+    fn export_names(&self, df: &DataFrame, row_numbers: bool) -> Result<Vec<String>, EtopError> {
+        let dataspec = self.dataspec()?;
+        let dataset_formats = dataspec.default_column_formats().unwrap_or_default();
+        let mut taken: Vec<String> = Vec::new();
+        if row_numbers {
+            taken.push(super::ui::ROW_NUMBER_COLUMN.to_string());
+        }
+        let mut names: Vec<String> = Vec::new();
+        for (column, dtype) in df.schema().iter() {
+            let column_format = self.resolve_column_format(&dataset_formats, column, dtype)?;
+            let name = column_format.display_name.split_whitespace().collect::<Vec<_>>().join(" ");
+            let name = match name.is_empty() || taken.contains(&name) {
+                true => column.to_string(),
+                false => name,
+            };
+            let name = match taken.contains(&name) {
+                true => (2..)
+                    .map(|n| format!("{}_{}", name, n))
+                    .find(|name| !taken.contains(name))
+                    .unwrap_or(name),
+                false => name,
+            };
+            taken.push(name.clone());
+            names.push(name);
+        }
+        Ok(names)
+    }
+}
@@ -0,0 +1,306 @@
+#[cfg(test)]
+mod command_tests {
+    use crate::{
+        parse_command, Command, EtopState, ExportOpts,
+        NameStyle::{Display, Raw},
+        SortKey,
+    };
+    use etop_format::{BorderStyle, ColumnFormatShorthand};
+    use polars::prelude::*;
+
+    fn sort(column: &str, descending: bool) -> Command {
+        Command::Sort(SortKey { column: column.to_string(), descending })
+    }
+
+    fn state() -> EtopState {
+        let df = df!("block_number" => [1u32, 2, 3], "gas_used" => [30u64, 10, 20]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    fn error(command: &str) -> String {
+        format!("{:?}", parse_command(command).unwrap_err())
+    }
+
+    #[test]
+    fn parse_sort() {
+        assert_eq!(parse_command("sort gas_used desc").unwrap(), sort("gas_used", true));
+        assert_eq!(parse_command(":sort gas_used").unwrap(), sort("gas_used", false));
+        assert_eq!(parse_command("  sort  gas_used   asc ").unwrap(), sort("gas_used", false));
+        assert!(parse_command("sort").is_err());
+        assert!(error("sort gas_used sideways").contains("expected asc or desc"));
+        assert!(parse_command("sort a b c").is_err());
+    }
+
+    #[test]
+    fn parse_filter() {
+        assert_eq!(
+            parse_command(":filter value > 0").unwrap(),
+            Command::Filter("value > 0".to_string())
+        );
+        assert!(error("filter").contains("usage: filter <expression>"));
+        assert!(error("filter value").contains("invalid filter"));
+    }
+
+    #[test]
+    fn parse_hide_show() {
+        assert_eq!(parse_command("hide gas_used").unwrap(), Command::Hide("gas_used".into()));
+        assert_eq!(parse_command("show gas_used").unwrap(), Command::Show("gas_used".into()));
+        assert!(error("hide").contains("usage: hide <column>"));
+        assert!(error("show a b").contains("usage: show <column>"));
+    }
+
+    #[test]
+    fn parse_export() {
+        let export = |path: &str, row_numbers, name_style| {
+            Command::Export(path.to_string(), ExportOpts { row_numbers, name_style })
+        };
+        assert_eq!(parse_command("export out.csv").unwrap(), export("out.csv", false, Raw));
+        assert_eq!(
+            parse_command("export out.csv row-numbers").unwrap(),
+            export("out.csv", true, Raw)
+        );
+        assert_eq!(
+            parse_command("export out.csv display-names row-numbers").unwrap(),
+            export("out.csv", true, Display)
+        );
+        let usage = "usage: export <path> [row-numbers] [display-names]";
+        assert!(error("export").contains(usage));
+        assert!(error("export out.csv numbers").contains(usage));
+    }
+
+    #[test]
+    fn parse_goto() {
+        let goto = |target: &str| Command::Goto(target.to_string());
+        assert_eq!(parse_command("goto 18000000").unwrap(), goto("18000000"));
+        assert_eq!(parse_command("goto 18_000_000").unwrap(), goto("18_000_000"));
+        assert_eq!(parse_command("goto latest-100:latest").unwrap(), goto("latest-100:latest"));
+        assert_eq!(parse_command("goto -5").unwrap(), goto("-5"));
+        assert_eq!(parse_command("goto +50").unwrap(), goto("+50"));
+        assert!(error("goto lastest").contains("invalid block number: lastest"));
+        assert!(error("goto 200:100").contains("ends before it starts"));
+        assert!(error("goto +x").contains("invalid block offset: x"));
+        assert!(error("goto").contains("usage: goto <block>"));
+    }
+
+    #[test]
+    fn parse_limit() {
+        assert_eq!(parse_command("limit 20").unwrap(), Command::Limit(Some(20)));
+        assert_eq!(parse_command("limit none").unwrap(), Command::Limit(None));
+        assert!(error("limit many").contains("invalid row count: many"));
+        assert!(error("limit").contains("usage: limit <rows>|none"));
+    }
+
+    #[test]
+    fn parse_highlight() {
+        assert_eq!(
+            parse_command("highlight gas_used").unwrap(),
+            Command::Highlight(Some("gas_used".into()))
+        );
+        assert_eq!(parse_command("highlight none").unwrap(), Command::Highlight(None));
+        assert!(error("highlight").contains("usage: highlight <column>|none"));
+    }
+
+    #[test]
+    fn parse_pin() {
+        assert_eq!(parse_command("pin value").unwrap(), Command::Pin("value".into()));
+        assert_eq!(parse_command("unpin value").unwrap(), Command::Unpin("value".into()));
+        assert!(error("pin").contains("usage: pin <column>"));
+        assert!(error("unpin a b").contains("usage: unpin <column>"));
+    }
+
+    #[test]
+    fn parse_preset() {
+        assert_eq!(parse_command("preset compact").unwrap(), Command::Preset("compact".into()));
+        assert!(error("preset").contains("usage: preset <name>"));
+    }
+
+    #[test]
+    fn parse_note() {
+        assert_eq!(
+            parse_command("note looks  suspicious ").unwrap(),
+            Command::Note("looks  suspicious".into())
+        );
+        assert_eq!(parse_command("note").unwrap(), Command::Note("".into()));
+        assert_eq!(
+            parse_command("export-notes notes.json").unwrap(),
+            Command::ExportNotes("notes.json".into())
+        );
+        assert!(error("export-notes").contains("usage: export-notes <path>"));
+    }
+
+    #[test]
+    fn border() {
+        let border = Command::Border;
+        assert_eq!(
+            parse_command("border unicode").unwrap(),
+            border(Some(BorderStyle::Unicode), false)
+        );
+        assert_eq!(
+            parse_command("border minimal ascii-only").unwrap(),
+            border(Some(BorderStyle::Minimal), true)
+        );
+        assert_eq!(parse_command("border default").unwrap(), border(None, false));
+        assert!(error("border dotted").contains("usage: border"));
+        assert!(error("border").contains("usage: border"));
+
+        // style is kept in the layout and drawn by the table
+        let mut state = state();
+        state.execute_command(parse_command("border unicode ascii-only").unwrap()).unwrap();
+        assert_eq!(state.layout.border_style, Some(BorderStyle::Unicode));
+        assert!(state.layout.ascii_borders);
+        state.cache_window(10, 40).unwrap();
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.starts_with('+'));
+        assert!(!render.contains('┌'));
+        state.execute_command(parse_command("border unicode").unwrap()).unwrap();
+        state.cache_window(10, 40).unwrap();
+        assert!(state.cache_df_render.clone().unwrap().starts_with('┌'));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        assert!(error("frobnicate").contains("unknown command: frobnicate (expected one of sort"));
+        assert!(error(":").contains("empty command"));
+        assert!(error("").contains("empty command"));
+        assert!(error("SORT x").contains("unknown command"));
+    }
+
+    #[test]
+    fn execute() {
+        let mut state = state();
+        state.execute_command(parse_command("sort gas_used desc").unwrap()).unwrap();
+        assert_eq!(
+            state.layout.sort,
+            vec![SortKey { column: "gas_used".to_string(), descending: true }]
+        );
+        assert!(state.execute_command(parse_command("sort missing").unwrap()).is_err());
+
+        state.execute_command(parse_command("filter gas_used > 10").unwrap()).unwrap();
+        assert_eq!(state.layout.filters, vec!["gas_used > 10"]);
+
+        state.execute_command(parse_command("hide block_number").unwrap()).unwrap();
+        assert_eq!(state.window_frame().unwrap().get_column_names(), vec!["gas_used"]);
+        assert!(state.execute_command(parse_command("hide block_number").unwrap()).is_err());
+        state.execute_command(parse_command("show block_number").unwrap()).unwrap();
+        assert_eq!(
+            state.window_frame().unwrap().get_column_names(),
+            vec!["gas_used", "block_number"]
+        );
+        assert!(state.execute_command(parse_command("show missing").unwrap()).is_err());
+
+        state.execute_command(parse_command("limit 1").unwrap()).unwrap();
+        assert_eq!(state.window_frame().unwrap().height(), 1);
+        state.execute_command(parse_command("limit none").unwrap()).unwrap();
+        assert_eq!(state.window_frame().unwrap().height(), 2);
+
+        state.execute_command(parse_command("goto 100").unwrap()).unwrap();
+        assert_eq!(state.window.end_block, Some(100));
+    }
+
+    #[test]
+    fn export() {
+        let state = state();
+        let path = std::env::temp_dir().join(format!("etop_export_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let opts = |row_numbers, name_style| ExportOpts { row_numbers, name_style };
+        state.export(&path, opts(false, Raw)).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().next(), Some("block_number,gas_used"));
+        assert_eq!(contents.lines().count(), 4);
+
+        state.export(&path, opts(true, Raw)).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().next(), Some("#,block_number,gas_used"));
+        assert_eq!(contents.lines().nth(1).and_then(|line| line.split(',').next()), Some("1"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(state.export("out.xlsx", ExportOpts::default()).is_err());
+    }
+
+    #[test]
+    fn export_display_names() {
+        let mut state = state();
+        let path = std::env::temp_dir().join(format!("etop_names_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let header = |state: &EtopState, name_style| {
+            let opts = ExportOpts { row_numbers: false, name_style };
+            state.export(&path, opts).unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            contents.lines().next().unwrap().to_string()
+        };
+        assert_eq!(header(&state, Raw), "block_number,gas_used");
+        // default headers break names at underscores
+        assert_eq!(header(&state, Display), "block number,gas used");
+
+        // newlines are joined, and headers with delimiters are quoted
+        let gas = ColumnFormatShorthand::new().name("gas_used").display_name("gas,\n\"used\"");
+        state.override_column_format("gas_used", gas).unwrap();
+        assert_eq!(header(&state, Display), "block number,\"gas, \"\"used\"\"\"");
+        assert_eq!(header(&state, Raw), "block_number,gas_used");
+
+        // a display name already taken keeps the raw name
+        let gas = ColumnFormatShorthand::new().name("gas_used").display_name("block\nnumber");
+        state.override_column_format("gas_used", gas).unwrap();
+        assert_eq!(header(&state, Display), "block number,gas_used");
+
+        let opts = ExportOpts { row_numbers: false, name_style: Display };
+        let json_path = path.replace(".csv", ".json");
+        state.export(&json_path, opts).unwrap();
+        let contents = std::fs::read_to_string(&json_path).unwrap();
+        assert_eq!(contents.lines().next(), Some("{\"block number\":1,\"gas_used\":30}"));
+
+        // names still taken, by raw names or the row number column, get a numeric suffix
+        let block = ColumnFormatShorthand::new().name("block_number").display_name("gas_used");
+        state.override_column_format("block_number", block).unwrap();
+        let gas = ColumnFormatShorthand::new().name("gas_used").display_name("gas_used");
+        state.override_column_format("gas_used", gas).unwrap();
+        assert_eq!(header(&state, Display), "gas_used,gas_used_2");
+        let gas = ColumnFormatShorthand::new().name("gas_used").display_name("#");
+        state.override_column_format("gas_used", gas).unwrap();
+        let opts = ExportOpts { row_numbers: true, name_style: Display };
+        state.export(&path, opts).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().next(), Some("#,gas_used,gas_used_2"));
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&json_path).unwrap();
+    }
+
+    #[test]
+    fn show_available_column() {
+        let blocks = df!(
+            "block_number" => [1u32, 2],
+            "block_hash" => [vec![1u8; 32], vec![2u8; 32]],
+            "timestamp" => [100u32, 112],
+            "gas_used" => [30u64, 10],
+            "base_fee_per_gas" => [7u64, 8],
+            "author" => [vec![3u8; 20], vec![4u8; 20]],
+            "nonce" => [0u64, 0],
+        )
+        .unwrap();
+        let txs = df!("block_number" => [1u32, 1, 2]).unwrap();
+        let mut state = EtopState { dataset: "blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("blocks".to_string(), blocks);
+        state.warehouse.data.insert("transactions".to_string(), txs);
+
+        // available columns follow the dataset's order, skipping undeclared and absent ones
+        let available = state.available_columns().unwrap();
+        let expected = [
+            "block_number",
+            "block_hash",
+            "timestamp",
+            "n_txs",
+            "gas_used",
+            "base_fee_per_gas",
+            "author",
+        ];
+        assert_eq!(available, expected);
+
+        // available but not default columns are hidden until shown
+        assert!(!state.window_frame().unwrap().get_column_names().contains(&"block_hash"));
+        state.execute_command(parse_command("show block_hash").unwrap()).unwrap();
+        assert_eq!(state.window_frame().unwrap().get_column_names().last(), Some(&"block_hash"));
+        assert!(state.execute_command(parse_command("show nonce").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+#[path = "datasource_tests.rs"]
+mod tests;
+
+use crate::{DatasetQuery, EtopError};
+use polars::prelude::*;
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+/// source that data queries are fetched from
+#[async_trait::async_trait]
+pub trait DataSource: Send + Sync {
+    /// fetch data of query
+    async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError>;
+
+    /// chain id of the network that data is fetched from, if known
+    fn chain_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for std::sync::Arc<cryo_freeze::Source> {
+    async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+        query.query(self.clone()).await
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        Some(self.as_ref().chain_id)
+    }
+}
+
+/// function that waits out the delay before a retry
+pub type Sleep = fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// schedule of retries of failed fetches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// number of retries after the first attempt
+    pub max_retries: u32,
+    /// delay before first retry, doubled for each further retry
+    pub base_delay: Duration,
+    /// waits out delays, replaceable so that retries can run without real time passing
+    pub sleep: Sleep,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(500), sleep: tokio_sleep }
+    }
+}
+
+/// sleep on the tokio timer
+pub fn tokio_sleep(delay: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+    Box::pin(tokio::time::sleep(delay))
+}
+
+impl RetryPolicy {
+    /// delay before given retry, counting from 0
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(retry))
+    }
+}
+
+/// source that retries transient errors of another source with exponential backoff, and
+/// returns permanent errors immediately
+pub struct RetryingSource<S> {
+    /// source being retried
+    pub source: S,
+    /// retry schedule
+    pub policy: RetryPolicy,
+}
+
+#[async_trait::async_trait]
+impl<S: DataSource> DataSource for RetryingSource<S> {
+    async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+        let mut retry = 0;
+        loop {
+            match self.source.fetch(query).await {
+                Err(e) if e.is_transient() && retry < self.policy.max_retries => {
+                    (self.policy.sleep)(self.policy.delay(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.source.chain_id()
+    }
+}
+
+/// fetch queries in background tasks, passing each result to `on_result` as soon as it arrives
+///
+/// returns without waiting for any fetch, so a render loop can keep drawing its last frame
+/// while fetches are in flight
+pub fn spawn_fetches<S, F>(
+    source: Arc<S>,
+    queries: Vec<DatasetQuery>,
+    on_result: F,
+) -> Vec<tokio::task::JoinHandle<()>>
+where
+    S: DataSource + ?Sized + 'static,
+    F: Fn(DatasetQuery, Result<DataFrame, EtopError>) + Clone + Send + 'static,
+{
+    queries
+        .into_iter()
+        .map(|query| {
+            let source = source.clone();
+            let on_result = on_result.clone();
+            tokio::spawn(async move {
+                let result = source.fetch(&query).await;
+                on_result(query, result);
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod datasource_tests {
+    use crate::{
+        spawn_fetches, DataSource, DatasetQuery, EtopError, InputDataset, RetryPolicy,
+        RetryingSource,
+    };
+    use polars::prelude::*;
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// source that answers only once all of its fetches are in flight, failing for empty block
+    /// lists
+    struct BarrierSource {
+        barrier: tokio::sync::Barrier,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for BarrierSource {
+        async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+            self.barrier.wait().await;
+            match query {
+                DatasetQuery::Block(_, blocks) if !blocks.is_empty() => {
+                    Ok(df!("block_number" => blocks.clone())?)
+                }
+                _ => Err(EtopError::MissingData("no blocks".to_string())),
+            }
+        }
+    }
+
+    fn query(blocks: Vec<u32>) -> DatasetQuery {
+        DatasetQuery::Block(InputDataset::Raw("blocks".to_string()), blocks)
+    }
+
+    #[tokio::test]
+    async fn fetch_does_not_block() {
+        let source = Arc::new(BarrierSource { barrier: tokio::sync::Barrier::new(3) });
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let queries = vec![query(vec![1, 2]), query(vec![]), query(vec![3])];
+
+        let handles = spawn_fetches(source, queries, move |query, result| {
+            let _ = tx.send((query, result.map(|df| df.height())));
+        });
+        assert!(rx.try_recv().is_err());
+
+        // fetches run concurrently, or none could pass the barrier
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let mut heights = vec![];
+        while let Ok((query, result)) = rx.try_recv() {
+            match result {
+                Ok(height) => heights.push(height),
+                Err(_) => assert!(matches!(query, DatasetQuery::Block(_, b) if b.is_empty())),
+            }
+        }
+        heights.sort();
+        assert_eq!(heights, vec![1, 2]);
+    }
+
+    /// source failing with given errors before succeeding
+    struct FlakySource {
+        errors: Mutex<Vec<String>>,
+        attempts: Mutex<usize>,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for FlakySource {
+        async fn fetch(&self, _query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+            *self.attempts.lock().unwrap() += 1;
+            match self.errors.lock().unwrap().pop() {
+                Some(message) => Err(EtopError::classify_rpc(message)),
+                None => Ok(df!("block_number" => [1u32])?),
+            }
+        }
+    }
+
+    /// delays waited out by record_sleep
+    static DELAYS: Mutex<Vec<Duration>> = Mutex::new(Vec::new());
+
+    /// record delay instead of waiting it out
+    fn record_sleep(delay: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        DELAYS.lock().unwrap().push(delay);
+        Box::pin(std::future::ready(()))
+    }
+
+    fn flaky(errors: &[&str], max_retries: u32) -> RetryingSource<FlakySource> {
+        let errors = errors.iter().map(|e| e.to_string()).collect();
+        let source = FlakySource { errors: Mutex::new(errors), attempts: Mutex::new(0) };
+        let base_delay = Duration::from_millis(100);
+        let policy = RetryPolicy { max_retries, base_delay, sleep: record_sleep };
+        RetryingSource { source, policy }
+    }
+
+    fn recorded_delays() -> Vec<u128> {
+        DELAYS.lock().unwrap().drain(..).map(|delay| delay.as_millis()).collect()
+    }
+
+    #[test]
+    fn classify_errors() {
+        let transient = |message: &str| EtopError::classify_rpc(message.to_string()).is_transient();
+        assert!(transient("request timed out"));
+        assert!(transient("Timeout after 30s"));
+        assert!(transient("HTTP error 429 Too Many Requests"));
+        assert!(transient("status code: 503"));
+        assert!(!transient("status code: 401"));
+        assert!(!transient("invalid params: block 42"));
+        assert!(transient("HTTP/1.1 502 Bad Gateway"));
+        assert!(transient(r#"{"jsonrpc":"2.0","error":{"code":-32005,"message":"limit"}}"#));
+        assert!(!transient(r#"{"error":{"code":-32602,"message":"invalid params"}}"#));
+        assert!(!transient("block 502 not found"));
+        assert!(!transient("header not found for block 18000503"));
+        assert!(!EtopError::MissingData("timeout".to_string()).is_transient());
+        assert!(!EtopError::ConnectionError("connection timed out".to_string()).is_transient());
+    }
+
+    #[test]
+    fn classify_collect_errors() {
+        use cryo_freeze::CollectError;
+        use ethers::providers::{HttpClientError, JsonRpcError, ProviderError};
+
+        let response = |code: i64| {
+            let error = JsonRpcError { code, message: "error".to_string(), data: None };
+            let error = HttpClientError::JsonRpcError(error);
+            CollectError::ProviderError(ProviderError::JsonRpcClientError(Box::new(error)))
+        };
+        let transient = |error: CollectError| EtopError::classify_collect(error).is_transient();
+        assert!(transient(response(-32005)));
+        assert!(transient(response(429)));
+        assert!(!transient(response(-32602)));
+        assert!(transient(CollectError::TooManyRequestsError));
+        assert!(transient(CollectError::RPCError("request timed out".to_string())));
+        assert!(!transient(CollectError::RPCError("could not get chain_id".to_string())));
+        assert!(!transient(CollectError::BadSchemaError));
+        assert!(!transient(CollectError::CollectError("status 503".to_string())));
+    }
+
+    #[test]
+    fn backoff_schedule() {
+        let base_delay = Duration::from_millis(100);
+        let policy = RetryPolicy { max_retries: 4, base_delay, ..Default::default() };
+        let delays: Vec<u128> = (0..4).map(|retry| policy.delay(retry).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800]);
+        assert!(policy.delay(200) >= policy.delay(31));
+    }
+
+    #[tokio::test]
+    async fn retry_transient_errors() {
+        let query = query(vec![1]);
+
+        // transient errors are retried until success
+        let source = flaky(&["status 502", "timed out"], 3);
+        assert!(source.fetch(&query).await.is_ok());
+        assert_eq!(*source.source.attempts.lock().unwrap(), 3);
+        assert_eq!(recorded_delays(), vec![100, 200]);
+
+        // retries are limited
+        let source = flaky(&["status 502", "timed out", "status 429"], 2);
+        let result = source.fetch(&query).await;
+        assert!(matches!(result, Err(EtopError::RpcTransient(_))));
+        assert_eq!(*source.source.attempts.lock().unwrap(), 3);
+        assert_eq!(recorded_delays(), vec![100, 200]);
+
+        // permanent errors are returned immediately
+        let source = flaky(&["execution reverted"], 3);
+        let result = source.fetch(&query).await;
+        assert!(matches!(result, Err(EtopError::RpcPermanent(_))));
+        assert_eq!(*source.source.attempts.lock().unwrap(), 1);
+        assert!(recorded_delays().is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{datasets, DataWarehouse, EtopError};
+use crate::{datasets, DataWarehouse, EtopError, MetricSpec};
 use etop_format::ColumnFormatShorthand;
 use polars::prelude::*;
 use std::collections::HashMap;
@@ -43,6 +43,34 @@ pub enum AddressQueryArgument {
     Contract,
 }
 
+/// natural unit of rows of dataset, used when paging through block ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Granularity {
+    /// one row per block
+    #[default]
+    Block,
+    /// rows derived from transactions
+    Transaction,
+    /// rows derived from logs
+    Log,
+}
+
+impl Granularity {
+    /// description of block range in terms of unit, e.g. `txs in blocks 100–120`
+    pub fn describe_range(&self, start_block: u32, end_block: u32) -> String {
+        let blocks = if start_block == end_block {
+            format!("block {}", start_block)
+        } else {
+            format!("blocks {}–{}", start_block, end_block)
+        };
+        match self {
+            Granularity::Block => blocks,
+            Granularity::Transaction => format!("txs in {}", blocks),
+            Granularity::Log => format!("logs in {}", blocks),
+        }
+    }
+}
+
 /// specification for dataset
 pub trait DataSpec {
     /// name of dataset
@@ -62,17 +90,77 @@ pub trait DataSpec {
         end_block: Option<u32>,
     ) -> Result<DataFrame, EtopError>;
 
-    /// default columns
+    /// transform inputs into several named frames, shown side by side in panes
+    ///
+    /// only called for datasets that name their panes, the others keep the single frame of
+    /// transform, which is also used for the frame of the table
+    fn transform_multi(
+        &self,
+        warehouse: &DataWarehouse,
+        start_block: Option<u32>,
+        end_block: Option<u32>,
+    ) -> Result<HashMap<String, DataFrame>, EtopError> {
+        let df = self.transform(warehouse, start_block, end_block)?;
+        Ok([(self.name(), df)].into_iter().collect())
+    }
+
+    /// names of the frames of transform_multi, in pane order, empty for a single frame
+    fn pane_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// columns that can be displayed, in the order offered to the column picker
+    ///
+    /// a superset of default_columns, None if every column of the transformed data is available
+    fn available_columns(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// columns that transform reads from each input, keyed by input name
+    fn required_columns(&self) -> HashMap<String, Vec<String>> {
+        HashMap::new()
+    }
+
+    /// columns initially displayed, in display order
     fn default_columns(&self) -> Option<Vec<String>>;
 
     /// default format for each column
     fn default_column_formats(&self) -> Option<HashMap<String, ColumnFormatShorthand>>;
+
+    /// column whose values identify rows
+    fn key_column(&self) -> Option<String> {
+        None
+    }
+
+    /// style of a row of the displayed data, such as a color name, applied to the whole row
+    ///
+    /// row styles take precedence over zebra striping, while rank highlights and the selected
+    /// row take precedence over row styles
+    fn row_style(&self, _df: &DataFrame, _row: usize) -> Option<String> {
+        None
+    }
+
+    /// aggregates of the transformed data shown in the metrics dashboard
+    fn metrics(&self) -> Vec<MetricSpec> {
+        vec![]
+    }
+
+    /// named sets of columns to switch between, such as "compact" and "full"
+    fn column_presets(&self) -> HashMap<String, Vec<String>> {
+        HashMap::new()
+    }
+
+    /// natural unit of rows
+    fn granularity(&self) -> Granularity {
+        Granularity::Block
+    }
 }
 
 /// load dataspec
 pub fn load_dataspec(name: String) -> Result<Box<dyn DataSpec>, EtopError> {
     match name.as_str() {
         "blocks" => Ok(Box::new(datasets::Blocks)),
+        "block_summaries" => Ok(Box::new(datasets::BlockSummaries)),
         "erc20_transfers_by_erc20" => Ok(Box::new(datasets::Erc20TransfersByErc20)),
         "transactions_by_to_address" => Ok(Box::new(datasets::TransactionsByToAddress)),
         // _ => Err(EtopError::UnknownData(format!("invalid dataset: {}", name))),
@@ -0,0 +1,83 @@
+#[cfg(test)]
+#[path = "diff_tests.rs"]
+mod tests;
+
+use crate::EtopError;
+use polars::prelude::*;
+
+/// column of diff_frames output marking whether each row was added, removed, or changed
+pub const DIFF_COLUMN: &str = "diff";
+
+/// suffix of columns of diff_frames output holding values replaced by a change
+pub const DIFF_OLD_SUFFIX: &str = "_old";
+
+/// row style of each diff status, in the colors used for rank highlights
+pub(crate) const DIFF_STYLES: [(&str, &str); 3] =
+    [("added", "green"), ("removed", "red"), ("changed", "yellow")];
+
+/// rows of b that differ from a, matching rows by key column
+///
+/// the output has the key, a `diff` column of `added`, `removed`, or `changed`, and each column
+/// shared by both frames. shared columns hold the new value, or the old value of removed rows,
+/// and a `<column>_old` column holds the old value of each changed cell. unchanged rows are
+/// left out, and rows are sorted by key
+pub fn diff_frames(a: &DataFrame, b: &DataFrame, key: &str) -> Result<DataFrame, EtopError> {
+    for df in [a, b] {
+        df.column(key)
+            .map_err(|_| EtopError::ColumnMissing(format!("no key column named {}", key)))?;
+    }
+    let columns: Vec<String> = b
+        .get_column_names()
+        .into_iter()
+        .filter(|name| *name != key && a.column(name).is_ok())
+        .map(String::from)
+        .collect();
+    let old_name = |column: &str| format!("{}{}", column, DIFF_OLD_SUFFIX);
+
+    let mut old_columns = vec![col(key), lit(true).alias("__in_old")];
+    old_columns.extend(columns.iter().map(|c| col(c).alias(&old_name(c))));
+    let mut new_columns = vec![col(key), lit(true).alias("__in_new")];
+    new_columns.extend(columns.iter().map(|c| col(c)));
+    let joined = a.clone().lazy().select(old_columns).join(
+        b.clone().lazy().select(new_columns),
+        [col(key)],
+        [col(key)],
+        JoinArgs::new(JoinType::Outer),
+    );
+
+    let changed = |c: &String| col(&old_name(c)).neq_missing(col(c));
+    let any_changed = columns.iter().map(changed).reduce(|x, y| x.or(y)).unwrap_or(lit(false));
+    let status = when(col("__in_old").is_null())
+        .then(lit("added"))
+        .when(col("__in_new").is_null())
+        .then(lit("removed"))
+        .when(any_changed)
+        .then(lit("changed"))
+        .otherwise(lit(NULL))
+        .alias(DIFF_COLUMN);
+
+    let mut output = vec![col(key), col(DIFF_COLUMN)];
+    for c in columns.iter() {
+        let removed = col(DIFF_COLUMN).eq(lit("removed"));
+        output.push(when(removed).then(col(&old_name(c))).otherwise(col(c)).alias(c));
+        let replaced = col(DIFF_COLUMN).eq(lit("changed")).and(changed(c));
+        output
+            .push(when(replaced).then(col(&old_name(c))).otherwise(lit(NULL)).alias(&old_name(c)));
+    }
+    joined
+        .with_column(status)
+        .filter(col(DIFF_COLUMN).is_not_null())
+        .select(output)
+        .sort(key, SortOptions::default())
+        .collect()
+        .map_err(EtopError::PolarsError)
+}
+
+/// style of each row of a frame with a diff column, None if frame has no diff column
+pub fn diff_styles(df: &DataFrame) -> Option<Vec<Option<String>>> {
+    let statuses = df.column(DIFF_COLUMN).ok()?.utf8().ok()?;
+    let style = |status: &str| {
+        DIFF_STYLES.iter().find(|(s, _)| *s == status).map(|(_, style)| style.to_string())
+    };
+    Some(statuses.into_iter().map(|status| status.and_then(style)).collect())
+}
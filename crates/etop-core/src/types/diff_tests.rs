@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod diff_tests {
+    use crate::{diff_frames, EtopState};
+    use polars::prelude::*;
+
+    #[test]
+    fn diff_frames_rows() {
+        let a = df!("block_number" => [1u32, 2, 3], "n" => [10u32, 20, 30]).unwrap();
+        let b = df!("block_number" => [1u32, 2, 3, 4], "n" => [10u32, 25, 30, 40]).unwrap();
+        let diff = diff_frames(&a, &b, "block_number").unwrap();
+        let expected = df!(
+            "block_number" => [2u32, 4],
+            "diff" => ["changed", "added"],
+            "n" => [25u32, 40],
+            "n_old" => [Some(20u32), None],
+        )
+        .unwrap();
+        assert!(diff.frame_equal_missing(&expected), "{}", diff);
+
+        // removed rows keep their old values
+        let diff = diff_frames(&b, &a, "block_number").unwrap();
+        let statuses: Vec<&str> =
+            diff.column("diff").unwrap().utf8().unwrap().into_no_null_iter().collect();
+        assert_eq!(statuses, vec!["changed", "removed"]);
+        assert_eq!(diff.column("n").unwrap().u32().unwrap().get(1), Some(40));
+        assert!(diff_frames(&a, &b, "missing").is_err());
+    }
+
+    #[test]
+    fn diff_row_styles() {
+        let a = df!("block_number" => [1u32, 2, 3], "n" => [10u32, 20, 30]).unwrap();
+        let b = df!("block_number" => [1u32, 2, 4], "n" => [10u32, 25, 40]).unwrap();
+        let diff = diff_frames(&a, &b, "block_number").unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), diff);
+        state.cache_window(10, 80).unwrap();
+        let styles: Vec<Option<&str>> =
+            state.cache_row_styles.iter().map(|s| s.as_deref()).collect();
+        assert_eq!(styles, vec![Some("yellow"), Some("red"), Some("green")]);
+    }
+}
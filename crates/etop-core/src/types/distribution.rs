@@ -0,0 +1,184 @@
+#[cfg(test)]
+#[path = "distribution_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+use etop_format::{CellFormatShorthand, DataBarStyle, NumberFormat};
+use polars::prelude::*;
+
+/// width in chars of the bars of distribution panels
+pub const DISTRIBUTION_BAR_WIDTH: usize = 30;
+
+/// number of bins of histograms opened from the keyboard
+pub const HISTOGRAM_BINS: usize = 10;
+
+/// number of values of top value breakdowns opened from the keyboard
+pub const TOP_VALUES: usize = 10;
+
+/// summary of the data shown in place of the table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Panel {
+    /// histogram of numeric column
+    Histogram(String),
+    /// most frequent values of column
+    TopValues(String),
+    /// metrics of the dataset as labeled numbers
+    MetricsDashboard,
+    /// meaning of the colors of the table
+    Legend,
+}
+
+/// split range of values into bins of equal width, returning the start, end, and count of
+/// values of each bin
+///
+/// bins include their start and exclude their end, except the last bin, which includes the
+/// maximum. values all equal to each other fall in a single bin, and no values give no bins
+pub fn bin_values(values: &[f64], bins: usize) -> Vec<(f64, f64, usize)> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.is_empty() || bins == 0 {
+        return vec![]
+    }
+    if min == max {
+        return vec![(min, max, values.len())]
+    }
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for value in values {
+        let bin = ((value - min) / width).floor() as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    let bound = |i: usize| if i == bins { max } else { min + width * i as f64 };
+    counts.into_iter().enumerate().map(|(i, count)| (bound(i), bound(i + 1), count)).collect()
+}
+
+impl EtopState {
+    /// horizontal histogram of the values of numeric column across the window, with bin
+    /// ranges formatted by the column's number format
+    ///
+    /// nulls and non-finite values are left out
+    pub fn render_histogram(&self, column: &str, bins: usize) -> Result<String, EtopError> {
+        let df = self.window_frame()?;
+        let series = df
+            .column(column)
+            .map_err(|_| EtopError::ColumnMissing(format!("no column named {}", column)))?;
+        if !series.dtype().is_numeric() {
+            return Err(EtopError::UnsupportedDatatype(format!(
+                "cannot draw histogram of column {} of type {}",
+                column,
+                series.dtype()
+            )))
+        }
+        let values: Vec<f64> = series
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_iter()
+            .flatten()
+            .filter(|value| value.is_finite())
+            .collect();
+        if values.is_empty() {
+            return Ok(format!("histogram of {}: no values", column))
+        }
+
+        let number_format = match self.column_format(column)?.format {
+            CellFormatShorthand::Number(number_format) => number_format,
+            _ => NumberFormat::new(),
+        };
+        let format = |value: f64| -> Result<String, EtopError> {
+            Ok(number_format.format(value)?.trim().to_string())
+        };
+        let bins = bin_values(&values, bins);
+        let labels = bins
+            .iter()
+            .map(|(start, end, _)| match start == end {
+                true => format(*start),
+                false => Ok(format!("{} – {}", format(*start)?, format(*end)?)),
+            })
+            .collect::<Result<Vec<String>, EtopError>>()?;
+
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let max_count = bins.iter().map(|(_, _, count)| *count).max().unwrap_or(0);
+        let mut lines = vec![format!("histogram of {} ({} values)", column, values.len())];
+        for (label, (_, _, count)) in labels.iter().zip(bins.iter()) {
+            let fraction = *count as f64 / max_count as f64;
+            let bar = DataBarStyle::Blocks.render(fraction, DISTRIBUTION_BAR_WIDTH, false);
+            lines.push(format!("{:>width$} │{} {}", label, bar, count, width = label_width));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// the n most frequent values of column across the window, each formatted by the column's
+    /// format, with its count and a bar proportional to the count
+    ///
+    /// values with equal counts are ordered by value, and nulls are counted as a value
+    pub fn render_top_values(&self, column: &str, n: usize) -> Result<String, EtopError> {
+        let df = self.window_frame()?;
+        let series = df
+            .column(column)
+            .map_err(|_| EtopError::ColumnMissing(format!("no column named {}", column)))?;
+        let counts = series
+            .value_counts(false, false)?
+            .lazy()
+            .sort_by_exprs([col("counts"), col(column)], [true, false], true, false)
+            .collect()?;
+        if counts.height() == 0 {
+            return Ok(format!("top values of {}: no values", column))
+        }
+
+        let shown = counts.head(Some(n));
+        let values = shown.column(column)?;
+        let labels: Vec<String> = self
+            .column_format(column)?
+            .finalize(values.dtype())?
+            .format(values)?
+            .into_iter()
+            .zip(&values.is_null())
+            .map(|(label, is_null)| match is_null {
+                Some(true) => "null".to_string(),
+                _ => label.trim().to_string(),
+            })
+            .collect();
+        let shown_counts = shown.column("counts")?.cast(&DataType::UInt32)?;
+        let shown_counts: Vec<u32> = shown_counts.u32()?.into_no_null_iter().collect();
+
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let max_count = shown_counts.iter().copied().max().unwrap_or(0);
+        let mut lines = vec![format!(
+            "top values of {} ({} values, {} distinct)",
+            column,
+            series.len(),
+            counts.height()
+        )];
+        for (label, count) in labels.iter().zip(shown_counts.iter()) {
+            let fraction = *count as f64 / max_count as f64;
+            let bar = DataBarStyle::Blocks.render(fraction, DISTRIBUTION_BAR_WIDTH, false);
+            lines.push(format!("{:>width$} │{} {}", label, bar, count, width = label_width));
+        }
+        if counts.height() > n {
+            lines.push(format!("… {} more values", counts.height() - n));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// open panel, or close it if it is already open
+    pub fn toggle_panel(&mut self, panel: Panel) {
+        self.panel = match self.panel.as_ref() == Some(&panel) {
+            true => None,
+            false => Some(panel),
+        };
+    }
+
+    /// rendering of the open panel, if any
+    pub fn render_panel(&self) -> Option<Result<String, EtopError>> {
+        match self.panel.as_ref()? {
+            Panel::Histogram(column) => Some(self.render_histogram(column, HISTOGRAM_BINS)),
+            Panel::TopValues(column) => Some(self.render_top_values(column, TOP_VALUES)),
+            Panel::MetricsDashboard => {
+                Some(self.dataspec().and_then(|dataspec| self.render_metrics(&dataspec.metrics())))
+            }
+            Panel::Legend => {
+                Some(Ok(self.render_legend().unwrap_or_else(|| crate::NO_LEGEND.to_string())))
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod distribution_tests {
+    use crate::{bin_values, EtopState, Panel};
+    use polars::prelude::*;
+
+    #[test]
+    fn histogram_bins() {
+        let values: Vec<f64> = (0..=10).map(f64::from).collect();
+        let bins = bin_values(&values, 5);
+        assert_eq!(
+            bins,
+            vec![(0.0, 2.0, 2), (2.0, 4.0, 2), (4.0, 6.0, 2), (6.0, 8.0, 2), (8.0, 10.0, 3)]
+        );
+
+        // equal values share one bin, and no values give no bins
+        assert_eq!(bin_values(&[3.0, 3.0, 3.0], 5), vec![(3.0, 3.0, 3)]);
+        assert!(bin_values(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn render_histogram() {
+        let df = df!(
+            "n" => [Some(1u32), Some(1), Some(1), Some(3), None],
+            "name" => ["a", "b", "c", "d", "e"],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let histogram = state.render_histogram("n", 2).unwrap();
+        let lines: Vec<&str> = histogram.lines().collect();
+        assert_eq!(lines[0], "histogram of n (4 values)");
+        assert_eq!(lines[1], format!("1 – 2 │{} 3", "█".repeat(30)));
+        assert_eq!(lines[2], format!("2 – 3 │{}{} 1", "█".repeat(10), " ".repeat(20)));
+        assert!(state.render_histogram("name", 2).is_err());
+
+        // panel opens and closes
+        state.toggle_panel(Panel::Histogram("n".to_string()));
+        assert!(state.render_panel().unwrap().unwrap().starts_with("histogram of n"));
+        state.toggle_panel(Panel::Histogram("n".to_string()));
+        assert!(state.render_panel().is_none());
+
+        // all equal, and empty after filters
+        state.layout.filters = vec!["n == 1".to_string()];
+        let histogram = state.render_histogram("n", 2).unwrap();
+        assert_eq!(histogram.lines().nth(1).unwrap(), format!("1 │{} 3", "█".repeat(30)));
+        state.layout.filters = vec!["n > 5".to_string()];
+        assert_eq!(state.render_histogram("n", 2).unwrap(), "histogram of n: no values");
+    }
+
+    #[test]
+    fn render_top_values() {
+        let df = df!(
+            "token" => [Some("usdc"), Some("weth"), Some("usdc"), Some("dai"), Some("usdc"), None],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let top = state.render_top_values("token", 3).unwrap();
+        let lines: Vec<&str> = top.lines().collect();
+        assert_eq!(lines[0], "top values of token (6 values, 4 distinct)");
+        assert_eq!(lines[1], format!("usdc │{} 3", "█".repeat(30)));
+        // ties are ordered by value
+        assert_eq!(lines[2], format!(" dai │{}{} 1", "█".repeat(10), " ".repeat(20)));
+        assert_eq!(lines[3], format!("weth │{}{} 1", "█".repeat(10), " ".repeat(20)));
+        assert_eq!(lines[4], "… 1 more values");
+
+        // nulls are counted as a value, after values with equal counts
+        let top = state.render_top_values("token", 5).unwrap();
+        assert_eq!(
+            top.lines().nth(4).unwrap(),
+            format!("null │{}{} 1", "█".repeat(10), " ".repeat(20))
+        );
+        assert!(state.render_top_values("missing", 3).is_err());
+    }
+}
@@ -1,3 +1,6 @@
+use cryo_freeze::CollectError;
+use ethers::providers::ProviderError;
+
 /// etop error
 #[derive(Debug)]
 pub enum EtopError {
@@ -37,10 +40,91 @@ pub enum EtopError {
     TuiError(String),
     /// connection error
     ConnectionError(String),
+    /// rpc error that may go away when retried, such as a timeout or rate limit
+    RpcTransient(String),
+    /// rpc error that retrying will not fix
+    RpcPermanent(String),
     /// cryo error
     CryoError(cryo_freeze::CollectError),
     /// cryo parse error
     CryoParseError(cryo_freeze::ParseError),
+    /// json error
+    JsonError(serde_json::Error),
+    /// fetch would exceed the maximum number of rows, (rows, max_rows)
+    RowLimitExceeded(usize, usize),
+}
+
+impl EtopError {
+    /// classify rpc error message as transient (timeouts, 429, 5xx) or permanent
+    ///
+    /// only numbers given as an http status, e.g. `HTTP error 503` or `status code: 503`, or as
+    /// an error code field, e.g. `"code": -32005`, are read as codes, so that other numbers in
+    /// the message, such as block numbers, are not mistaken for them
+    pub fn classify_rpc(message: String) -> EtopError {
+        let lowercase = message.to_lowercase();
+        let timeout =
+            ["timeout", "timed out", "too many requests"].iter().any(|s| lowercase.contains(s));
+        let words: Vec<&str> = lowercase
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .filter(|word| !word.is_empty())
+            .collect();
+        let status = words.iter().enumerate().any(|(i, word)| {
+            let code = match word.parse::<i64>() {
+                Ok(code) => code,
+                Err(_) => return false,
+            };
+            let http = words[i.saturating_sub(3)..i].iter().any(|w| *w == "http" || *w == "status");
+            let field = i > 0 && words[i - 1] == "code";
+            (http || field) && is_transient_code(code)
+        });
+        if timeout || status {
+            EtopError::RpcTransient(message)
+        } else {
+            EtopError::RpcPermanent(message)
+        }
+    }
+
+    /// classify error of a cryo collection as transient or permanent by its type
+    ///
+    /// json-rpc error responses are classified by their code, rate limits and failures to reach
+    /// the provider are transient, and all other errors are permanent
+    pub fn classify_collect(error: CollectError) -> EtopError {
+        let transient = match &error {
+            CollectError::TooManyRequestsError => true,
+            CollectError::RPCError(message) => {
+                return EtopError::classify_rpc(message.clone());
+            }
+            CollectError::ProviderError(ProviderError::JsonRpcClientError(e)) => {
+                match e.as_error_response() {
+                    Some(response) => is_transient_code(response.code),
+                    None => !e.is_serde_error(),
+                }
+            }
+            CollectError::ProviderError(ProviderError::HTTPError(e)) => {
+                let status = e.status().map(|status| is_transient_code(status.as_u16() as i64));
+                e.is_timeout() || e.is_connect() || status.unwrap_or(false)
+            }
+            _ => false,
+        };
+        if transient {
+            EtopError::RpcTransient(error.to_string())
+        } else {
+            EtopError::RpcPermanent(error.to_string())
+        }
+    }
+
+    /// whether retrying the operation that caused the error may succeed
+    pub fn is_transient(&self) -> bool {
+        matches!(self, EtopError::RpcTransient(_))
+    }
+}
+
+/// json-rpc error code of requests exceeding a provider's limits
+const RPC_LIMIT_EXCEEDED: i64 = -32005;
+
+/// whether http status or json-rpc error code is of an error that may pass
+fn is_transient_code(code: i64) -> bool {
+    code == 429 || (500..600).contains(&code) || code == RPC_LIMIT_EXCEEDED
 }
 
 impl From<polars::prelude::PolarsError> for EtopError {
@@ -72,3 +156,9 @@ impl From<std::io::Error> for EtopError {
         EtopError::IOError(err)
     }
 }
+
+impl From<serde_json::Error> for EtopError {
+    fn from(err: serde_json::Error) -> EtopError {
+        EtopError::JsonError(err)
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+#[path = "fetch_tests.rs"]
+mod tests;
+
+/// state of data fetching
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FetchState {
+    /// no fetch in flight
+    #[default]
+    Idle,
+    /// fetches in flight, previous data stays shown meanwhile
+    Loading,
+    /// a fetch failed, previous data stays shown
+    Error(String),
+}
+
+/// tracks fetches in flight to derive the fetch state
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchTracker {
+    /// current state
+    pub state: FetchState,
+    /// number of fetches in flight
+    pub pending: usize,
+}
+
+impl FetchTracker {
+    /// record that n fetches started, clearing any previous error
+    pub fn start(&mut self, n: usize) {
+        if n > 0 {
+            self.pending += n;
+            self.state = FetchState::Loading;
+        }
+    }
+
+    /// record that a fetch completed, becoming idle once no fetches are in flight
+    pub fn complete(&mut self) {
+        self.pending = self.pending.saturating_sub(1);
+        if self.pending == 0 && self.state == FetchState::Loading {
+            self.state = FetchState::Idle;
+        }
+    }
+
+    /// record that a fetch failed, the error is kept until the next fetch starts
+    pub fn fail(&mut self, error: String) {
+        self.pending = self.pending.saturating_sub(1);
+        self.state = FetchState::Error(error);
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod fetch_tests {
+    use crate::{FetchState, FetchTracker};
+
+    #[test]
+    fn start_and_complete() {
+        let mut fetch = FetchTracker::default();
+        assert_eq!(fetch.state, FetchState::Idle);
+        fetch.start(0);
+        assert_eq!(fetch.state, FetchState::Idle);
+
+        fetch.start(2);
+        assert_eq!(fetch.state, FetchState::Loading);
+        fetch.complete();
+        assert_eq!(fetch.state, FetchState::Loading);
+        fetch.complete();
+        assert_eq!(fetch.state, FetchState::Idle);
+
+        // completions without a start stay idle
+        fetch.complete();
+        assert_eq!(fetch, FetchTracker::default());
+    }
+
+    #[test]
+    fn fail() {
+        let mut fetch = FetchTracker::default();
+        fetch.start(2);
+        fetch.fail("timeout".to_string());
+        assert_eq!(fetch.state, FetchState::Error("timeout".to_string()));
+
+        // error outlives the remaining fetch
+        fetch.complete();
+        assert_eq!(fetch.state, FetchState::Error("timeout".to_string()));
+        assert_eq!(fetch.pending, 0);
+
+        // next fetch clears the error
+        fetch.start(1);
+        assert_eq!(fetch.state, FetchState::Loading);
+        fetch.complete();
+        assert_eq!(fetch.state, FetchState::Idle);
+    }
+}
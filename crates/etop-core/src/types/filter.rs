@@ -0,0 +1,310 @@
+#[cfg(test)]
+#[path = "filter_tests.rs"]
+mod tests;
+
+use crate::EtopError;
+use polars::prelude::*;
+
+/// currency symbols accepted, and ignored, by `parse_human`
+const CURRENCY_SYMBOLS: [char; 5] = ['$', '€', '£', '¥', 'Ξ'];
+
+/// comparison operators of filter expressions, longest first so that `>=` is not read as `>`
+const FILTER_OPS: [(&str, FilterOp); 7] = [
+    (">=", FilterOp::GreaterEqual),
+    ("<=", FilterOp::LessEqual),
+    ("==", FilterOp::Equal),
+    ("!=", FilterOp::NotEqual),
+    (">", FilterOp::Greater),
+    ("<", FilterOp::Less),
+    ("=", FilterOp::Equal),
+];
+
+/// parse human readable number, the inverse of the `s` and `%` formats
+///
+/// accepts SI suffixes (`1.5k`, `2M`, `3m`) and order of magnitude suffixes (`4B`, `5T`),
+/// grouping separators (`1,234.5`), percentages (`12.5%`), currency symbols (`$5`), and hex
+/// literals (`0x1f`)
+pub fn parse_human(text: &str) -> Result<f64, EtopError> {
+    let invalid = || EtopError::ParseError(format!("invalid number: {}", text));
+
+    // sign and currency may come in either order, e.g. `-$5` or `$-5`
+    let (mut negative, mut s) = strip_sign(text.trim());
+    if let Some(rest) = s.strip_prefix(CURRENCY_SYMBOLS) {
+        s = rest;
+        if !negative {
+            (negative, s) = strip_sign(s);
+        }
+    }
+    let sign = if negative { -1.0 } else { 1.0 };
+
+    // hex literal
+    if let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        let digits = digits.replace('_', "");
+        let value = u128::from_str_radix(&digits, 16).map_err(|_| invalid())?;
+        return Ok(sign * value as f64)
+    }
+
+    // percentage or magnitude suffix
+    let mut multiplier = 1.0;
+    if let Some(rest) = s.strip_suffix('%') {
+        multiplier = 0.01;
+        s = rest;
+    } else if let Some(last) = s.chars().last().filter(|c| c.is_alphabetic()) {
+        multiplier = suffix_multiplier(last).ok_or_else(invalid)?;
+        s = &s[..s.len() - last.len_utf8()];
+    }
+    let s = s.trim_end();
+
+    // grouping separators must separate groups of three digits
+    let (integer, fraction) = match s.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (s, None),
+    };
+    if integer.contains(',') {
+        let mut groups = integer.split(',');
+        let first = groups.next().unwrap_or_default();
+        if first.is_empty() || first.len() > 3 || groups.any(|group| group.len() != 3) {
+            return Err(invalid())
+        }
+    }
+    let mut number = integer.replace([',', '_'], "");
+    if let Some(fraction) = fraction {
+        number = format!("{}.{}", number, fraction.replace('_', ""));
+    }
+
+    // reject words that f64 parses, such as `inf` or `nan`
+    if !number.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return Err(invalid())
+    }
+    let value: f64 = number.parse().map_err(|_| invalid())?;
+    Ok(sign * value * multiplier)
+}
+
+/// strip leading sign, returning whether it is negative
+fn strip_sign(s: &str) -> (bool, &str) {
+    match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    }
+}
+
+/// multiplier of SI or order of magnitude suffix
+fn suffix_multiplier(suffix: char) -> Option<f64> {
+    let exponent = match suffix {
+        'y' => -24,
+        'z' => -21,
+        'a' => -18,
+        'f' => -15,
+        'p' => -12,
+        'n' => -9,
+        'µ' | 'u' => -6,
+        'm' => -3,
+        'k' | 'K' => 3,
+        'M' => 6,
+        'G' | 'B' => 9,
+        'T' => 12,
+        'P' | 'Q' => 15,
+        'E' => 18,
+        'Z' => 21,
+        'Y' => 24,
+        _ => return None,
+    };
+    Some(10f64.powi(exponent))
+}
+
+/// comparison operator of filter expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+    /// `==` or `=`
+    Equal,
+    /// `!=`
+    NotEqual,
+}
+
+impl std::fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            FilterOp::Greater => ">",
+            FilterOp::GreaterEqual => ">=",
+            FilterOp::Less => "<",
+            FilterOp::LessEqual => "<=",
+            FilterOp::Equal => "==",
+            FilterOp::NotEqual => "!=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// filter expression of the form `column op value`, e.g. `value > 1.5k`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpr {
+    /// column to compare
+    pub column: String,
+    /// comparison operator
+    pub op: FilterOp,
+    /// value compared against, as entered
+    pub value: String,
+}
+
+impl std::str::FromStr for FilterExpr {
+    type Err = EtopError;
+
+    /// parse filter expression of the form `column op value`
+    fn from_str(s: &str) -> Result<FilterExpr, EtopError> {
+        let usage = || {
+            EtopError::ParseError(format!(
+                "invalid filter: {} (expected <column> <op> <value>, with op one of > >= < <= == !=)",
+                s
+            ))
+        };
+        let position = s.find(['<', '>', '=', '!']).ok_or_else(usage)?;
+        let (column, rest) = s.split_at(position);
+        let (symbol, op) =
+            FILTER_OPS.iter().find(|(symbol, _)| rest.starts_with(symbol)).ok_or_else(usage)?;
+        let column = column.trim();
+        let value = rest[symbol.len()..].trim();
+        if column.is_empty() || column.contains(char::is_whitespace) || value.is_empty() {
+            return Err(usage())
+        }
+        Ok(FilterExpr { column: column.to_string(), op: *op, value: value.to_string() })
+    }
+}
+
+impl std::fmt::Display for FilterExpr {
+    /// compact form of expression, e.g. `value>0`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.column, self.op, self.value)
+    }
+}
+
+impl FilterExpr {
+    /// mask of rows of frame that pass the filter
+    ///
+    /// numeric columns are compared against the value parsed by `parse_human`, binary columns
+    /// against the bytes of a hex literal, and string columns against the value itself. hex
+    /// literals match regardless of case, so that checksummed addresses match, and string
+    /// columns of hex values are compared as hex when the value is a hex literal
+    pub fn mask(&self, df: &DataFrame) -> Result<BooleanChunked, EtopError> {
+        let series = df
+            .column(self.column.as_str())
+            .map_err(|_| EtopError::ColumnMissing(format!("no column named {}", self.column)))?;
+        let mask = match series.dtype() {
+            DataType::Utf8 => {
+                let value = self.value.trim_matches(|c| c == '"' || c == '\'');
+                let equality = matches!(self.op, FilterOp::Equal | FilterOp::NotEqual);
+                match parse_hex_literal(value) {
+                    Ok((nibbles, prefix)) if equality => {
+                        let matches = series.utf8()?.into_iter().map(|text| {
+                            text.map(|text| match parse_hex_literal(text) {
+                                Ok((value, _)) => hex_matches(&value, &nibbles, prefix),
+                                _ => false,
+                            })
+                        });
+                        self.equality_mask(matches.collect())?
+                    }
+                    _ => compare(series.utf8()?, self.op, value),
+                }
+            }
+            DataType::Binary => {
+                let (nibbles, prefix) = parse_hex_literal(&self.value)?;
+                let matches = series.binary()?.into_iter().map(|bytes| {
+                    bytes.map(|bytes| {
+                        let value: Vec<u8> = bytes.iter().flat_map(|b| [b >> 4, b & 0xf]).collect();
+                        hex_matches(&value, &nibbles, prefix)
+                    })
+                });
+                self.equality_mask(matches.collect())?
+            }
+            dtype if dtype.is_numeric() => {
+                let value = parse_human(&self.value)?;
+                compare(series.cast(&DataType::Float64)?.f64()?, self.op, value)
+            }
+            dtype => {
+                return Err(EtopError::UnsupportedDatatype(format!(
+                    "cannot filter column {} of type {}",
+                    self.column, dtype
+                )))
+            }
+        };
+        Ok(mask)
+    }
+
+    /// mask of rows passing an `==` or `!=` filter, given the rows whose values match
+    fn equality_mask(&self, matches: BooleanChunked) -> Result<BooleanChunked, EtopError> {
+        match self.op {
+            FilterOp::Equal => Ok(matches),
+            FilterOp::NotEqual => Ok(!matches),
+            _ => Err(EtopError::ParseError(format!(
+                "binary column {} only supports == and !=",
+                self.column
+            ))),
+        }
+    }
+}
+
+/// parse hex literal of the form `0xabcd`, or `0xab*` to match values starting with `0xab`
+///
+/// returns the hex digits as nibbles and whether the literal is a prefix, digits are case
+/// insensitive. literals with an odd number of digits, such as `0xabc`, cannot be whole bytes
+/// and are prefixes of the nibbles of values, with or without `*`, while literals with an even
+/// number of digits match whole values unless they end in `*`
+pub fn parse_hex_literal(text: &str) -> Result<(Vec<u8>, bool), EtopError> {
+    let invalid =
+        |reason: &str| EtopError::ParseError(format!("invalid hex literal {}: {}", text, reason));
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .ok_or_else(|| invalid("expected 0x prefix"))?;
+    let (digits, prefix) = match digits.strip_suffix('*') {
+        Some(digits) => (digits, true),
+        None => (digits, false),
+    };
+    let nibbles = digits
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| invalid("expected only hex digits"))?;
+    let prefix = prefix || nibbles.len() % 2 == 1;
+    Ok((nibbles, prefix))
+}
+
+/// whether nibbles of value equal those of a literal, or start with them if it is a prefix
+fn hex_matches(value: &[u8], nibbles: &[u8], prefix: bool) -> bool {
+    match prefix {
+        true => value.starts_with(nibbles),
+        false => value == nibbles,
+    }
+}
+
+/// compare each element of array against value
+fn compare<A: ChunkCompare<T, Item = BooleanChunked>, T>(
+    array: &A,
+    op: FilterOp,
+    value: T,
+) -> BooleanChunked {
+    match op {
+        FilterOp::Greater => array.gt(value),
+        FilterOp::GreaterEqual => array.gt_eq(value),
+        FilterOp::Less => array.lt(value),
+        FilterOp::LessEqual => array.lt_eq(value),
+        FilterOp::Equal => array.equal(value),
+        FilterOp::NotEqual => array.not_equal(value),
+    }
+}
+
+/// keep rows of frame that pass all filter expressions
+pub fn apply_filters(mut df: DataFrame, filters: &[String]) -> Result<DataFrame, EtopError> {
+    for filter in filters {
+        let mask = filter.parse::<FilterExpr>()?.mask(&df)?;
+        df = df.filter(&mask)?;
+    }
+    Ok(df)
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod filter_tests {
+    use crate::{parse_hex_literal, parse_human, EtopState, FilterExpr, FilterOp};
+    use polars::prelude::*;
+
+    #[test]
+    fn parse_plain() {
+        assert_eq!(parse_human("42").unwrap(), 42.0);
+        assert_eq!(parse_human(" 1.25 ").unwrap(), 1.25);
+        assert_eq!(parse_human(".5").unwrap(), 0.5);
+        assert_eq!(parse_human("-3").unwrap(), -3.0);
+        assert_eq!(parse_human("+3").unwrap(), 3.0);
+        assert_eq!(parse_human("1e18").unwrap(), 1e18);
+        assert_eq!(parse_human("1_000_000").unwrap(), 1_000_000.0);
+    }
+
+    #[test]
+    fn parse_suffixes() {
+        assert_eq!(parse_human("1.5k").unwrap(), 1500.0);
+        assert_eq!(parse_human("1.5K").unwrap(), 1500.0);
+        assert_eq!(parse_human("2M").unwrap(), 2_000_000.0);
+        assert_eq!(parse_human("3G").unwrap(), 3e9);
+        assert_eq!(parse_human("3B").unwrap(), 3e9);
+        assert_eq!(parse_human("4 T").unwrap(), 4e12);
+        assert_eq!(parse_human("-2.5k").unwrap(), -2500.0);
+        assert!((parse_human("250m").unwrap() - 0.25).abs() < 1e-12);
+        assert!((parse_human("12µ").unwrap() - 12e-6).abs() < 1e-18);
+        assert!(parse_human("2x").is_err());
+        assert!(parse_human("k").is_err());
+    }
+
+    #[test]
+    fn parse_grouping() {
+        assert_eq!(parse_human("1,234.5").unwrap(), 1234.5);
+        assert_eq!(parse_human("12,345,678").unwrap(), 12_345_678.0);
+        assert_eq!(parse_human("1,234k").unwrap(), 1_234_000.0);
+        assert!(parse_human("1,23").is_err());
+        assert!(parse_human("1234,567").is_err());
+        assert!(parse_human(",123").is_err());
+    }
+
+    #[test]
+    fn parse_percent_and_currency() {
+        assert_eq!(parse_human("50%").unwrap(), 0.5);
+        assert_eq!(parse_human("12.5 %").unwrap(), 0.125);
+        assert_eq!(parse_human("$1,000").unwrap(), 1000.0);
+        assert_eq!(parse_human("€2.5M").unwrap(), 2_500_000.0);
+        assert_eq!(parse_human("-$5").unwrap(), -5.0);
+        assert_eq!(parse_human("$-5").unwrap(), -5.0);
+        assert_eq!(parse_human("Ξ0.1").unwrap(), 0.1);
+    }
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!(parse_human("0x1f").unwrap(), 31.0);
+        assert_eq!(parse_human("0X1F").unwrap(), 31.0);
+        assert_eq!(parse_human("-0xff").unwrap(), -255.0);
+        assert!(parse_human("0x").is_err());
+        assert!(parse_human("0xzz").is_err());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for text in ["", "-", "abc", "inf", "nan", "1.2.3", "$", "%", "--1"] {
+            assert!(parse_human(text).is_err(), "{} should not parse", text);
+        }
+    }
+
+    #[test]
+    fn parse_filter_expr() {
+        let expr: FilterExpr = "value >= 1.5k".parse().unwrap();
+        assert_eq!(expr.column, "value");
+        assert_eq!(expr.op, FilterOp::GreaterEqual);
+        assert_eq!(expr.value, "1.5k");
+        let expr: FilterExpr = "name=alice".parse().unwrap();
+        assert_eq!((expr.op, expr.value.as_str()), (FilterOp::Equal, "alice"));
+        assert_eq!("n != 0".parse::<FilterExpr>().unwrap().op, FilterOp::NotEqual);
+        for text in ["value", "> 1", "value >", "gas used > 1", "value ! 1"] {
+            assert!(text.parse::<FilterExpr>().is_err(), "{} should not parse", text);
+        }
+    }
+
+    #[test]
+    fn apply_filters() {
+        let df = df!(
+            "name" => ["alice", "bob", "carol"],
+            "value" => [1200u64, 1500, 2_000_000],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let names = |state: &EtopState| -> Vec<String> {
+            let df = state.window_frame().unwrap();
+            let names = df.column("name").unwrap().utf8().unwrap().clone();
+            names.into_iter().map(|name| name.unwrap().to_string()).collect()
+        };
+
+        state.layout.filters = vec!["value > 1.5k".to_string()];
+        assert_eq!(names(&state), vec!["carol"]);
+        state.layout.filters = vec!["value >= 1.5k".to_string(), "value < 2M".to_string()];
+        assert_eq!(names(&state), vec!["bob"]);
+        state.layout.filters = vec!["name != bob".to_string()];
+        assert_eq!(names(&state), vec!["alice", "carol"]);
+
+        state.layout.filters = vec!["value > lots".to_string()];
+        assert!(state.window_frame().is_err());
+        state.layout.filters = vec!["missing > 1".to_string()];
+        assert!(state.window_frame().is_err());
+    }
+
+    #[test]
+    fn parse_hex_literals() {
+        assert_eq!(parse_hex_literal("0xAb01").unwrap(), (vec![0xa, 0xb, 0x0, 0x1], false));
+        assert_eq!(parse_hex_literal("0xabc*").unwrap(), (vec![0xa, 0xb, 0xc], true));
+        assert_eq!(parse_hex_literal("0x*").unwrap(), (vec![], true));
+        assert_eq!(parse_hex_literal("0XaBc*").unwrap(), (vec![0xa, 0xb, 0xc], true));
+        // odd number of digits is a prefix, with or without `*`
+        assert_eq!(parse_hex_literal("0xabc").unwrap(), (vec![0xa, 0xb, 0xc], true));
+        let error = |text: &str| format!("{:?}", parse_hex_literal(text).unwrap_err());
+        assert!(error("abcd").contains("expected 0x prefix"));
+        assert!(error("0xabz1").contains("expected only hex digits"));
+    }
+
+    #[test]
+    fn filter_binary_column() {
+        let df = df!(
+            "to_address" => [vec![0xabu8, 0xcd, 0x01], vec![0xab, 0xc0, 0x02], vec![0x12, 0x34, 0x56]],
+            "n" => [1u32, 2, 3],
+        )
+        .unwrap();
+        let rows = |filter: &str| -> Result<Vec<u32>, crate::EtopError> {
+            let df = crate::apply_filters(df.clone(), &[filter.to_string()])?;
+            Ok(df.column("n").unwrap().u32().unwrap().into_no_null_iter().collect())
+        };
+
+        // full literal, case insensitive
+        assert_eq!(rows("to_address == 0xABCD01").unwrap(), vec![1]);
+        assert_eq!(rows("to_address != 0xabcd01").unwrap(), vec![2, 3]);
+        assert!(rows("to_address == 0xabcd").unwrap().is_empty());
+
+        // prefix literal, ending in `*` or with an odd number of digits
+        assert_eq!(rows("to_address == 0xabc*").unwrap(), vec![1, 2]);
+        assert_eq!(rows("to_address == 0xabcd*").unwrap(), vec![1]);
+        assert_eq!(rows("to_address != 0x1*").unwrap(), vec![1, 2]);
+        assert_eq!(rows("to_address == 0xABC*").unwrap(), vec![1, 2]);
+        assert_eq!(rows("to_address == 0xabc").unwrap(), vec![1, 2]);
+        assert_eq!(rows("to_address == 0xABC").unwrap(), vec![1, 2]);
+        assert_eq!(rows("to_address != 0x123").unwrap(), vec![1, 2]);
+
+        // malformed literals and unsupported operators
+        assert!(rows("to_address == 0xabq").is_err());
+        assert!(rows("to_address == abcd01").is_err());
+        assert!(rows("to_address > 0xab").is_err());
+    }
+
+    #[test]
+    fn filter_hex_string_column() {
+        let df = df!(
+            "address" => [
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+                "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+                "vitalik.eth",
+            ],
+            "n" => [1u32, 2, 3, 4],
+        )
+        .unwrap();
+        let rows = |filter: &str| -> Result<Vec<u32>, crate::EtopError> {
+            let df = crate::apply_filters(df.clone(), &[filter.to_string()])?;
+            Ok(df.column("n").unwrap().u32().unwrap().into_no_null_iter().collect())
+        };
+
+        // checksummed and lowercase addresses match each other
+        assert_eq!(
+            rows("address == 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            rows("address == 0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            rows("address != 0xFB6916095CA1DF60BB79CE92CE3EA74C37C5D359").unwrap(),
+            vec![1, 2, 4]
+        );
+
+        // prefixes match any address starting with their digits
+        assert!(rows("address == 0x5AaE").unwrap().is_empty());
+        assert_eq!(rows("address == 0x5AaE*").unwrap(), vec![1, 2]);
+        assert_eq!(rows("address == 0xFb6*").unwrap(), vec![3]);
+        assert_eq!(rows("address == 0xFb6").unwrap(), vec![3]);
+
+        // other values and operators compare as text
+        assert_eq!(rows("address == vitalik.eth").unwrap(), vec![4]);
+        assert_eq!(rows("address > 0xa").unwrap(), vec![3, 4]);
+    }
+}
@@ -0,0 +1,259 @@
+#[cfg(test)]
+#[path = "keybindings_tests.rs"]
+mod tests;
+
+use crate::{EtopError, NavMode, VimKeys};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+/// chords that bindings are moved to in vim mode, where vim motions take over the original chord
+const VIM_CHORDS: [(&str, &str); 1] = [("l", "ctrl+l")];
+
+/// action that can be bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    /// quit
+    Quit,
+    /// go to previous window
+    PreviousWindow,
+    /// follow latest block
+    LiveWindow,
+    /// move window back one block
+    DecrementBlock,
+    /// move window forward one block
+    IncrementBlock,
+    /// move window back one window
+    DecrementWindow,
+    /// move window forward one window
+    IncrementWindow,
+    /// toggle cell inspection
+    ToggleInspect,
+    /// move cursor up
+    CursorUp,
+    /// move cursor down
+    CursorDown,
+    /// move cursor left
+    CursorLeft,
+    /// move cursor right
+    CursorRight,
+    /// scroll table left one column
+    ScrollLeft,
+    /// scroll table right one column
+    ScrollRight,
+    /// scroll table up one page
+    PageUp,
+    /// scroll table down one page
+    PageDown,
+    /// toggle scrollbar
+    ToggleScrollbar,
+    /// refresh data now, whatever the refresh mode
+    Refresh,
+    /// toggle redaction of binary and string columns
+    ToggleRedact,
+    /// toggle showing columns as rows
+    ToggleTranspose,
+    /// toggle shading of every other row
+    ToggleZebra,
+    /// show one more digit of the inspected column
+    IncreasePrecision,
+    /// show one less digit of the inspected column
+    DecreasePrecision,
+    /// toggle inspected column between converted and raw units
+    ToggleRawUnits,
+    /// cycle inspected column through fixed point, SI, exponent, and hex formats
+    CycleNumberFormat,
+    /// toggle histogram of inspected column
+    ToggleHistogram,
+    /// toggle most frequent values of inspected column
+    ToggleTopValues,
+    /// toggle dashboard of metrics of dataset
+    ToggleMetrics,
+    /// toggle legend of the colors of the table
+    ToggleLegend,
+    /// toggle fixing the width of each column
+    ToggleLockWidths,
+    /// open prompt to sort by a column
+    SortColumn,
+    /// open prompt to add a filter
+    Filter,
+    /// open command palette
+    CommandPalette,
+    /// switch to next view
+    NextView,
+    /// switch to previous view
+    PrevView,
+    /// split view beside a copy of itself, or end the split
+    ToggleSplit,
+    /// move focus to the other pane of the split
+    SwitchPane,
+}
+
+/// keys bound to each action
+///
+/// keys are chords such as `q`, `up`, or `ctrl+c`, with modifiers given in the order `ctrl`,
+/// `alt`, `shift`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    /// key chords of each action
+    pub bindings: HashMap<KeyAction, Vec<String>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        let bindings = [
+            (KeyAction::Quit, "q"),
+            (KeyAction::PreviousWindow, "backspace"),
+            (KeyAction::LiveWindow, "l"),
+            (KeyAction::DecrementBlock, "["),
+            (KeyAction::IncrementBlock, "]"),
+            (KeyAction::DecrementWindow, "{"),
+            (KeyAction::IncrementWindow, "}"),
+            (KeyAction::ToggleInspect, "i"),
+            (KeyAction::CursorUp, "up"),
+            (KeyAction::CursorDown, "down"),
+            (KeyAction::CursorLeft, "left"),
+            (KeyAction::CursorRight, "right"),
+            (KeyAction::ScrollLeft, "<"),
+            (KeyAction::ScrollRight, ">"),
+            (KeyAction::PageUp, "pageup"),
+            (KeyAction::PageDown, "pagedown"),
+            (KeyAction::ToggleScrollbar, "b"),
+            (KeyAction::Refresh, "r"),
+            (KeyAction::ToggleRedact, "R"),
+            (KeyAction::ToggleTranspose, "t"),
+            (KeyAction::ToggleZebra, "z"),
+            (KeyAction::IncreasePrecision, "+"),
+            (KeyAction::DecreasePrecision, "-"),
+            (KeyAction::ToggleRawUnits, "u"),
+            (KeyAction::CycleNumberFormat, "n"),
+            (KeyAction::ToggleHistogram, "H"),
+            (KeyAction::ToggleTopValues, "V"),
+            (KeyAction::ToggleMetrics, "M"),
+            (KeyAction::ToggleLegend, "L"),
+            (KeyAction::ToggleLockWidths, "w"),
+            (KeyAction::SortColumn, "s"),
+            (KeyAction::Filter, "f"),
+            (KeyAction::CommandPalette, ":"),
+            (KeyAction::NextView, "tab"),
+            (KeyAction::PrevView, "backtab"),
+            (KeyAction::ToggleSplit, "|"),
+            (KeyAction::SwitchPane, "\\"),
+        ];
+        let bindings = bindings.into_iter().map(|(a, k)| (a, vec![k.to_string()])).collect();
+        KeyBindings { bindings }
+    }
+}
+
+impl KeyBindings {
+    /// action bound to key chord
+    pub fn action(&self, chord: &str) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(_, chords)| chords.iter().any(|c| c == chord))
+            .map(|(action, _)| *action)
+    }
+
+    /// adapt bindings to nav mode
+    ///
+    /// in vim mode, chords taken over by vim motions, such as `l` of LiveWindow, are moved to
+    /// the chords of VIM_CHORDS, unless those are already bound
+    pub fn for_nav_mode(mut self, nav_mode: NavMode) -> KeyBindings {
+        if nav_mode != NavMode::Vim {
+            return self
+        }
+        let vim_keys = VimKeys::default();
+        for (chord, vim_chord) in VIM_CHORDS.iter() {
+            if !vim_keys.handles(chord) || self.action(vim_chord).is_some() {
+                continue
+            }
+            for chords in self.bindings.values_mut() {
+                for c in chords.iter_mut().filter(|c| c == chord) {
+                    *c = vim_chord.to_string();
+                }
+            }
+        }
+        self
+    }
+
+    /// bind action to chords, replacing its previous chords
+    pub fn bind(&mut self, action: KeyAction, chords: Vec<String>) -> Result<(), EtopError> {
+        let chords = chords.iter().map(|c| normalize_chord(c)).collect::<Result<_, _>>()?;
+        self.bindings.insert(action, chords);
+        Ok(())
+    }
+
+    /// check that no chord is bound to more than one action
+    pub fn validate(&self) -> Result<(), EtopError> {
+        let mut seen: HashMap<&str, KeyAction> = HashMap::new();
+        for (action, chords) in self.bindings.iter() {
+            for chord in chords.iter() {
+                if let Some(other) = seen.insert(chord.as_str(), *action) {
+                    if other != *action {
+                        return Err(EtopError::InvalidSpecification(format!(
+                            "key {} bound to both {:?} and {:?}",
+                            chord, other, action
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// load bindings from json file mapping actions to lists of chords
+    ///
+    /// actions not in the file keep their default chords, a missing file gives the defaults
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<KeyBindings, EtopError> {
+        let mut bindings = KeyBindings::default();
+        if !path.as_ref().exists() {
+            return Ok(bindings)
+        }
+        let contents = std::fs::read_to_string(path)?;
+        let overrides: HashMap<KeyAction, Vec<String>> = serde_json::from_str(&contents)?;
+        for (action, chords) in overrides.into_iter() {
+            bindings.bind(action, chords)?;
+        }
+        bindings.validate()?;
+        Ok(bindings)
+    }
+}
+
+/// put chord into canonical form, e.g. `Shift+Ctrl+Up` becomes `ctrl+shift+up`
+///
+/// shift of a single character key is folded into the character, as terminals report it, e.g.
+/// `Shift+Ctrl+x` becomes `ctrl+X`
+pub fn normalize_chord(chord: &str) -> Result<String, EtopError> {
+    // the key itself may be `+`, as in `+` or `alt++`
+    let mut parts: Vec<&str> = match chord.strip_suffix("++") {
+        Some(modifiers) => modifiers.split('+').chain(["+"]).collect(),
+        None if chord == "+" => vec!["+"],
+        None => chord.split('+').collect(),
+    };
+    let key = match parts.pop() {
+        Some(key) if !key.is_empty() => key,
+        _ => return Err(EtopError::ParseError(format!("invalid key: {}", chord))),
+    };
+    let mut modifiers = vec![];
+    for modifier in parts.iter() {
+        match modifier.to_lowercase().as_str() {
+            "ctrl" => modifiers.push(0),
+            "alt" => modifiers.push(1),
+            "shift" => modifiers.push(2),
+            _ => return Err(EtopError::ParseError(format!("invalid key modifier: {}", modifier))),
+        }
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    let key = match key.chars().count() {
+        1 if modifiers.contains(&2) => {
+            modifiers.retain(|m| *m != 2);
+            key.to_uppercase()
+        }
+        1 => key.to_string(),
+        _ => key.to_lowercase(),
+    };
+    let mut chord: Vec<String> =
+        modifiers.into_iter().map(|m| ["ctrl", "alt", "shift"][m].to_string()).collect();
+    chord.push(key);
+    Ok(chord.join("+"))
+}
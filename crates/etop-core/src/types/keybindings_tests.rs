@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod keybindings_tests {
+    use crate::{normalize_chord, KeyAction, KeyBindings, NavMode, VimKeys};
+
+    fn path(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("etop_{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn default_bindings() {
+        let bindings = KeyBindings::default();
+        bindings.validate().unwrap();
+        assert_eq!(bindings.action("q"), Some(KeyAction::Quit));
+        assert_eq!(bindings.action("up"), Some(KeyAction::CursorUp));
+        assert_eq!(bindings.action("x"), None);
+    }
+
+    #[test]
+    fn vim_bindings() {
+        // vim motions take over `l`, so live window moves to another chord
+        let bindings = KeyBindings::default().for_nav_mode(NavMode::Vim);
+        bindings.validate().unwrap();
+        assert!(VimKeys::default().handles("l"));
+        assert_eq!(bindings.action("l"), None);
+        assert_eq!(bindings.action("ctrl+l"), Some(KeyAction::LiveWindow));
+        assert!(!VimKeys::default().handles("ctrl+l"));
+        assert_eq!(bindings.action("q"), Some(KeyAction::Quit));
+
+        // bindings are unchanged outside of vim mode
+        let bindings = KeyBindings::default().for_nav_mode(NavMode::Default);
+        assert_eq!(bindings, KeyBindings::default());
+
+        // chords already bound elsewhere are not taken
+        let mut bindings = KeyBindings::default();
+        bindings.bind(KeyAction::Refresh, vec!["ctrl+l".to_string()]).unwrap();
+        let bindings = bindings.for_nav_mode(NavMode::Vim);
+        assert_eq!(bindings.action("ctrl+l"), Some(KeyAction::Refresh));
+        assert_eq!(bindings.action("l"), Some(KeyAction::LiveWindow));
+    }
+
+    #[test]
+    fn remapped_key() {
+        let path = path("remapped_key");
+        std::fs::write(&path, r#"{"Quit": ["x", "Ctrl+C"], "CursorDown": ["j", "down"]}"#).unwrap();
+        let bindings = KeyBindings::load(&path).unwrap();
+        assert_eq!(bindings.action("x"), Some(KeyAction::Quit));
+        assert_eq!(bindings.action("ctrl+C"), Some(KeyAction::Quit));
+        assert_eq!(bindings.action("q"), None);
+        assert_eq!(bindings.action("j"), Some(KeyAction::CursorDown));
+        assert_eq!(bindings.action("i"), Some(KeyAction::ToggleInspect));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(KeyBindings::load(&path).unwrap(), KeyBindings::default());
+    }
+
+    #[test]
+    fn duplicate_bindings() {
+        let path = path("duplicate_bindings");
+        std::fs::write(&path, r#"{"Quit": ["i"]}"#).unwrap();
+        assert!(KeyBindings::load(&path).is_err());
+
+        std::fs::write(&path, r#"{"NotAnAction": ["z"]}"#).unwrap();
+        assert!(KeyBindings::load(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let mut bindings = KeyBindings::default();
+        bindings.bind(KeyAction::Filter, vec!["s".to_string()]).unwrap();
+        assert!(bindings.validate().is_err());
+    }
+
+    #[test]
+    fn normalize_chords() {
+        assert_eq!(normalize_chord("q").unwrap(), "q");
+        assert_eq!(normalize_chord("Q").unwrap(), "Q");
+        assert_eq!(normalize_chord("Up").unwrap(), "up");
+        assert_eq!(normalize_chord("Shift+Ctrl+x").unwrap(), "ctrl+X");
+        assert_eq!(normalize_chord("shift+X").unwrap(), "X");
+        assert_eq!(normalize_chord("Shift+Ctrl+Up").unwrap(), "ctrl+shift+up");
+        assert_eq!(normalize_chord("+").unwrap(), "+");
+        assert_eq!(normalize_chord("alt++").unwrap(), "alt++");
+        assert!(normalize_chord("hyper+x").is_err());
+        assert!(normalize_chord("").is_err());
+        assert!(normalize_chord("ctrl+").is_err());
+    }
+}
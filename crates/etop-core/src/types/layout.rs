@@ -0,0 +1,222 @@
+#[cfg(test)]
+#[path = "layout_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState, FilterExpr};
+use etop_format::BorderStyle;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+/// runtime layout of a view, persisted across sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewLayout {
+    /// visible columns, in display order (None uses the dataset's default columns)
+    pub columns: Option<Vec<String>>,
+    /// sort keys, applied in order
+    pub sort: Vec<SortKey>,
+    /// filter expressions
+    pub filters: Vec<String>,
+    /// number of leading columns frozen during horizontal scroll
+    pub frozen_columns: usize,
+    /// maximum number of rows shown
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// whether to show a scrollbar in the right margin
+    #[serde(default)]
+    pub scrollbar: bool,
+    /// column whose top ranked rows are highlighted
+    #[serde(default)]
+    pub highlight: Option<String>,
+    /// whether to show columns as rows when the frame has few rows
+    #[serde(default)]
+    pub transpose: bool,
+    /// whether to shade the background of every other row
+    #[serde(default)]
+    pub zebra: bool,
+    /// whether to show a leading column numbering the rows in display order
+    #[serde(default)]
+    pub show_row_numbers: bool,
+    /// columns always shown at the right of the table, regardless of horizontal scroll
+    #[serde(default)]
+    pub pinned_columns: Vec<String>,
+    /// named sets of visible columns, in addition to those of the dataset
+    #[serde(default)]
+    pub presets: HashMap<String, Vec<String>>,
+    /// labels spanning the headers of groups of columns, with their member columns, in the
+    /// order they were declared
+    #[serde(default)]
+    pub column_groups: Vec<(String, Vec<String>)>,
+    /// style of lines between and around columns, None for the default separators
+    #[serde(default, with = "border_style_name")]
+    pub border_style: Option<BorderStyle>,
+    /// whether to draw borders with ascii characters only, in place of box-drawing characters
+    #[serde(default)]
+    pub ascii_borders: bool,
+}
+
+/// border style stored by name, as etop_format does not serialize its types
+mod border_style_name {
+    use etop_format::BorderStyle;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        style: &Option<BorderStyle>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        style.map(|style| style.name()).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<BorderStyle>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(name) => name.parse().map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+/// sort key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortKey {
+    /// column to sort by
+    pub column: String,
+    /// whether to sort descending
+    pub descending: bool,
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = EtopError;
+
+    /// parse sort key of the form `column [asc|desc]`
+    fn from_str(s: &str) -> Result<SortKey, EtopError> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let descending = match tokens.get(1).copied() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(other) => {
+                return Err(EtopError::ParseError(format!(
+                    "invalid sort direction {}, expected asc or desc",
+                    other
+                )))
+            }
+        };
+        match tokens.as_slice() {
+            [column] | [column, _] => Ok(SortKey { column: column.to_string(), descending }),
+            _ => Err(EtopError::ParseError("usage: sort <column> [asc|desc]".to_string())),
+        }
+    }
+}
+
+impl EtopState {
+    /// define named set of visible columns for current view, replacing any preset of that name
+    pub fn define_preset(&mut self, name: String, columns: Vec<String>) {
+        self.layout.presets.insert(name, columns);
+    }
+
+    /// show the columns of named preset, from the view's presets or else the dataset's
+    pub fn apply_preset(&mut self, name: &str) -> Result<(), EtopError> {
+        let columns = match self.layout.presets.get(name) {
+            Some(columns) => columns.clone(),
+            None => match self.dataspec()?.column_presets().remove(name) {
+                Some(columns) => columns,
+                None => return Err(EtopError::ArgumentError(format!("unknown preset: {}", name))),
+            },
+        };
+        for column in columns.iter() {
+            self.require_column(column)?;
+        }
+        self.layout.columns = Some(columns);
+        Ok(())
+    }
+
+    /// save layout of current view to file, keyed by view name
+    ///
+    /// layouts of other views already stored in the file are preserved
+    pub fn save_layout<P: AsRef<Path>>(&self, path: P) -> Result<(), EtopError> {
+        let mut layouts = read_layouts(path.as_ref())?;
+        layouts.insert(self.dataset.clone(), self.layout.clone());
+        let contents = serde_json::to_string_pretty(&layouts)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// load layout of current view from file
+    ///
+    /// columns that are no longer present in the view's data are dropped with a warning, once
+    /// the data is available, see check_layout
+    pub fn load_layout<P: AsRef<Path>>(&mut self, path: P) -> Result<(), EtopError> {
+        let mut layouts = read_layouts(path.as_ref())?;
+        let layout = match layouts.remove(&self.dataset) {
+            Some(layout) => layout,
+            None => {
+                return Err(EtopError::MissingData(format!("no layout saved for {}", self.dataset)))
+            }
+        };
+        self.layout = layout;
+        self.layout_unchecked = true;
+        self.check_layout();
+        Ok(())
+    }
+
+    /// drop columns of a loaded layout that are missing from the view's data, with a warning
+    /// for each
+    ///
+    /// covers visible, sorted, pinned, highlighted, filtered, and grouped columns, dropping
+    /// filters on missing columns, and does nothing until the data of the view is available
+    pub fn check_layout(&mut self) {
+        if !self.layout_unchecked {
+            return
+        }
+        let available = match self.available_columns() {
+            Ok(available) => available,
+            Err(_) => return,
+        };
+        self.layout_unchecked = false;
+        let stale = |column: &String| !available.contains(column);
+        let mut dropped: BTreeSet<String> = BTreeSet::new();
+        let layout = &mut self.layout;
+        if let Some(columns) = layout.columns.as_mut() {
+            dropped.extend(columns.iter().filter(|c| stale(c)).cloned());
+            columns.retain(|column| !stale(column));
+        }
+        dropped.extend(layout.sort.iter().map(|key| &key.column).filter(|c| stale(c)).cloned());
+        layout.sort.retain(|key| !stale(&key.column));
+        dropped.extend(layout.pinned_columns.iter().filter(|c| stale(c)).cloned());
+        layout.pinned_columns.retain(|column| !stale(column));
+        if let Some(column) = layout.highlight.take() {
+            match stale(&column) {
+                true => {
+                    dropped.insert(column);
+                }
+                false => layout.highlight = Some(column),
+            }
+        }
+        layout.filters.retain(|filter| match filter.parse::<FilterExpr>() {
+            Ok(expr) if stale(&expr.column) => {
+                dropped.insert(expr.column);
+                false
+            }
+            _ => true,
+        });
+        for (_, columns) in layout.column_groups.iter_mut() {
+            dropped.extend(columns.iter().filter(|c| stale(c)).cloned());
+            columns.retain(|column| !stale(column));
+        }
+        layout.column_groups.retain(|(_, columns)| !columns.is_empty());
+        for column in dropped.iter() {
+            self.messages.push(format!("dropping stale layout column: {}", column));
+        }
+    }
+}
+
+fn read_layouts(path: &Path) -> Result<HashMap<String, ViewLayout>, EtopError> {
+    if !path.exists() {
+        return Ok(HashMap::new())
+    }
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod layout_tests {
+    use crate::{EtopError, EtopState, SortKey, ViewLayout};
+    use etop_format::BorderStyle;
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!(
+            "block_number" => [1u32, 2, 3],
+            "gas_used" => [30u64, 10, 20],
+            "author" => ["a", "b", "c"],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    fn layout() -> ViewLayout {
+        ViewLayout {
+            columns: Some(vec!["gas_used".to_string(), "block_number".to_string()]),
+            sort: vec![SortKey { column: "gas_used".to_string(), descending: true }],
+            filters: vec!["gas_used > 10".to_string()],
+            frozen_columns: 1,
+            limit: None,
+            scrollbar: true,
+            highlight: Some("gas_used".to_string()),
+            transpose: true,
+            zebra: true,
+            show_row_numbers: true,
+            pinned_columns: vec!["block_number".to_string()],
+            presets: [("compact".to_string(), vec!["block_number".to_string()])].into(),
+            // groups keep their declared order, rather than that of their labels
+            column_groups: vec![
+                ("number".to_string(), vec!["block_number".to_string()]),
+                ("gas".to_string(), vec!["gas_used".to_string()]),
+            ],
+            border_style: Some(BorderStyle::Minimal),
+            ascii_borders: true,
+        }
+    }
+
+    fn path(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("etop_{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = path("round_trip");
+        let mut state = state();
+        state.layout = layout();
+        state.save_layout(&path).unwrap();
+
+        let mut other = EtopState { dataset: "other_view".to_string(), ..Default::default() };
+        other.layout.frozen_columns = 3;
+        other.save_layout(&path).unwrap();
+
+        let mut loaded = self::state();
+        loaded.load_layout(&path).unwrap();
+        assert_eq!(loaded.layout, layout());
+        assert!(loaded.messages.is_empty());
+
+        let mut loaded = EtopState { dataset: "other_view".to_string(), ..Default::default() };
+        loaded.load_layout(&path).unwrap();
+        assert_eq!(loaded.layout.frozen_columns, 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn layout_applied_to_frame() {
+        let mut state = state();
+        state.layout = layout();
+        let df = state.window_frame().unwrap();
+        assert_eq!(df.get_column_names(), vec!["gas_used", "block_number"]);
+        let blocks: Vec<u32> =
+            df.column("block_number").unwrap().u32().unwrap().into_no_null_iter().collect();
+        assert_eq!(blocks, vec![1, 3]);
+    }
+
+    #[test]
+    fn stale_columns_dropped() {
+        let path = path("stale_columns");
+        let mut state = state();
+        state.layout = layout();
+        state.layout.columns.as_mut().unwrap().push("removed_column".to_string());
+        state.layout.sort.push(SortKey { column: "removed_column".to_string(), descending: false });
+        state.layout.pinned_columns.push("removed_column".to_string());
+        state.layout.filters.push("old_gas < 5".to_string());
+        state.layout.highlight = Some("old_gas".to_string());
+        state.layout.column_groups[1].1.push("old_gas".to_string());
+        state.layout.column_groups.push(("old".to_string(), vec!["old_gas".to_string()]));
+        state.save_layout(&path).unwrap();
+
+        let mut loaded = self::state();
+        loaded.load_layout(&path).unwrap();
+        let expected = ViewLayout { highlight: None, ..layout() };
+        assert_eq!(loaded.layout, expected);
+        assert_eq!(
+            loaded.messages,
+            vec![
+                "dropping stale layout column: old_gas",
+                "dropping stale layout column: removed_column"
+            ]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn stale_columns_dropped_once_data_arrives() {
+        let path = path("stale_columns_deferred");
+        let mut state = state();
+        state.layout = layout();
+        state.layout.columns.as_mut().unwrap().push("removed_column".to_string());
+        state.save_layout(&path).unwrap();
+
+        // layouts load at startup, before any data is fetched
+        let mut loaded = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        loaded.load_layout(&path).unwrap();
+        assert!(loaded.layout_unchecked);
+        assert!(loaded.messages.is_empty());
+
+        loaded.warehouse = state.warehouse.clone();
+        loaded.cache_window(10, 80).unwrap();
+        assert!(!loaded.layout_unchecked);
+        assert_eq!(loaded.layout.columns, layout().columns);
+        assert_eq!(loaded.messages, vec!["dropping stale layout column: removed_column"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn missing_layout() {
+        let path = path("missing_layout");
+        assert!(state().load_layout(&path).is_err());
+    }
+
+    #[test]
+    fn presets() {
+        let mut state = state();
+        state.define_preset("compact".to_string(), vec!["author".into(), "gas_used".into()]);
+        state.apply_preset("compact").unwrap();
+        assert_eq!(state.window_frame().unwrap().get_column_names(), vec!["author", "gas_used"]);
+
+        // redefining replaces the preset
+        state.define_preset("compact".to_string(), vec!["block_number".into()]);
+        state.apply_preset("compact").unwrap();
+        assert_eq!(state.layout.columns, Some(vec!["block_number".to_string()]));
+
+        // unknown presets and columns are rejected, leaving the columns unchanged
+        assert!(matches!(state.apply_preset("missing"), Err(EtopError::ArgumentError(_))));
+        state.define_preset("stale".to_string(), vec!["gas_used".into(), "missing".into()]);
+        assert!(matches!(state.apply_preset("stale"), Err(EtopError::ColumnMissing(_))));
+        assert_eq!(state.layout.columns, Some(vec!["block_number".to_string()]));
+    }
+
+    #[test]
+    fn dataset_presets() {
+        let presets = crate::load_dataspec("blocks".to_string()).unwrap().column_presets();
+        assert_eq!(presets["compact"], vec!["block_number", "n_txs", "gas_used"]);
+        assert_eq!(presets["full"].len(), 6);
+        assert!(crate::load_dataspec("test_blocks".to_string())
+            .unwrap()
+            .column_presets()
+            .is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+#[path = "legend_tests.rs"]
+mod tests;
+
+use crate::{EtopState, DIFF_COLUMN};
+use etop_format::{CellFormatShorthand, NegativeColor, RankHighlight};
+
+/// text of the legend panel when no colors encode data
+pub const NO_LEGEND: &str = "no color encodings active";
+
+/// names of ansi foreground colors, indexed from code 30, or from 90 for bright colors
+const ANSI_COLOR_NAMES: [&str; 8] =
+    ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+impl EtopState {
+    /// description of each color encoding of the current table, one per line, None if no
+    /// colors encode data
+    ///
+    /// covers diff statuses, rank highlights, and colors of negative numbers, e.g.
+    /// `gas_used: #1=gold #2=silver #3=bronze` or `balance: red=negative`
+    pub fn render_legend(&self) -> Option<String> {
+        let df = self.current_frame();
+        let fmt = self.frame_format(df, 0, 0).ok()?;
+        let mut lines = Vec::new();
+        if df.column(DIFF_COLUMN).is_ok() {
+            let styles: Vec<String> = crate::DIFF_STYLES
+                .iter()
+                .map(|(status, style)| format!("{}={}", style, status))
+                .collect();
+            lines.push(format!("{}: {}", DIFF_COLUMN, styles.join(" ")));
+        }
+        for column in fmt.column_formats.iter().flatten() {
+            if let Some(rank_highlight) = &column.rank_highlight {
+                lines.push(format!("{}: {}", column.name, describe_ranks(rank_highlight)));
+            }
+            if let Some(color) = negative_color(&column.format) {
+                lines.push(format!("{}: {}=negative", column.name, ansi_color_name(color.code)));
+            }
+        }
+        match lines.is_empty() {
+            true => None,
+            false => Some(lines.join("\n")),
+        }
+    }
+}
+
+/// style of each rank, with the last style covering the remaining ranks, e.g. `#1=gold
+/// #2–#5=silver`
+fn describe_ranks(rank_highlight: &RankHighlight) -> String {
+    let n_styles = rank_highlight.styles.len().min(rank_highlight.n);
+    let mut ranks = Vec::new();
+    for (i, style) in rank_highlight.styles.iter().take(n_styles).enumerate() {
+        let style = style_name(style);
+        if i + 1 == n_styles && rank_highlight.n > n_styles {
+            ranks.push(format!("#{}–#{}={}", i + 1, rank_highlight.n, style));
+        } else {
+            ranks.push(format!("#{}={}", i + 1, style));
+        }
+    }
+    ranks.join(" ")
+}
+
+/// name of style, such as `gold` for the color of top ranked rows
+fn style_name(style: &str) -> &str {
+    match crate::RANK_STYLES.iter().position(|s| *s == style) {
+        Some(i) => crate::RANK_STYLE_NAMES[i],
+        None => style,
+    }
+}
+
+/// color of negative numbers of format, or of the first rule of a conditional format with one
+fn negative_color(format: &CellFormatShorthand) -> Option<NegativeColor> {
+    match format {
+        CellFormatShorthand::Number(fmt) => fmt.negative_color,
+        CellFormatShorthand::Conditional(rules) => {
+            rules.iter().find_map(|(_, format)| negative_color(format))
+        }
+        _ => None,
+    }
+}
+
+/// name of ansi foreground color code, e.g. `red` for 31
+fn ansi_color_name(code: u8) -> String {
+    match code {
+        30..=37 => ANSI_COLOR_NAMES[(code - 30) as usize].to_string(),
+        90..=97 => format!("bright {}", ANSI_COLOR_NAMES[(code - 90) as usize]),
+        code => format!("color {}", code),
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod legend_tests {
+    use crate::{EtopState, Panel, NO_LEGEND};
+    use etop_format::{
+        CellFormatShorthand, ColumnFormatShorthand, NegativeColorScope, NumberFormat, Predicate,
+    };
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!(
+            "gas_used" => [21_000u64, 50_000, 129_000],
+            "balance" => [1.5, -2.5, 0.0],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    #[test]
+    fn legend() {
+        let mut state = state();
+        state.cache_window(10, 60).unwrap();
+        assert_eq!(state.render_legend(), None);
+
+        // rank highlight and colored negative numbers
+        state.layout.highlight = Some("gas_used".to_string());
+        let red = NumberFormat::new().negative_color(31, NegativeColorScope::Whole);
+        let balance = ColumnFormatShorthand::new().name("balance").set_format(red);
+        state.column_formats.insert("balance".to_string(), balance);
+        state.cache_window(10, 60).unwrap();
+        assert_eq!(
+            state.render_legend().unwrap(),
+            "gas_used: #1=gold #2=silver #3=bronze\nbalance: red=negative"
+        );
+
+        // colors given by rules of conditional formats, and other styles named as given
+        let rules = vec![
+            (Predicate::Above(1.0), CellFormatShorthand::Number(NumberFormat::new())),
+            (
+                Predicate::Always,
+                NumberFormat::new().negative_color(91, NegativeColorScope::SignOnly).into(),
+            ),
+        ];
+        let balance = ColumnFormatShorthand::new()
+            .name("balance")
+            .set_format(CellFormatShorthand::Conditional(rules))
+            .rank_highlight(5, vec!["green".to_string(), "blue".to_string()]);
+        state.column_formats.insert("balance".to_string(), balance);
+        state.layout.highlight = None;
+        state.cache_window(10, 60).unwrap();
+        assert_eq!(
+            state.render_legend().unwrap(),
+            "balance: #1=green #2–#5=blue\nbalance: bright red=negative"
+        );
+    }
+
+    #[test]
+    fn legend_panel() {
+        let mut state = state();
+        state.cache_window(10, 60).unwrap();
+        state.toggle_panel(Panel::Legend);
+        assert_eq!(state.render_panel().unwrap().unwrap(), NO_LEGEND);
+
+        state.layout.highlight = Some("gas_used".to_string());
+        state.cache_window(10, 60).unwrap();
+        assert_eq!(state.render_panel().unwrap().unwrap(), "gas_used: #1=gold #2=silver #3=bronze");
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+#[path = "meta_banner_tests.rs"]
+mod tests;
+
+use crate::{EtopState, Granularity};
+use etop_format::NumberFormat;
+
+/// summary of the data behind the table, shown above it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetaBanner {
+    /// chain id of data source
+    pub chain_id: Option<u64>,
+    /// first block of window
+    pub start_block: Option<u32>,
+    /// last block of window
+    pub end_block: Option<u32>,
+    /// number of rows of window, before filters
+    pub total_rows: Option<usize>,
+    /// unix time when data was last received
+    pub fetched_at: Option<u64>,
+}
+
+impl MetaBanner {
+    /// render banner as a single line, e.g.
+    /// `ethereum | blocks 100–120 | 42 rows | fetched 2023-11-14 22:13:20`
+    ///
+    /// parts that are not known are left out
+    pub fn render_meta(&self) -> String {
+        let mut parts = vec![];
+        if let Some(chain_id) = self.chain_id {
+            match chain_name(chain_id) {
+                Some(name) => parts.push(name.to_string()),
+                None => parts.push(format!("chain {}", chain_id)),
+            }
+        }
+        match (self.start_block, self.end_block) {
+            (Some(start_block), Some(end_block)) => {
+                parts.push(Granularity::Block.describe_range(start_block, end_block))
+            }
+            (_, Some(end_block)) => parts.push(format!("block {}", end_block)),
+            (_, None) => {}
+        }
+        if let Some(total_rows) = self.total_rows {
+            parts.push(format!("{} rows", total_rows));
+        }
+        if let Some(fetched_at) = self.fetched_at {
+            if let Ok(time) = NumberFormat::new().timestamp().format(fetched_at as f64) {
+                parts.push(format!("fetched {}", time));
+            }
+        }
+        if parts.is_empty() {
+            return "-".to_string()
+        }
+        parts.join(" | ")
+    }
+}
+
+impl EtopState {
+    /// banner of the chain, block range, row count, and fetch time of the current window
+    pub fn meta_banner(&self) -> MetaBanner {
+        MetaBanner {
+            chain_id: self.chain_id,
+            start_block: self.window.start_block,
+            end_block: self.window.end_block,
+            total_rows: self.cache_df.as_ref().map(|_| self.cache_row_counts.1),
+            fetched_at: self.fetched_at,
+        }
+    }
+}
+
+/// name of well known chain
+pub fn chain_name(chain_id: u64) -> Option<&'static str> {
+    let name = match chain_id {
+        1 => "ethereum",
+        10 => "optimism",
+        56 => "bnb",
+        137 => "polygon",
+        8453 => "base",
+        42161 => "arbitrum",
+        11155111 => "sepolia",
+        _ => return None,
+    };
+    Some(name)
+}
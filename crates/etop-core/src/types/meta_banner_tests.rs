@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod meta_banner_tests {
+    use crate::{EtopState, MetaBanner, WindowSize};
+    use polars::prelude::*;
+
+    #[test]
+    fn render_meta() {
+        let df = df!("block_number" => [100u32, 110, 120], "n" => [1u32, 2, 3]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        assert_eq!(state.meta_banner().render_meta(), "-");
+
+        state.chain_id = Some(1);
+        state.window.size = WindowSize::Block(21);
+        state.window.set_end_block(120);
+        state.fetched_at = Some(1_700_000_000);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(
+            state.meta_banner().render_meta(),
+            "ethereum | blocks 100–120 | 3 rows | fetched 2023-11-14 22:13:20"
+        );
+
+        // banner follows navigation of the window
+        state.decrement_block(5);
+        state.layout.filters = vec!["n > 1".to_string()];
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(
+            state.meta_banner().render_meta(),
+            "ethereum | blocks 95–115 | 2 rows | fetched 2023-11-14 22:13:20"
+        );
+
+        let banner = MetaBanner { chain_id: Some(5), end_block: Some(7), ..Default::default() };
+        assert_eq!(banner.render_meta(), "chain 5 | block 7");
+    }
+}
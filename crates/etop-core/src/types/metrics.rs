@@ -0,0 +1,78 @@
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+use etop_format::NumberFormat;
+use polars::prelude::*;
+
+/// delimiter between the cells of the metrics dashboard
+const METRICS_DELIMITER: &str = "  │  ";
+
+/// aggregate of the data of a dataset, shown as a labeled number in the metrics dashboard
+#[derive(Debug, Clone)]
+pub struct MetricSpec {
+    /// label of metric, e.g. "total gas"
+    pub name: String,
+    /// expression aggregating the transformed data to a single value, e.g. `col("gas").sum()`
+    pub expr: Expr,
+    /// format of value
+    pub format: NumberFormat,
+}
+
+impl MetricSpec {
+    /// create metric with default format
+    pub fn new<T: AsRef<str>>(name: T, expr: Expr) -> MetricSpec {
+        MetricSpec { name: name.as_ref().to_string(), expr, format: NumberFormat::new() }
+    }
+
+    /// set format
+    pub fn set_format(mut self, format: NumberFormat) -> MetricSpec {
+        self.format = format;
+        self
+    }
+}
+
+impl EtopState {
+    /// value of each metric over the transformed data of current window, after filters
+    ///
+    /// values that are null or not numeric are None
+    pub fn compute_metrics(&self, metrics: &[MetricSpec]) -> Result<Vec<Option<f64>>, EtopError> {
+        let df = crate::apply_filters(self.transformed_frame()?, &self.layout.filters)?;
+        let exprs: Vec<Expr> = metrics
+            .iter()
+            .enumerate()
+            .map(|(i, metric)| {
+                metric.expr.clone().cast(DataType::Float64).alias(format!("metric_{}", i).as_str())
+            })
+            .collect();
+        let values = df.lazy().select(exprs).collect()?;
+        let values = values
+            .get_columns()
+            .iter()
+            .map(|series| match series.get(0) {
+                Ok(AnyValue::Float64(value)) => Some(value),
+                _ => None,
+            })
+            .collect();
+        Ok(values)
+    }
+
+    /// metrics as a row of cells, each a label above its formatted value
+    pub fn render_metrics(&self, metrics: &[MetricSpec]) -> Result<String, EtopError> {
+        if metrics.is_empty() {
+            return Err(EtopError::MissingData(format!("no metrics for {}", self.dataset)))
+        }
+        let values = self.compute_metrics(metrics)?;
+        let mut labels = vec![];
+        let mut cells = vec![];
+        for (metric, value) in metrics.iter().zip(values) {
+            let cell = metric.format.format_option(value, &metric.format.null_repr)?;
+            let cell = cell.trim().to_string();
+            let width = metric.name.chars().count().max(cell.chars().count());
+            labels.push(format!("{:>width$}", metric.name, width = width));
+            cells.push(format!("{:>width$}", cell, width = width));
+        }
+        Ok(format!("{}\n{}", labels.join(METRICS_DELIMITER), cells.join(METRICS_DELIMITER)))
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod metrics_tests {
+    use crate::{EtopState, MetricSpec};
+    use etop_format::NumberFormat;
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!(
+            "gas_used" => [21_000u64, 50_000, 129_000],
+            "fee" => [Some(1.5), Some(2.5), None],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    fn metrics() -> Vec<MetricSpec> {
+        vec![
+            MetricSpec::new("total gas", col("gas_used").sum())
+                .set_format(NumberFormat::new().integer_oom().precision(1)),
+            MetricSpec::new("tx count", count()).set_format(NumberFormat::new().precision(0)),
+            MetricSpec::new("avg fee", col("fee").mean())
+                .set_format(NumberFormat::new().precision(2)),
+        ]
+    }
+
+    #[test]
+    fn compute_metrics() {
+        let mut state = state();
+        assert_eq!(
+            state.compute_metrics(&metrics()).unwrap(),
+            vec![Some(200_000.0), Some(3.0), Some(2.0)]
+        );
+
+        // metrics follow filters, and aggregates of no values are None
+        state.layout.filters = vec!["gas_used > 100000".to_string()];
+        assert_eq!(
+            state.compute_metrics(&metrics()).unwrap(),
+            vec![Some(129_000.0), Some(1.0), None]
+        );
+    }
+
+    #[test]
+    fn render_metrics() {
+        let state = state();
+        let rendered = state.render_metrics(&metrics()).unwrap();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            lines,
+            vec!["total gas  │  tx count  │  avg fee", "   200.0K  │         3  │     2.00"]
+        );
+        assert!(state.render_metrics(&[]).is_err());
+    }
+}
@@ -1,15 +1,62 @@
+mod annotations;
+mod block_range;
+mod column_formats;
+mod command;
 mod dataframes;
+mod datasource;
 mod dataspecs;
+mod diff;
+mod distribution;
 mod exceptions;
+mod fetch;
+mod filter;
+mod keybindings;
+mod layout;
+mod legend;
+mod meta_banner;
+mod metrics;
+mod navigation;
+mod panes;
+mod plain;
+mod prompt;
 mod query;
+mod refresh;
+mod render_plan;
+mod row_limit;
+mod split;
 mod ui;
+mod validate;
+mod views;
 mod warehouse;
 mod windows;
 
+pub use annotations::*;
+pub use block_range::*;
+pub use column_formats::*;
+pub use command::*;
 pub use dataframes::*;
+pub use datasource::*;
 pub use dataspecs::*;
+pub use diff::*;
+pub use distribution::*;
 pub use exceptions::*;
+pub use fetch::*;
+pub use filter::*;
+pub use keybindings::*;
+pub use layout::*;
+pub use legend::*;
+pub use meta_banner::*;
+pub use metrics::*;
+pub use navigation::*;
+pub use panes::*;
+pub use plain::*;
+pub use prompt::*;
 pub use query::*;
+pub use refresh::*;
+pub use render_plan::*;
+pub use row_limit::*;
+pub use split::*;
 pub use ui::*;
+pub use views::*;
 pub use warehouse::*;
 pub use windows::*;
@@ -0,0 +1,102 @@
+#[cfg(test)]
+#[path = "navigation_tests.rs"]
+mod tests;
+
+use crate::EtopState;
+
+/// keymap used for navigation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NavMode {
+    /// keys are dispatched through keybindings only
+    #[default]
+    Default,
+    /// vim motions take precedence over keybindings
+    Vim,
+}
+
+/// navigation produced by vim keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavAction {
+    /// move cursor by (rows, columns)
+    Move(i64, i64),
+    /// move cursor to first row
+    Top,
+    /// move cursor to last row
+    Bottom,
+    /// move cursor to row, counting from 0
+    Row(usize),
+    /// open search prompt
+    Search,
+    /// open command prompt
+    Command,
+}
+
+/// state of a vim key sequence being typed, such as `10j` or `gg`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VimKeys {
+    count: Option<usize>,
+    pending_g: bool,
+}
+
+impl VimKeys {
+    /// feed key chord into sequence, returning navigation if the sequence is complete
+    ///
+    /// keys that are not part of a vim sequence reset the sequence and give None
+    pub fn key(&mut self, chord: &str) -> Option<NavAction> {
+        let count = self.count.take();
+        let pending_g = std::mem::take(&mut self.pending_g);
+        let n = count.unwrap_or(1) as i64;
+        match chord {
+            "0" if count.is_none() => None,
+            digit if digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()) => {
+                let digit = digit.parse::<usize>().unwrap_or(0);
+                self.count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+                None
+            }
+            "j" | "down" => Some(NavAction::Move(n, 0)),
+            "k" | "up" => Some(NavAction::Move(-n, 0)),
+            "h" | "left" => Some(NavAction::Move(0, -n)),
+            "l" | "right" => Some(NavAction::Move(0, n)),
+            "g" if pending_g => {
+                Some(count.map(|c| NavAction::Row(c - 1)).unwrap_or(NavAction::Top))
+            }
+            "g" => {
+                self.count = count;
+                self.pending_g = true;
+                None
+            }
+            "G" => Some(count.map(|c| NavAction::Row(c - 1)).unwrap_or(NavAction::Bottom)),
+            "/" => Some(NavAction::Search),
+            ":" => Some(NavAction::Command),
+            _ => None,
+        }
+    }
+
+    /// whether chord is consumed by vim sequences, rather than passed on to keybindings
+    pub fn handles(&self, chord: &str) -> bool {
+        matches!(chord, "j" | "k" | "h" | "l" | "g" | "G" | "/" | ":") ||
+            (chord.len() == 1 && chord.chars().all(|c| c.is_ascii_digit())) ||
+            (self.pending_g || self.count.is_some())
+    }
+}
+
+impl EtopState {
+    /// apply navigation to the inspected cell, starting inspection if needed
+    pub fn navigate(&mut self, action: &NavAction) {
+        if self.selected_cell.is_none() {
+            self.toggle_inspect();
+        }
+        let n_rows = self.cache_df.as_ref().map(|df| df.height()).unwrap_or(0) as i64;
+        match action {
+            NavAction::Move(rows, columns) => self.move_selected_cell(*rows, *columns),
+            NavAction::Top => self.move_selected_cell(-n_rows, 0),
+            NavAction::Bottom => self.move_selected_cell(n_rows, 0),
+            NavAction::Row(row) => {
+                if let Some((current, _)) = self.selected_cell {
+                    self.move_selected_cell(*row as i64 - current as i64, 0)
+                }
+            }
+            NavAction::Search | NavAction::Command => {}
+        }
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod navigation_tests {
+    use crate::{EtopState, NavAction, VimKeys};
+    use polars::prelude::*;
+
+    fn keys(vim: &mut VimKeys, chords: &str) -> Vec<NavAction> {
+        chords.chars().filter_map(|c| vim.key(&c.to_string())).collect()
+    }
+
+    #[test]
+    fn numeric_prefix() {
+        let mut vim = VimKeys::default();
+        assert_eq!(keys(&mut vim, "j"), vec![NavAction::Move(1, 0)]);
+        assert_eq!(keys(&mut vim, "10j"), vec![NavAction::Move(10, 0)]);
+        assert_eq!(keys(&mut vim, "3k2h"), vec![NavAction::Move(-3, 0), NavAction::Move(0, -2)]);
+        assert_eq!(keys(&mut vim, "gg"), vec![NavAction::Top]);
+        assert_eq!(keys(&mut vim, "G"), vec![NavAction::Bottom]);
+        assert_eq!(keys(&mut vim, "5G"), vec![NavAction::Row(4)]);
+        assert_eq!(keys(&mut vim, "12gg"), vec![NavAction::Row(11)]);
+
+        // other keys cancel a pending sequence
+        assert_eq!(keys(&mut vim, "5xj"), vec![NavAction::Move(1, 0)]);
+        assert_eq!(keys(&mut vim, "gxg"), vec![]);
+        assert_eq!(keys(&mut vim, "g"), vec![NavAction::Top]);
+    }
+
+    #[test]
+    fn handled_keys() {
+        let mut vim = VimKeys::default();
+        assert!(vim.handles("j"));
+        assert!(vim.handles("7"));
+        assert!(!vim.handles("q"));
+        vim.key("3");
+        assert!(vim.handles("q"));
+        assert_eq!(vim.key("q"), None);
+        assert!(!vim.handles("q"));
+    }
+
+    #[test]
+    fn navigate() {
+        let df = df!("a" => [1, 2, 3, 4, 5], "b" => [5, 4, 3, 2, 1]).unwrap();
+        let mut state = EtopState { cache_df: Some(df), ..Default::default() };
+        state.navigate(&NavAction::Move(2, 1));
+        assert_eq!(state.selected_cell, Some((2, 1)));
+        state.navigate(&NavAction::Bottom);
+        assert_eq!(state.selected_cell, Some((4, 1)));
+        state.navigate(&NavAction::Top);
+        assert_eq!(state.selected_cell, Some((0, 1)));
+        state.navigate(&NavAction::Row(3));
+        assert_eq!(state.selected_cell, Some((3, 1)));
+        state.navigate(&NavAction::Row(30));
+        assert_eq!(state.selected_cell, Some((4, 1)));
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+#[path = "panes_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+use etop_format::DataFrameFormat;
+use polars::prelude::*;
+
+/// separator between panes side by side
+pub const PANE_SEPARATOR: &str = " │ ";
+
+/// region of the table area given to a pane, in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneRect {
+    /// column of left edge
+    pub x: usize,
+    /// row of top edge
+    pub y: usize,
+    /// number of columns
+    pub width: usize,
+    /// number of rows
+    pub height: usize,
+}
+
+/// split area into a grid of panes, filled row by row
+///
+/// the grid has as many columns as rows, or one more, so that two panes are split side by side,
+/// columns are separated by PANE_SEPARATOR and rows by a blank line, and leftover cells go to
+/// the leading panes
+pub fn split_layout(n_panes: usize, width: usize, height: usize) -> Vec<PaneRect> {
+    if n_panes == 0 {
+        return vec![]
+    }
+    let n_columns = (1..=n_panes).find(|c| c * c >= n_panes).unwrap_or(n_panes);
+    let n_rows = (n_panes + n_columns - 1) / n_columns;
+    let widths = split_evenly(width, n_columns, PANE_SEPARATOR.chars().count());
+    let heights = split_evenly(height, n_rows, 1);
+    (0..n_panes)
+        .map(|pane| {
+            let (row, column) = (pane / n_columns, pane % n_columns);
+            PaneRect {
+                x: widths[..column].iter().map(|w| w + PANE_SEPARATOR.chars().count()).sum(),
+                y: heights[..row].iter().map(|h| h + 1).sum(),
+                width: widths[column],
+                height: heights[row],
+            }
+        })
+        .collect()
+}
+
+/// sizes of n parts of total, after a gap between each part, leading parts taking the remainder
+fn split_evenly(total: usize, n: usize, gap: usize) -> Vec<usize> {
+    let available = total.saturating_sub(gap * (n - 1));
+    (0..n).map(|i| available / n + usize::from(i < available % n)).collect()
+}
+
+/// frames of panes of a window, kept until the window or its data change
+#[derive(Debug, Clone, Default)]
+pub struct PaneFrames {
+    /// start and end block of window of frames
+    pub blocks: (Option<u32>, Option<u32>),
+    /// named frames, in pane order
+    pub frames: Vec<(String, DataFrame)>,
+}
+
+impl EtopState {
+    /// whether panes are shown in place of the table, which commands acting on the table
+    /// cannot reach
+    pub fn panes_shown(&self) -> bool {
+        self.cache_panes.is_some()
+    }
+
+    /// named frames of current window, in pane order, empty if the dataset has a single frame
+    pub fn pane_frames(&self) -> Result<Vec<(String, DataFrame)>, EtopError> {
+        let dataspec = self.dataspec()?;
+        let names = dataspec.pane_names();
+        if names.is_empty() {
+            return Ok(vec![])
+        }
+        let mut frames = dataspec.transform_multi(
+            &self.warehouse,
+            self.window.start_block,
+            self.window.end_block,
+        )?;
+        names
+            .into_iter()
+            .map(|name| match frames.remove(&name) {
+                Some(df) => Ok((name, df)),
+                None => Err(EtopError::MissingData(format!("no frame for pane {}", name))),
+            })
+            .collect()
+    }
+
+    /// named frames of current window, transformed once per window and refresh of its data
+    fn cached_pane_frames(&mut self) -> Result<Vec<(String, DataFrame)>, EtopError> {
+        let blocks = (self.window.start_block, self.window.end_block);
+        if let Some(cached) = self.cache_pane_frames.as_ref().filter(|c| c.blocks == blocks) {
+            return Ok(cached.frames.clone())
+        }
+        let frames = self.pane_frames()?;
+        self.cache_pane_frames = Some(PaneFrames { blocks, frames: frames.clone() });
+        Ok(frames)
+    }
+
+    /// frames of current window laid out in a grid of panes, each titled by its name, None if
+    /// the dataset has a single frame
+    ///
+    /// panes use the dataset's column formats, while sort, filters, and the other options of the
+    /// layout only apply to the table, and a failure to transform the frames is shown in place
+    /// of the panes
+    pub fn format_panes(
+        &mut self,
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<Option<String>, EtopError> {
+        if self.dataspec()?.pane_names().is_empty() {
+            return Ok(None)
+        }
+        let frames = match self.cached_pane_frames() {
+            Ok(frames) => frames,
+            Err(e) => return Ok(Some(format!("{:?}", e))),
+        };
+        let rects = split_layout(frames.len(), render_width, render_height);
+        let mut panes = Vec::new();
+        for ((name, df), rect) in frames.into_iter().zip(rects) {
+            let render = self.format_pane(&df, rect.height.saturating_sub(1), rect.width)?;
+            panes.push((name, rect, render));
+        }
+        Ok(Some(compose_panes(&panes, render_height)))
+    }
+
+    fn format_pane(
+        &self,
+        df: &DataFrame,
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<String, EtopError> {
+        let dataset_formats = self.dataspec()?.default_column_formats().unwrap_or_default();
+        let column_formats = df
+            .schema()
+            .iter()
+            .map(|(name, dtype)| self.resolve_column_format(&dataset_formats, name, dtype))
+            .collect::<Result<Vec<_>, EtopError>>()?;
+        let fmt = DataFrameFormat {
+            column_formats: Some(column_formats),
+            render_height: Some(render_height),
+            max_render_width: Some(render_width),
+            include_header_separator_row: true,
+            column_delimiter: "   ".to_string(),
+            header_separator_delimiter: "───".to_string(),
+            ..Default::default()
+        };
+        Ok(fmt.format(df.clone())?)
+    }
+}
+
+/// text of panes placed in their regions, each a title line above its render
+///
+/// separators run the full height of panes to the right of others
+pub(crate) fn compose_panes(panes: &[(String, PaneRect, String)], render_height: usize) -> String {
+    let mut lines = vec![String::new(); render_height];
+    for (title, rect, render) in panes.iter() {
+        let pane_lines = std::iter::once(title.as_str()).chain(render.lines());
+        let pane_lines = pane_lines.chain(std::iter::repeat(""));
+        for (i, pane_line) in pane_lines.take(rect.height).enumerate() {
+            let line = match lines.get_mut(rect.y + i) {
+                Some(line) => line,
+                None => break,
+            };
+            if rect.x > 0 {
+                pad(line, rect.x.saturating_sub(PANE_SEPARATOR.chars().count()));
+                line.push_str(PANE_SEPARATOR);
+            }
+            line.extend(pane_line.chars().take(rect.width));
+        }
+    }
+    let lines: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+/// pad line with spaces to width
+fn pad(line: &mut String, width: usize) {
+    let n_chars = line.chars().count();
+    line.extend(std::iter::repeat(' ').take(width.saturating_sub(n_chars)));
+}
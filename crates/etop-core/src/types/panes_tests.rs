@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod panes_tests {
+    use crate::{parse_command, split_layout, EtopError, EtopState, PaneRect};
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let txs = df!(
+            "block_number" => [1u32, 1, 2, 3, 3, 3],
+            "from_address" => ["a", "b", "a", "a", "c", "b"],
+            "gas_used" => [10u64, 20, 30, 40, 50, 60],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "block_summaries".to_string(), ..Default::default() };
+        state.warehouse.data.insert("transactions".to_string(), txs);
+        state
+    }
+
+    fn rect(x: usize, y: usize, width: usize, height: usize) -> PaneRect {
+        PaneRect { x, y, width, height }
+    }
+
+    #[test]
+    fn split_layouts() {
+        assert!(split_layout(0, 80, 20).is_empty());
+        assert_eq!(split_layout(1, 80, 20), vec![rect(0, 0, 80, 20)]);
+        // side by side, the odd column going to the left pane
+        assert_eq!(split_layout(2, 80, 20), vec![rect(0, 0, 39, 20), rect(42, 0, 38, 20)]);
+        // a 2x2 grid with its last cell empty
+        assert_eq!(
+            split_layout(3, 81, 21),
+            vec![rect(0, 0, 39, 10), rect(42, 0, 39, 10), rect(0, 11, 39, 10)]
+        );
+        assert_eq!(split_layout(5, 80, 20).len(), 5);
+        assert_eq!(split_layout(5, 80, 20)[4], rect(28, 11, 25, 9));
+    }
+
+    #[test]
+    fn transform_multi() {
+        let state = state();
+        let dataspec = crate::load_dataspec(state.dataset.clone()).unwrap();
+        let frames = dataspec.transform_multi(&state.warehouse, None, None).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames["per_block"].column("n_txs").unwrap().len(), 3);
+        assert_eq!(frames["per_sender"].column("n_txs").unwrap().len(), 3);
+
+        // the frame of the table is the first pane
+        let df = dataspec.transform(&state.warehouse, None, None).unwrap();
+        assert!(df.frame_equal(&frames["per_block"]));
+
+        // single frame datasets keep their frame of transform under their name
+        let blocks = crate::load_dataspec("test_blocks".to_string()).unwrap();
+        let mut warehouse = state.warehouse.clone();
+        warehouse.data.insert("test_blocks".to_string(), df!("n" => [1u32]).unwrap());
+        let frames = blocks.transform_multi(&warehouse, None, None).unwrap();
+        assert_eq!(frames.keys().collect::<Vec<_>>(), vec!["test_blocks"]);
+        assert!(blocks.pane_names().is_empty());
+    }
+
+    #[test]
+    fn pane_frames_cached() {
+        let mut state = state();
+        state.cache_window(10, 80).unwrap();
+        let render = state.cache_panes.clone().unwrap();
+
+        // frames are kept until the window changes
+        state.warehouse.data.remove("transactions");
+        state.cache_window(10, 80).unwrap();
+        assert_eq!(state.cache_panes, Some(render));
+
+        // and failing to transform them is shown in place of the panes
+        state.window.end_block = Some(2);
+        state.cache_window(10, 80).unwrap();
+        assert!(state.cache_panes.unwrap().contains("transactions"));
+    }
+
+    #[test]
+    fn format_panes() {
+        let mut state = state();
+        let names: Vec<String> = state.pane_frames().unwrap().into_iter().map(|p| p.0).collect();
+        assert_eq!(names, vec!["per_block", "per_sender"]);
+
+        let render = state.format_panes(10, 80).unwrap().unwrap();
+        let lines: Vec<&str> = render.lines().collect();
+        assert!(lines[0].starts_with("per_block"));
+        assert_eq!(lines[0].find("│ per_sender"), Some(40));
+        assert!(lines.iter().all(|line| line.chars().nth(40) == Some('│')));
+
+        state.cache_window(10, 80).unwrap();
+        assert_eq!(state.cache_panes, Some(render));
+
+        // the hidden table gives commands nothing to act on
+        assert!(state.cache_df.is_none());
+        state.toggle_inspect();
+        assert_eq!(state.selected_cell, None);
+        let sort = parse_command("sort n_txs").unwrap();
+        assert!(matches!(state.execute_command(sort), Err(EtopError::ArgumentError(_))));
+        let goto = parse_command("goto 2").unwrap();
+        assert!(state.clone().execute_command(goto).is_ok());
+
+        // single frame datasets have no panes
+        state.dataset = "test_blocks".to_string();
+        state.warehouse.data.insert("test_blocks".to_string(), df!("n" => [1u32]).unwrap());
+        assert_eq!(state.format_panes(10, 80).unwrap(), None);
+        state.cache_window(10, 80).unwrap();
+        assert_eq!(state.cache_panes, None);
+    }
+}
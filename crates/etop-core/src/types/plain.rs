@@ -0,0 +1,56 @@
+#[cfg(test)]
+#[path = "plain_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState};
+
+/// options of plain text rendering, for pasting tables into chat
+#[derive(Debug, Clone)]
+pub struct PlainOpts {
+    /// rows of text available to the table, including header
+    pub render_height: usize,
+    /// columns of text available to the table, excluding margin
+    pub render_width: usize,
+    /// strip trailing whitespace of each line
+    pub trim_trailing: bool,
+    /// spaces added to the left of each line
+    pub margin: usize,
+    /// wrap table in a ``` code fence
+    pub fence: bool,
+}
+
+impl Default for PlainOpts {
+    fn default() -> PlainOpts {
+        PlainOpts {
+            render_height: 40,
+            render_width: 120,
+            trim_trailing: true,
+            margin: 0,
+            fence: false,
+        }
+    }
+}
+
+impl EtopState {
+    /// format data of current window as plain text, padded and fenced for chat
+    pub fn render_plain(&self, opts: PlainOpts) -> Result<String, EtopError> {
+        let render = self.format_window(opts.render_height, opts.render_width)?;
+        let margin = " ".repeat(opts.margin);
+        let mut lines: Vec<String> = render
+            .lines()
+            .map(|line| {
+                let line = if opts.trim_trailing { line.trim_end() } else { line };
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}", margin, line)
+                }
+            })
+            .collect();
+        if opts.fence {
+            lines.insert(0, "```".to_string());
+            lines.push("```".to_string());
+        }
+        Ok(lines.join("\n"))
+    }
+}
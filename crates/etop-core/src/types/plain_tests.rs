@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod plain_tests {
+    use crate::{EtopState, PlainOpts};
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!("name" => ["a", "bb"], "n" => [1u32, 22]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    #[test]
+    fn trim_and_margin() {
+        let state = state();
+        let plain = state.render_plain(PlainOpts { margin: 2, ..Default::default() }).unwrap();
+        assert_eq!(plain, ["  name    n", "  ─────────", "     a    1", "    bb   22"].join("\n"));
+
+        // untrimmed lines keep the padding of the render
+        let opts = PlainOpts { trim_trailing: false, ..Default::default() };
+        let untrimmed = state.render_plain(opts).unwrap();
+        assert_eq!(untrimmed, state.format_window(40, 120).unwrap());
+    }
+
+    #[test]
+    fn fence() {
+        let state = state();
+        let fenced = state.render_plain(PlainOpts { fence: true, ..Default::default() }).unwrap();
+        assert_eq!(
+            fenced,
+            ["```", "name    n", "─────────", "   a    1", "  bb   22", "```"].join("\n")
+        );
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+#[path = "prompt_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState, FilterExpr, SortKey};
+
+/// what the text typed into a prompt refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptContext {
+    /// name of a column to sort by
+    Column,
+    /// filter expression
+    Filter,
+    /// text to search for in cells
+    Search,
+    /// command, such as `sort gas_used desc`
+    Command,
+    /// answer to a fetch of the given estimated number of rows exceeding max_rows, `y` to
+    /// proceed or a number of blocks to narrow the window to
+    RowLimit(usize),
+}
+
+/// interactive text input, with tab-completion against a list of candidates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prompt {
+    /// context of prompt
+    pub context: PromptContext,
+    /// text entered so far
+    pub text: String,
+    /// completions being cycled through by repeated tabs
+    completions: Vec<String>,
+    /// index of completion currently shown
+    completion_index: usize,
+    /// byte offset in text where the completed word starts
+    completion_start: usize,
+}
+
+/// candidates that start with prefix, sorted and deduplicated
+pub fn complete(prefix: &str, candidates: &[String]) -> Vec<String> {
+    let mut completions: Vec<String> =
+        candidates.iter().filter(|c| c.starts_with(prefix)).cloned().collect();
+    completions.sort();
+    completions.dedup();
+    completions
+}
+
+impl Prompt {
+    /// create new empty prompt
+    pub fn new(context: PromptContext) -> Prompt {
+        Prompt {
+            context,
+            text: String::new(),
+            completions: vec![],
+            completion_index: 0,
+            completion_start: 0,
+        }
+    }
+
+    /// append char to text
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+        self.completions.clear();
+    }
+
+    /// remove last char of text
+    pub fn backspace(&mut self) {
+        self.text.pop();
+        self.completions.clear();
+    }
+
+    /// complete word being typed, cycling through completions on repeated calls
+    ///
+    /// column and search prompts complete the whole text, others complete the last word
+    pub fn complete(&mut self, candidates: &[String]) {
+        if self.completions.is_empty() {
+            self.completion_start = match self.context {
+                PromptContext::Column | PromptContext::Search | PromptContext::RowLimit(_) => 0,
+                PromptContext::Filter | PromptContext::Command => {
+                    self.text.rfind(|c: char| c.is_whitespace()).map(|i| i + 1).unwrap_or(0)
+                }
+            };
+            self.completions = complete(&self.text[self.completion_start..], candidates);
+            self.completion_index = 0;
+        } else {
+            self.completion_index = (self.completion_index + 1) % self.completions.len();
+        }
+        if let Some(completion) = self.completions.get(self.completion_index) {
+            self.text.truncate(self.completion_start);
+            self.text.push_str(completion);
+        }
+    }
+
+    /// text shown in prompt line
+    pub fn render(&self) -> String {
+        let label = match self.context {
+            PromptContext::Column => "sort",
+            PromptContext::Filter => "filter",
+            PromptContext::Search => return format!("/{}", self.text),
+            PromptContext::Command => return format!(":{}", self.text),
+            PromptContext::RowLimit(rows) => {
+                return format!(
+                    "fetch of ~{} rows exceeds max rows, y to proceed or blocks to narrow to: {}",
+                    rows, self.text
+                )
+            }
+        };
+        format!("{}: {}", label, self.text)
+    }
+}
+
+impl EtopState {
+    /// open prompt, discarding any prompt already open
+    pub fn open_prompt(&mut self, context: PromptContext) {
+        self.prompt = Some(Prompt::new(context));
+    }
+
+    /// close prompt without submitting it
+    pub fn close_prompt(&mut self) {
+        self.prompt = None;
+    }
+
+    /// tab-complete prompt text against the columns of current view
+    pub fn complete_prompt(&mut self) {
+        let candidates = self.available_columns().unwrap_or_default();
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.complete(&candidates);
+        }
+    }
+
+    /// close prompt and apply its text to the layout of current view
+    ///
+    /// a column prompt sorts by the column, descending, or flips the direction if the view
+    /// is already sorted by it; a filter prompt adds a filter; a search prompt moves the
+    /// inspected cell to the next match; a command prompt executes the command; a row limit
+    /// prompt raises max_rows to the estimated rows or narrows the window
+    pub fn submit_prompt(&mut self) -> Result<(), EtopError> {
+        let prompt = match self.prompt.take() {
+            Some(prompt) => prompt,
+            None => return Ok(()),
+        };
+        let text = prompt.text.trim().to_string();
+        if text.is_empty() {
+            return Ok(())
+        }
+        match prompt.context {
+            PromptContext::Column => {
+                if !self.available_columns()?.contains(&text) {
+                    return Err(EtopError::ColumnMissing(format!("no column named {}", text)))
+                }
+                let descending = match self.layout.sort.first() {
+                    Some(key) if key.column == text => !key.descending,
+                    _ => true,
+                };
+                self.layout.sort = vec![SortKey { column: text, descending }];
+            }
+            PromptContext::Filter => {
+                text.parse::<FilterExpr>()?;
+                self.layout.filters.push(text)
+            }
+            PromptContext::Search => self.search(&text)?,
+            PromptContext::Command => self.execute_command(crate::parse_command(&text)?)?,
+            PromptContext::RowLimit(rows) => match text.as_str() {
+                "y" | "yes" => self.max_rows = Some(rows),
+                _ => {
+                    let n_blocks = text.parse::<u32>().map_err(|_| {
+                        EtopError::ParseError(format!("expected y or number of blocks: {}", text))
+                    })?;
+                    self.narrow_window(n_blocks)?
+                }
+            },
+        }
+        Ok(())
+    }
+}
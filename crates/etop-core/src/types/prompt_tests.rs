@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod prompt_tests {
+    use crate::{complete, EtopState, Prompt, PromptContext, SortKey};
+    use polars::prelude::*;
+
+    fn candidates() -> Vec<String> {
+        ["gas_used", "gas_price", "block_number", "author"].iter().map(|s| s.to_string()).collect()
+    }
+
+    fn state() -> EtopState {
+        let df = df!(
+            "block_number" => [1u32, 2, 3],
+            "gas_used" => [30u64, 10, 20],
+            "gas_price" => [5u64, 6, 7],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state
+    }
+
+    #[test]
+    fn unique_prefix() {
+        assert_eq!(complete("bl", &candidates()), vec!["block_number"]);
+        assert_eq!(complete("block_number", &candidates()), vec!["block_number"]);
+        assert!(complete("x", &candidates()).is_empty());
+
+        let mut prompt = Prompt::new(PromptContext::Column);
+        "au".chars().for_each(|c| prompt.push(c));
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "author");
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "author");
+    }
+
+    #[test]
+    fn ambiguous_prefix() {
+        assert_eq!(complete("gas", &candidates()), vec!["gas_price", "gas_used"]);
+        assert_eq!(complete("", &candidates()).len(), 4);
+
+        let mut prompt = Prompt::new(PromptContext::Column);
+        "gas".chars().for_each(|c| prompt.push(c));
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "gas_price");
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "gas_used");
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "gas_price");
+
+        // typing restarts completion from the new text
+        prompt.backspace();
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "gas_price");
+    }
+
+    #[test]
+    fn filter_completes_last_word() {
+        let mut prompt = Prompt::new(PromptContext::Filter);
+        "gas_price > 5 and bl".chars().for_each(|c| prompt.push(c));
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "gas_price > 5 and block_number");
+
+        let mut prompt = Prompt::new(PromptContext::Filter);
+        "zz".chars().for_each(|c| prompt.push(c));
+        prompt.complete(&candidates());
+        assert_eq!(prompt.text, "zz");
+    }
+
+    #[test]
+    fn submit_prompt() {
+        let mut state = state();
+        state.open_prompt(PromptContext::Column);
+        "gas_u".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        state.complete_prompt();
+        state.submit_prompt().unwrap();
+        assert!(state.prompt.is_none());
+        assert_eq!(
+            state.layout.sort,
+            vec![SortKey { column: "gas_used".to_string(), descending: true }]
+        );
+
+        state.open_prompt(PromptContext::Column);
+        "gas_used".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        state.submit_prompt().unwrap();
+        assert!(!state.layout.sort[0].descending);
+
+        state.open_prompt(PromptContext::Column);
+        "missing".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        assert!(state.submit_prompt().is_err());
+
+        state.open_prompt(PromptContext::Filter);
+        "gas_used > 10".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        state.submit_prompt().unwrap();
+        assert_eq!(state.layout.filters, vec!["gas_used > 10"]);
+    }
+}
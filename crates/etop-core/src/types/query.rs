@@ -45,7 +45,7 @@ impl DatasetQuery {
             .await
             .map_err(EtopError::CryoParseError)?;
         let query = std::sync::Arc::new(query);
-        cryo_freeze::collect(query, source.clone()).await.map_err(EtopError::CryoError)
+        cryo_freeze::collect(query, source.clone()).await.map_err(EtopError::classify_collect)
     }
 
     pub(crate) fn blockwise_query(dataset: &str, blocks: &[u32]) -> cryo_cli::Args {
@@ -0,0 +1,145 @@
+#[cfg(test)]
+#[path = "refresh_tests.rs"]
+mod tests;
+
+use crate::EtopError;
+use polars::prelude::*;
+use std::time::Duration;
+
+/// shortest interval between adaptive refreshes
+pub const MIN_ADAPTIVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// longest interval between adaptive refreshes
+pub const MAX_ADAPTIVE_INTERVAL: Duration = Duration::from_secs(32);
+
+/// when live views refresh their data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshMode {
+    /// only when the refresh key is pressed
+    Manual,
+    /// at a fixed interval
+    Interval(Duration),
+    /// at an interval that shortens while the data changes and lengthens while it does not
+    Adaptive,
+}
+
+impl Default for RefreshMode {
+    fn default() -> RefreshMode {
+        RefreshMode::Interval(MIN_ADAPTIVE_INTERVAL)
+    }
+}
+
+impl std::str::FromStr for RefreshMode {
+    type Err = EtopError;
+
+    /// parse refresh mode, either `manual`, `adaptive`, or an interval in seconds such as `5`
+    fn from_str(s: &str) -> Result<RefreshMode, EtopError> {
+        match s {
+            "manual" => Ok(RefreshMode::Manual),
+            "adaptive" => Ok(RefreshMode::Adaptive),
+            seconds => match seconds.trim_end_matches('s').parse::<f64>() {
+                Ok(seconds) if seconds > 0.0 && seconds.is_finite() => {
+                    Ok(RefreshMode::Interval(Duration::from_secs_f64(seconds)))
+                }
+                _ => Err(EtopError::ParseError(format!(
+                    "invalid refresh mode: {} (expected manual, adaptive, or seconds)",
+                    s
+                ))),
+            },
+        }
+    }
+}
+
+/// schedule of refreshes following a refresh mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefreshSchedule {
+    /// refresh mode
+    pub mode: RefreshMode,
+    /// current interval of adaptive mode
+    pub adaptive_interval: Duration,
+    /// scheduled refresh whose data is still being fetched
+    pub pending: Option<PendingRefresh>,
+}
+
+/// scheduled refresh, observed once the data it fetches has all been received
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingRefresh {
+    /// frame cached before the refresh
+    pub previous: Option<DataFrame>,
+    /// number of fetches of the refresh not yet received
+    pub fetching: usize,
+}
+
+impl RefreshSchedule {
+    /// create schedule for refresh mode, with adaptive mode starting from its shortest interval
+    pub fn new(mode: RefreshMode) -> RefreshSchedule {
+        RefreshSchedule { mode, adaptive_interval: MIN_ADAPTIVE_INTERVAL, pending: None }
+    }
+
+    /// interval between refreshes, None if refreshes are manual
+    pub fn interval(&self) -> Option<Duration> {
+        match self.mode {
+            RefreshMode::Manual => None,
+            RefreshMode::Interval(interval) => Some(interval),
+            RefreshMode::Adaptive => Some(self.adaptive_interval),
+        }
+    }
+
+    /// whether a refresh is due, given the time since the last refresh
+    pub fn is_due(&self, elapsed: Duration) -> bool {
+        self.interval().map(|interval| elapsed >= interval).unwrap_or(false)
+    }
+
+    /// record whether a refresh changed the data, halving the adaptive interval if it did and
+    /// doubling it if it did not, within [MIN_ADAPTIVE_INTERVAL, MAX_ADAPTIVE_INTERVAL]
+    pub fn observe(&mut self, changed: bool) {
+        let interval = match changed {
+            true => self.adaptive_interval / 2,
+            false => self.adaptive_interval.saturating_mul(2),
+        };
+        self.adaptive_interval = interval.clamp(MIN_ADAPTIVE_INTERVAL, MAX_ADAPTIVE_INTERVAL);
+    }
+
+    /// start a scheduled refresh from the frame cached before it
+    pub fn begin(&mut self, previous: Option<DataFrame>) {
+        self.pending = Some(PendingRefresh { previous, fetching: 0 });
+    }
+
+    /// record that the pending refresh started n fetches
+    pub fn fetches_started(&mut self, n: usize) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.fetching += n;
+        }
+    }
+
+    /// record that a fetch of the pending refresh was received
+    pub fn fetch_received(&mut self) {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.fetching = pending.fetching.saturating_sub(1);
+        }
+    }
+
+    /// drop the pending refresh without observing it, such as when one of its fetches failed
+    pub fn abandon(&mut self) {
+        self.pending = None;
+    }
+
+    /// observe the pending refresh if all of its fetches were received, comparing the frame
+    /// cached before it with the current frame, returning whether it was observed
+    ///
+    /// frames cached while the fetches are in flight, such as after keypresses, are not observed
+    pub fn finish(&mut self, current: Option<&DataFrame>) -> bool {
+        match self.pending.as_ref() {
+            Some(pending) if pending.fetching == 0 => {
+                let changed = match (pending.previous.as_ref(), current) {
+                    (Some(previous), Some(current)) => !previous.frame_equal_missing(current),
+                    (previous, current) => previous.is_some() != current.is_some(),
+                };
+                self.pending = None;
+                self.observe(changed);
+                true
+            }
+            _ => false,
+        }
+    }
+}
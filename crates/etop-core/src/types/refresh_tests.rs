@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod refresh_tests {
+    use crate::{RefreshMode, RefreshSchedule, MAX_ADAPTIVE_INTERVAL, MIN_ADAPTIVE_INTERVAL};
+    use polars::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_mode() {
+        assert_eq!("manual".parse::<RefreshMode>().unwrap(), RefreshMode::Manual);
+        assert_eq!("adaptive".parse::<RefreshMode>().unwrap(), RefreshMode::Adaptive);
+        let interval = RefreshMode::Interval(Duration::from_millis(2500));
+        assert_eq!("2.5".parse::<RefreshMode>().unwrap(), interval);
+        assert_eq!("2.5s".parse::<RefreshMode>().unwrap(), interval);
+        assert!("0".parse::<RefreshMode>().is_err());
+        assert!("often".parse::<RefreshMode>().is_err());
+    }
+
+    #[test]
+    fn due() {
+        let schedule = RefreshSchedule::new(RefreshMode::Interval(Duration::from_secs(5)));
+        assert!(!schedule.is_due(Duration::from_secs(4)));
+        assert!(schedule.is_due(Duration::from_secs(5)));
+
+        // manual refreshes are never due
+        let schedule = RefreshSchedule::new(RefreshMode::Manual);
+        assert_eq!(schedule.interval(), None);
+        assert!(!schedule.is_due(Duration::MAX));
+    }
+
+    #[test]
+    fn adaptive_interval() {
+        let mut schedule = RefreshSchedule::new(RefreshMode::Adaptive);
+        let mut intervals = vec![];
+        for changed in [false, false, false, true, false, true, true, true] {
+            schedule.observe(changed);
+            intervals.push(schedule.interval().unwrap().as_secs());
+        }
+        assert_eq!(intervals, vec![2, 4, 8, 4, 8, 4, 2, 1]);
+
+        // interval stays within bounds
+        for _ in 0..10 {
+            schedule.observe(false);
+        }
+        assert_eq!(schedule.interval(), Some(MAX_ADAPTIVE_INTERVAL));
+        for _ in 0..10 {
+            schedule.observe(true);
+        }
+        assert_eq!(schedule.interval(), Some(MIN_ADAPTIVE_INTERVAL));
+
+        // fixed intervals do not adapt
+        let mut schedule = RefreshSchedule::new(RefreshMode::Interval(Duration::from_secs(3)));
+        schedule.observe(false);
+        assert_eq!(schedule.interval(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn pending_refresh() {
+        let before = df!("n" => [1u32, 2]).unwrap();
+        let after = df!("n" => [1u32, 2, 3]).unwrap();
+        let mut schedule = RefreshSchedule::new(RefreshMode::Adaptive);
+        schedule.observe(false);
+        assert_eq!(schedule.interval(), Some(Duration::from_secs(2)));
+
+        // a keypress re-caching the view while fetches are in flight is not observed
+        schedule.begin(Some(before.clone()));
+        schedule.fetches_started(2);
+        assert!(!schedule.finish(Some(&before)));
+        schedule.fetch_received();
+        assert!(!schedule.finish(Some(&after)));
+        assert!(schedule.pending.is_some());
+
+        // once every fetch is received, the frame before the refresh is compared with the new one
+        schedule.fetch_received();
+        assert!(schedule.finish(Some(&after)));
+        assert_eq!(schedule.interval(), Some(MIN_ADAPTIVE_INTERVAL));
+        assert!(!schedule.finish(Some(&after)));
+
+        // refreshes without fetches are observed at once, and failed ones are not observed
+        schedule.begin(Some(after.clone()));
+        assert!(schedule.finish(Some(&after)));
+        assert_eq!(schedule.interval(), Some(Duration::from_secs(2)));
+        schedule.begin(Some(after.clone()));
+        schedule.fetches_started(1);
+        schedule.abandon();
+        assert!(!schedule.finish(Some(&before)));
+        assert_eq!(schedule.interval(), Some(Duration::from_secs(2)));
+    }
+}
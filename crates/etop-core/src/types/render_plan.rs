@@ -0,0 +1,83 @@
+#[cfg(test)]
+#[path = "render_plan_tests.rs"]
+mod tests;
+
+use crate::{EtopState, ViewLayout};
+use polars::prelude::*;
+
+/// how cache_window redraws the table after the data of its window changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderPlan {
+    /// format the rows in view from scratch
+    Full,
+    /// keep the last render, given number of rows having been appended below the last frame
+    Append(usize),
+}
+
+/// inputs of a render besides its data, which must be unchanged to render incrementally
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderKey {
+    /// dataset of view
+    pub dataset: String,
+    /// layout of view, including sort and filters
+    pub layout: ViewLayout,
+    /// rows of text available to the table
+    pub render_height: usize,
+    /// columns of text available to the table
+    pub render_width: usize,
+    /// number of columns scrolled past
+    pub column_offset: usize,
+    /// debug form of the state that formats cells, such as format overrides and redaction
+    pub formats: String,
+}
+
+/// decide whether frame can be rendered by appending to the render of the previous frame
+///
+/// appending needs unchanged render inputs and a frame that extends the previous frame with
+/// new rows, so that changes of sort, filters, or size fall back to a full render
+pub fn plan_render(
+    previous: Option<(&RenderKey, &DataFrame)>,
+    key: &RenderKey,
+    df: &DataFrame,
+) -> RenderPlan {
+    let (previous_key, previous_df) = match previous {
+        Some(previous) => previous,
+        None => return RenderPlan::Full,
+    };
+    if previous_key != key ||
+        previous_df.height() == 0 ||
+        df.height() <= previous_df.height() ||
+        df.get_column_names() != previous_df.get_column_names()
+    {
+        return RenderPlan::Full
+    }
+    match df.head(Some(previous_df.height())).frame_equal_missing(previous_df) {
+        true => RenderPlan::Append(df.height() - previous_df.height()),
+        false => RenderPlan::Full,
+    }
+}
+
+impl EtopState {
+    /// inputs of a render of current view at given size, besides its data
+    pub fn render_key(&self, render_height: usize, render_width: usize) -> RenderKey {
+        let formats = format!(
+            "{:?}",
+            (
+                &self.column_formats,
+                &self.raw_units,
+                self.redact_all,
+                self.disable_format_inference,
+                &self.locked_widths,
+                &self.annotations,
+            )
+        );
+        RenderKey {
+            dataset: self.dataset.clone(),
+            layout: self.layout.clone(),
+            render_height,
+            render_width,
+            column_offset: self.column_offset,
+            formats,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod render_plan_tests {
+    use crate::{plan_render, EtopState, RenderPlan, SortKey, DIFF_COLUMN};
+    use polars::prelude::*;
+
+    fn frame(n: u32) -> DataFrame {
+        df!("n" => (0..n).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn plan() {
+        let state = EtopState::default();
+        let key = state.render_key(10, 80);
+        let old = frame(3);
+
+        // appended rows
+        assert_eq!(plan_render(Some((&key, &old)), &key, &frame(5)), RenderPlan::Append(2));
+
+        // nothing to append to
+        assert_eq!(plan_render(None, &key, &frame(5)), RenderPlan::Full);
+        assert_eq!(plan_render(Some((&key, &frame(0))), &key, &frame(5)), RenderPlan::Full);
+        assert_eq!(plan_render(Some((&key, &old)), &key, &frame(3)), RenderPlan::Full);
+        assert_eq!(plan_render(Some((&key, &old)), &key, &frame(2)), RenderPlan::Full);
+
+        // leading rows changed
+        let changed = df!("n" => [9u32, 1, 2, 3]).unwrap();
+        assert_eq!(plan_render(Some((&key, &old)), &key, &changed), RenderPlan::Full);
+        let renamed = df!("m" => [0u32, 1, 2, 3]).unwrap();
+        assert_eq!(plan_render(Some((&key, &old)), &key, &renamed), RenderPlan::Full);
+
+        // resize, sort, and filter
+        let resized = state.render_key(12, 80);
+        assert_eq!(plan_render(Some((&key, &old)), &resized, &frame(5)), RenderPlan::Full);
+        let mut sorted = state.clone();
+        sorted.layout.sort = vec![SortKey { column: "n".into(), descending: true }];
+        let sorted = sorted.render_key(10, 80);
+        assert_eq!(plan_render(Some((&key, &old)), &sorted, &frame(5)), RenderPlan::Full);
+        let mut filtered = state.clone();
+        filtered.layout.filters = vec!["n > 1".into()];
+        let filtered = filtered.render_key(10, 80);
+        assert_eq!(plan_render(Some((&key, &old)), &filtered, &frame(5)), RenderPlan::Full);
+    }
+
+    #[test]
+    fn append_follows_tail() {
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.disable_format_inference = true;
+        let set_rows = |state: &mut EtopState, n: u32| {
+            state.warehouse.data.insert("test_blocks".to_string(), frame(n));
+            state.cache_window(6, 40).unwrap();
+        };
+        let last_line = |state: &EtopState| {
+            state.cache_df_render.clone().unwrap().lines().last().unwrap().trim().to_string()
+        };
+
+        // table showing the last row follows appended rows
+        set_rows(&mut state, 4);
+        assert_eq!(state.cache_visible_rows, 0..4);
+        set_rows(&mut state, 6);
+        assert_eq!(state.cache_visible_rows, 2..6);
+        assert_eq!(last_line(&state), "5");
+        assert_eq!(state.cache_df_render, Some(state.format_window(6, 40).unwrap()));
+
+        // table scrolled away from the last row keeps its rows
+        state.scroll(-2);
+        state.cache_window(6, 40).unwrap();
+        assert_eq!(state.cache_visible_rows, 0..4);
+        set_rows(&mut state, 8);
+        assert_eq!(state.cache_visible_rows, 0..4);
+        assert_eq!(last_line(&state), "3");
+        assert_eq!(state.cache_row_counts, (8, 8));
+        assert_eq!(state.cache_df.as_ref().unwrap().height(), 8);
+    }
+
+    #[test]
+    fn append_keeps_row_styles() {
+        // rows of a diff are styled by their status
+        let frame = |n: usize| {
+            let statuses = ["added", "changed", "removed"];
+            let diff: Vec<&str> = (0..n).map(|i| statuses[i % 3]).collect();
+            let gas: Vec<u64> = (0..n as u64).map(|i| 21_000 + i * 1_000).collect();
+            df!(DIFF_COLUMN => diff, "gas_used" => gas).unwrap()
+        };
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), frame(4));
+        state.cache_window(7, 40).unwrap();
+        state.warehouse.data.insert("test_blocks".to_string(), frame(6));
+        state.cache_window(7, 40).unwrap();
+
+        // the appended rows match a full render of the rows in view
+        assert_eq!(state.cache_visible_rows, 2..6);
+        assert_eq!(state.cache_df_render, Some(state.format_window(7, 40).unwrap()));
+        let styles: Vec<Option<String>> =
+            ["red", "green", "yellow", "red"].iter().map(|s| Some(s.to_string())).collect();
+        assert_eq!(state.cache_row_styles, styles);
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+#[path = "row_limit_tests.rs"]
+mod tests;
+
+use crate::{DatasetQuery, EtopError, EtopState, WindowSize};
+
+/// default maximum number of rows fetched into or rendered from a view
+pub const DEFAULT_MAX_ROWS: usize = 1_000_000;
+
+impl EtopState {
+    /// estimated number of rows that queries would fetch
+    ///
+    /// each block is estimated to hold as many rows as the average block already fetched for its
+    /// dataset, or a single row if none is fetched yet, and address queries are not counted
+    pub fn estimate_query_rows(&self, queries: &[DatasetQuery]) -> usize {
+        queries
+            .iter()
+            .map(|query| match query {
+                DatasetQuery::Block(dataset, blocks) => {
+                    let name = dataset.name();
+                    let n_blocks = self.warehouse.block_index.get(&name).map(|b| b.len());
+                    let n_rows = self.warehouse.data.get(&name).map(|df| df.height());
+                    match (n_rows, n_blocks) {
+                        (Some(n_rows), Some(n_blocks)) if n_blocks > 0 => {
+                            (blocks.len() * n_rows).div_ceil(n_blocks)
+                        }
+                        _ => blocks.len(),
+                    }
+                }
+                DatasetQuery::Address(..) => 0,
+            })
+            .sum()
+    }
+
+    /// error with the estimated number of rows if queries would fetch more than max_rows
+    pub fn check_row_limit(&self, queries: &[DatasetQuery]) -> Result<(), EtopError> {
+        let max_rows = match self.max_rows {
+            Some(max_rows) => max_rows,
+            None => return Ok(()),
+        };
+        let rows = self.estimate_query_rows(queries);
+        if rows > max_rows {
+            Err(EtopError::RowLimitExceeded(rows, max_rows))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// shrink window to its last n blocks
+    pub fn narrow_window(&mut self, n_blocks: u32) -> Result<(), EtopError> {
+        if n_blocks == 0 {
+            return Err(EtopError::ArgumentError("window must span at least 1 block".to_string()))
+        }
+        self.window.size = WindowSize::Block(n_blocks);
+        if let Some(end_block) = self.window.end_block {
+            self.window.set_end_block(end_block.max(n_blocks - 1));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod row_limit_tests {
+    use crate::{DatasetQuery, EtopError, EtopState, InputDataset, PromptContext};
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        let df = df!("block_number" => [1u32, 1, 1, 2, 2, 2]).unwrap();
+        state.warehouse.add_dataset(InputDataset::Raw("blocks".to_string()), df).unwrap();
+        state
+    }
+
+    #[test]
+    fn row_limit_exceeded() {
+        let mut state = state();
+        let blocks =
+            DatasetQuery::Block(InputDataset::Raw("blocks".to_string()), (3..13).collect());
+        let logs = DatasetQuery::Block(InputDataset::Raw("logs".to_string()), (3..8).collect());
+        let queries = vec![blocks, logs];
+
+        // fetched blocks average 3 rows, unfetched datasets are estimated at 1 row per block
+        assert_eq!(state.estimate_query_rows(&queries), 35);
+        assert!(state.check_row_limit(&queries).is_ok());
+        state.max_rows = Some(35);
+        assert!(state.check_row_limit(&queries).is_ok());
+        state.max_rows = Some(20);
+        match state.check_row_limit(&queries) {
+            Err(EtopError::RowLimitExceeded(rows, max_rows)) => {
+                assert_eq!((rows, max_rows), (35, 20))
+            }
+            other => panic!("expected RowLimitExceeded, got {:?}", other),
+        }
+
+        // proceeding raises the limit to the estimate
+        state.open_prompt(PromptContext::RowLimit(35));
+        "y".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        state.submit_prompt().unwrap();
+        assert_eq!(state.max_rows, Some(35));
+    }
+
+    #[test]
+    fn narrow_window() {
+        let mut state = state();
+        state.window.set_end_block(100);
+        state.open_prompt(PromptContext::RowLimit(35));
+        "10".chars().for_each(|c| state.prompt.as_mut().unwrap().push(c));
+        state.submit_prompt().unwrap();
+        assert_eq!((state.window.start_block, state.window.end_block), (Some(91), Some(100)));
+        assert!(state.narrow_window(0).is_err());
+    }
+
+    #[test]
+    fn render_capped() {
+        let mut state = state();
+        let df = df!("n" => (0u32..10).collect::<Vec<_>>()).unwrap();
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.max_rows = Some(4);
+        assert_eq!(state.window_frame().unwrap().height(), 4);
+    }
+}
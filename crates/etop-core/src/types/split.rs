@@ -0,0 +1,145 @@
+#[cfg(test)]
+#[path = "split_tests.rs"]
+mod tests;
+
+use crate::{EtopError, EtopState, Views, PANE_SEPARATOR};
+
+/// share of the width taken by the left pane of a split made with toggle_split
+pub const DEFAULT_SPLIT_RATIO: f32 = 0.5;
+
+/// marker of the title of the focused pane of a split
+const FOCUS_MARKER: &str = "▸ ";
+
+/// two views shown side by side, each scrolled independently
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Split {
+    /// index of view of left pane
+    pub left: usize,
+    /// index of view of right pane
+    pub right: usize,
+    /// share of the width taken by the left pane, between 0 and 1
+    pub ratio: f32,
+    /// whether the right view is a copy made by toggle_split, removed when the split ends
+    pub copied: bool,
+}
+
+/// panes of a split as drawn in place of the table of the active view
+#[derive(Debug, Clone)]
+pub struct SplitPanes {
+    /// share of the width taken by the left pane
+    pub ratio: f32,
+    /// title and view of each pane, left first, with each view's table sized to its pane
+    pub panes: Vec<(String, EtopState)>,
+}
+
+/// widths of the left and right panes of a split of total width, after PANE_SEPARATOR
+///
+/// each pane keeps at least one column when there is room for both
+pub fn split_widths(total_width: usize, ratio: f32) -> (usize, usize) {
+    let available = total_width.saturating_sub(PANE_SEPARATOR.chars().count());
+    let left = (available as f32 * ratio.clamp(0.0, 1.0)).round() as usize;
+    let left = match available {
+        0 | 1 => left.min(available),
+        _ => left.clamp(1, available - 1),
+    };
+    (left, available - left)
+}
+
+impl Views {
+    /// show two views side by side, the left taking ratio of the width, and focus the left
+    pub fn split(&mut self, left: usize, right: usize, ratio: f32) -> Result<(), EtopError> {
+        if left >= self.views.len() || right >= self.views.len() || left == right {
+            return Err(EtopError::ArgumentError(format!(
+                "cannot split views {} and {} of {} views",
+                left,
+                right,
+                self.views.len()
+            )))
+        }
+        if !(ratio > 0.0 && ratio < 1.0) {
+            return Err(EtopError::ArgumentError(format!(
+                "split ratio must be between 0 and 1, got {}",
+                ratio
+            )))
+        }
+        self.split = Some(Split { left, right, ratio, copied: false });
+        self.current = left;
+        Ok(())
+    }
+
+    /// split active view beside a copy of itself, or end the split if there is one
+    ///
+    /// the copy starts with the same window and layout, to be moved to another block range, and
+    /// is removed when the split ends
+    pub fn toggle_split(&mut self) -> Result<(), EtopError> {
+        if let Some(split) = self.split.take() {
+            if split.copied {
+                self.views.remove(split.right);
+                match self.current.cmp(&split.right) {
+                    std::cmp::Ordering::Equal => self.current = split.left,
+                    std::cmp::Ordering::Greater => self.current -= 1,
+                    std::cmp::Ordering::Less => {}
+                }
+            }
+            self.current_mut().cache_split = None;
+            return Ok(())
+        }
+        let mut copy = self.current().clone();
+        copy.cache_panes = None;
+        copy.cache_split = None;
+        self.views.push(copy);
+        self.split(self.current, self.views.len() - 1, DEFAULT_SPLIT_RATIO)?;
+        if let Some(split) = self.split.as_mut() {
+            split.copied = true;
+        }
+        Ok(())
+    }
+
+    /// move focus to the other pane of the split
+    pub fn switch_focus(&mut self) {
+        if let Some(split) = self.split {
+            self.current = if self.current == split.left { split.right } else { split.left };
+        }
+    }
+
+    /// split whose panes include the active view, if any
+    pub fn active_split(&self) -> Option<Split> {
+        self.split.filter(|split| self.current == split.left || self.current == split.right)
+    }
+
+    /// format data of active view, and while split, of the view beside it, sizing each view's
+    /// table to its pane
+    ///
+    /// panes are titled by the dataset and block range of their view, and placed in cache_split
+    /// of the active view in place of its table
+    pub fn cache_windows(
+        &mut self,
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<(), EtopError> {
+        let split = match self.active_split() {
+            Some(split) => split,
+            None => {
+                self.current_mut().cache_split = None;
+                return self.current_mut().cache_window(render_height, render_width)
+            }
+        };
+        let (left_width, right_width) = split_widths(render_width, split.ratio);
+        let table_height = render_height.saturating_sub(1);
+        self.views[split.left].cache_window(table_height, left_width)?;
+        self.views[split.right].cache_window(table_height, right_width)?;
+        let pane = |index: usize| {
+            let mut view = self.views[index].clone();
+            view.cache_split = None;
+            let marker = if index == self.current { FOCUS_MARKER } else { "" };
+            let title = match (view.window.start_block, view.window.end_block) {
+                (Some(start), Some(end)) => format!("{}{} {}–{}", marker, view.dataset, start, end),
+                _ => format!("{}{}", marker, view.dataset),
+            };
+            (title, view)
+        };
+        let panes = vec![pane(split.left), pane(split.right)];
+        self.current_mut().cache_split = Some(SplitPanes { ratio: split.ratio, panes });
+        Ok(())
+    }
+}
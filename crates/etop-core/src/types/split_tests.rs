@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod split_tests {
+    use crate::{split_widths, EtopState, Split, Views};
+    use polars::prelude::*;
+
+    fn views() -> Views {
+        let df = df!("n" => [1u32, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let other = EtopState { dataset: "other".to_string(), ..state.clone() };
+        Views::new(vec![state, other]).unwrap()
+    }
+
+    #[test]
+    fn pane_widths() {
+        // the separator takes 3 columns
+        assert_eq!(split_widths(83, 0.5), (40, 40));
+        assert_eq!(split_widths(80, 0.5), (39, 38));
+        assert_eq!(split_widths(103, 0.3), (30, 70));
+        assert_eq!(split_widths(103, 0.75), (75, 25));
+        // each pane keeps a column
+        assert_eq!(split_widths(53, 0.001), (1, 49));
+        assert_eq!(split_widths(53, 0.999), (49, 1));
+        assert_eq!(split_widths(4, 0.5), (1, 0));
+        assert_eq!(split_widths(2, 0.5), (0, 0));
+    }
+
+    #[test]
+    fn split_views() {
+        let mut views = views();
+        assert!(views.split(0, 0, 0.5).is_err());
+        assert!(views.split(0, 2, 0.5).is_err());
+        assert!(views.split(0, 1, 1.0).is_err());
+        assert!(views.split(0, 1, f32::NAN).is_err());
+        assert_eq!(views.split, None);
+
+        views.current = 1;
+        views.split(0, 1, 0.25).unwrap();
+        assert_eq!(views.split, Some(Split { left: 0, right: 1, ratio: 0.25, copied: false }));
+        assert_eq!(views.current, 0);
+        assert_eq!(views.views_to_refresh(), vec![0, 1]);
+
+        views.switch_focus();
+        assert_eq!(views.current, 1);
+        views.switch_focus();
+        assert_eq!(views.current, 0);
+    }
+
+    #[test]
+    fn toggle_split() {
+        let mut views = views();
+        views.toggle_split().unwrap();
+        assert_eq!(views.views.len(), 3);
+        assert_eq!(views.names()[2], "test_blocks");
+        assert_eq!(views.split, Some(Split { left: 0, right: 2, ratio: 0.5, copied: true }));
+
+        // ending the split removes the copy, focus going back to the view it was made from
+        views.switch_focus();
+        views.toggle_split().unwrap();
+        assert_eq!(views.split, None);
+        assert_eq!(views.names(), vec!["test_blocks", "other"]);
+        assert_eq!(views.current, 0);
+        assert_eq!(views.views_to_refresh(), vec![0]);
+
+        // splitting again does not pile up copies
+        for _ in 0..4 {
+            views.toggle_split().unwrap();
+        }
+        assert_eq!(views.views.len(), 2);
+
+        // views split by index are kept
+        views.split(0, 1, 0.5).unwrap();
+        views.toggle_split().unwrap();
+        assert_eq!(views.views.len(), 2);
+    }
+
+    #[test]
+    fn panes_scroll_independently() {
+        let mut views = views();
+        views.toggle_split().unwrap();
+        views.cache_windows(6, 43).unwrap();
+        let titles = |views: &Views| -> Vec<String> {
+            let split = views.current().cache_split.clone().unwrap();
+            split.panes.into_iter().map(|(title, _)| title).collect()
+        };
+        let first_row = |views: &Views, pane: usize| -> String {
+            let split = views.current().cache_split.clone().unwrap();
+            let render = split.panes[pane].1.cache_df_render.clone().unwrap();
+            render.lines().nth(2).unwrap().trim().to_string()
+        };
+        // each view's table is sized to its pane, below the pane's title
+        assert_eq!(views.views[0].cache_visible_rows, 0..3);
+        assert_eq!(views.current().cache_split.as_ref().unwrap().ratio, 0.5);
+        assert_eq!(titles(&views), vec!["▸ test_blocks", "test_blocks"]);
+        assert_eq!(first_row(&views, 0), "1");
+        assert_eq!(first_row(&views, 1), "1");
+
+        views.switch_focus();
+        views.current_mut().scroll(2);
+        views.cache_windows(6, 43).unwrap();
+        assert_eq!(titles(&views), vec!["test_blocks", "▸ test_blocks"]);
+        assert_eq!(first_row(&views, 0), "1");
+        assert_eq!(first_row(&views, 1), "3");
+        assert_eq!(views.views[0].scroll_offset, 0);
+
+        // the active view keeps its table while not split
+        views.toggle_split().unwrap();
+        views.cache_windows(6, 43).unwrap();
+        assert!(views.current().cache_split.is_none());
+        assert!(views.current().cache_df_render.is_some());
+    }
+}
@@ -1,8 +1,53 @@
-use crate::{DataSpec, DataWarehouse, DatasetQuery, EtopError, InputDataset, Window};
-use etop_format::{ColumnFormatShorthand, DataFrameFormat};
+#[cfg(test)]
+#[path = "ui_tests.rs"]
+mod tests;
+
+use crate::{
+    Annotation, DataSpec, DataWarehouse, DatasetQuery, EtopError, FetchTracker, InputDataset,
+    PaneFrames, Panel, Prompt, RenderKey, RenderPlan, SplitPanes, ViewLayout, Window,
+};
+use etop_format::{
+    ColumnFormatShorthand, ColumnGroup, DataFrameFormat, NumberFormat, RedactStyle, SortKind,
+};
 use polars::prelude::*;
 use std::collections::HashMap;
 
+/// colors of highlighted top ranked rows: gold, silver, bronze
+pub(crate) const RANK_STYLES: [&str; 3] = ["#ffd700", "#c0c0c0", "#cd7f32"];
+
+/// names of RANK_STYLES, as shown in the legend
+pub(crate) const RANK_STYLE_NAMES: [&str; 3] = ["gold", "silver", "bronze"];
+
+/// temporary column of row positions, used as the last sort key
+const SORT_TIEBREAK_COLUMN: &str = "__etop_row";
+
+/// prefix of temporary columns of sort keys derived by a column's SortKind
+const SORT_KEY_COLUMN_PREFIX: &str = "__etop_sort_";
+
+/// name of column numbering displayed rows, shown when the layout's show_row_numbers is set
+pub const ROW_NUMBER_COLUMN: &str = "#";
+
+/// message in place of rows when filters or the row limit remove every row of window
+pub const NO_MATCHING_ROWS: &str = "no rows match";
+
+/// message in place of rows when window has no rows before filters
+pub const NO_DATA_IN_RANGE: &str = "no data in range";
+
+/// style of a displayed table row, in order of increasing precedence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowStyle {
+    /// no styling
+    Plain,
+    /// shaded background of every other row
+    Zebra,
+    /// color given to the row by the dataset, such as red for failed transactions
+    Data(String),
+    /// color of a highlighted top ranked row
+    Rank(String),
+    /// row of the inspected cell
+    Selected,
+}
+
 /// ui
 #[derive(Debug, Clone, Default)]
 pub struct EtopState {
@@ -20,14 +65,70 @@ pub struct EtopState {
     pub file_source: Option<String>,
     /// rpc source
     pub rpc_source: Option<std::sync::Arc<cryo_freeze::Source>>,
+    /// layout of view
+    pub layout: ViewLayout,
+    /// whether a loaded layout awaits checking against the columns of the view's data
+    pub layout_unchecked: bool,
+    /// number of rows scrolled past in the table
+    pub scroll_offset: usize,
+    /// number of columns scrolled past in the table, after the frozen columns
+    pub column_offset: usize,
+    /// whether to redact all binary and string columns, e.g. for screenshots
+    pub redact_all: bool,
+    /// notes attached to cells
+    pub annotations: Vec<Annotation>,
+    /// column formats overriding those of the dataset, such as after adjusting precision
+    pub column_formats: HashMap<String, ColumnFormatShorthand>,
+    /// overrides of columns toggled to raw units, restored when toggled back
+    pub raw_units: HashMap<String, Option<ColumnFormatShorthand>>,
+    /// whether to skip inferring formats of columns from their names, such as gwei for `*_gwei`
+    pub disable_format_inference: bool,
+    /// fixed width of each column, set while widths are locked
+    pub locked_widths: Option<HashMap<String, usize>>,
+    /// maximum number of rows fetched into or rendered from the view, None for no limit
+    pub max_rows: Option<usize>,
     //
     // cache fields
     /// current df
     pub cache_df: Option<DataFrame>,
+    /// current window frame, after transform, filters, sort, and limits, before scrolling
+    pub cache_frame: DataFrame,
     /// current table
     pub cache_df_render: Option<String>,
+    /// panes shown in place of the table, the frames of a dataset with several frames
+    pub cache_panes: Option<String>,
+    /// frames of panes of current window
+    pub cache_pane_frames: Option<PaneFrames>,
+    /// views of a split shown in place of the table, while this view is active
+    pub cache_split: Option<SplitPanes>,
+    /// rows of current df shown in current table
+    pub cache_visible_rows: std::ops::Range<usize>,
+    /// number of lines of current table above its data rows
+    pub cache_header_lines: usize,
+    /// number of rows of current window after and before filters
+    pub cache_row_counts: (usize, usize),
+    /// style of each row shown in current table, such as a color name
+    pub cache_row_styles: Vec<Option<String>>,
+    /// style given by the dataset to each row shown in current table
+    pub cache_data_styles: Vec<Option<String>>,
+    /// display columns spanned by each column in rows of current table
+    pub cache_cell_spans: Vec<(String, std::ops::Range<usize>)>,
+    /// inputs of current table besides its data, to tell whether the next can be appended
+    pub cache_render_key: Option<RenderKey>,
     /// messages
     pub messages: Vec<String>,
+    /// (row, column) of cell being inspected
+    pub selected_cell: Option<(usize, usize)>,
+    /// text input being typed
+    pub prompt: Option<Prompt>,
+    /// fetches of data in flight
+    pub fetch: FetchTracker,
+    /// chain id of data source, if known
+    pub chain_id: Option<u64>,
+    /// unix time when data was last received
+    pub fetched_at: Option<u64>,
+    /// summary of a column shown in place of the table
+    pub panel: Option<Panel>,
 }
 
 // state updates
@@ -185,63 +286,776 @@ impl EtopState {
         self.warehouse.data.contains_key(self.dataset.as_str())
     }
 
+    /// transformed data of current window, before layout is applied
+    pub(crate) fn transformed_frame(&self) -> Result<DataFrame, EtopError> {
+        let dataspec = crate::load_dataspec(self.dataset.clone())?;
+        dataspec.transform(&self.warehouse, self.window.start_block, self.window.end_block)
+    }
+
+    /// columns available in the transformed data of current window
+    ///
+    /// if the dataset lists its available columns, only those present are returned, in its order
+    pub fn available_columns(&self) -> Result<Vec<String>, EtopError> {
+        self.available_columns_of(&self.transformed_frame()?)
+    }
+
+    fn available_columns_of(&self, df: &DataFrame) -> Result<Vec<String>, EtopError> {
+        let present: Vec<String> = df.get_column_names().iter().map(|s| s.to_string()).collect();
+        match self.dataspec()?.available_columns() {
+            Some(columns) => Ok(columns.into_iter().filter(|c| present.contains(c)).collect()),
+            None => Ok(present),
+        }
+    }
+
+    /// name and dtype of each available column, including those computed by the transform
+    ///
+    /// empty if the transform fails, such as before the dataset's inputs are fetched
+    pub fn view_schema(&self) -> Vec<(String, DataType)> {
+        let df = match self.transformed_frame() {
+            Ok(df) => df,
+            Err(_) => return vec![],
+        };
+        let columns = self.available_columns_of(&df).unwrap_or_default();
+        columns
+            .into_iter()
+            .filter_map(|name| {
+                let dtype = df.column(name.as_str()).ok()?.dtype().clone();
+                Some((name, dtype))
+            })
+            .collect()
+    }
+
+    /// transformed data of current window, restricted to the displayed columns
+    pub fn window_frame(&self) -> Result<DataFrame, EtopError> {
+        Ok(self.counted_window_frame()?.0)
+    }
+
+    /// frame currently displayed, after transform, filters, sort, and limits
+    ///
+    /// empty until the window is cached, and holds every displayed column regardless of
+    /// scrolling or transposing
+    pub fn current_frame(&self) -> &DataFrame {
+        &self.cache_frame
+    }
+
+    /// clone of current_frame
+    pub fn current_frame_owned(&self) -> DataFrame {
+        self.cache_frame.clone()
+    }
+
+    /// window_frame, along with the number of rows of the window before filters
+    fn counted_window_frame(&self) -> Result<(DataFrame, usize), EtopError> {
+        let (df, n_rows) = self.unselected_window_frame()?;
+        Ok((self.select_displayed_columns(df)?, n_rows))
+    }
+
+    /// counted_window_frame, keeping every column of the transformed data
+    fn unselected_window_frame(&self) -> Result<(DataFrame, usize), EtopError> {
+        let df = self.transformed_frame()?;
+        let n_rows = df.height();
+        let mut df = crate::apply_filters(df, &self.layout.filters)?;
+
+        // apply sort, breaking ties by original row position so that they keep their order
+        if !self.layout.sort.is_empty() {
+            let n_columns = df.width();
+            df = df.with_row_count(SORT_TIEBREAK_COLUMN, None)?;
+            let mut columns: Vec<String> = Vec::new();
+            for key in self.layout.sort.iter() {
+                match self.sort_kind(&key.column)? {
+                    Some(kind) => {
+                        // sort by derived key column, such as natural order of text
+                        let name = format!("{}{}", SORT_KEY_COLUMN_PREFIX, columns.len());
+                        let series = df.column(key.column.as_str())?;
+                        df.with_column(kind.sort_key(series)?.with_name(name.as_str()))?;
+                        columns.push(name);
+                    }
+                    None => columns.push(key.column.clone()),
+                }
+            }
+            let mut descending: Vec<bool> = self.layout.sort.iter().map(|k| k.descending).collect();
+            columns.push(SORT_TIEBREAK_COLUMN.to_string());
+            descending.push(false);
+            df = df.sort(columns, descending, true)?;
+            df = df.select_by_range(1..n_columns + 1)?;
+        }
+
+        // apply row limit
+        if let Some(limit) = self.layout.limit {
+            df = df.head(Some(limit));
+        }
+        if let Some(max_rows) = self.max_rows {
+            df = df.head(Some(max_rows));
+        }
+        Ok((df, n_rows))
+    }
+
+    /// restrict frame to the columns displayed in current view
+    fn select_displayed_columns(&self, df: DataFrame) -> Result<DataFrame, EtopError> {
+        match self.displayed_columns()? {
+            Some(columns) => Ok(df.select(columns)?),
+            None => Ok(df),
+        }
+    }
+
+    /// comparator of sort by column, from its format, None to sort by its values
+    fn sort_kind(&self, column: &str) -> Result<Option<SortKind>, EtopError> {
+        let sort_as = match self.column_formats.get(column) {
+            Some(column_format) => column_format.sort_as,
+            None => self
+                .dataspec()?
+                .default_column_formats()
+                .and_then(|formats| formats.get(column).and_then(|f| f.sort_as)),
+        };
+        Ok(sort_as)
+    }
+
+    /// columns displayed in current view, None if all columns are displayed
+    pub fn displayed_columns(&self) -> Result<Option<Vec<String>>, EtopError> {
+        match &self.layout.columns {
+            Some(columns) => Ok(Some(columns.clone())),
+            None => Ok(self.dataspec()?.default_columns()),
+        }
+    }
+
     /// format data of current window
     pub fn format_window(
         &self,
         render_height: usize,
         render_width: usize,
     ) -> Result<String, EtopError> {
-        let dataspec = crate::load_dataspec(self.dataset.clone())?;
-        let df =
-            dataspec.transform(&self.warehouse, self.window.start_block, self.window.end_block)?;
+        let (df, n_rows) = self.counted_window_frame()?;
+        let message = empty_message(df.height(), n_rows);
+        let df = self.scrolled_frame(df)?;
+        let fmt = self.frame_format(&df, render_height, render_width)?;
+        let header_lines = fmt.header_height(&df)?;
+        let render = fmt.format(df)?;
+        match message {
+            Some(message) => Ok(place_message(&render, message, header_lines, render_height)),
+            None => Ok(render),
+        }
+    }
 
-        // decide which columns to use
-        let column_names: Vec<String> = if let Some(columns) = dataspec.default_columns() {
-            columns
-        } else {
-            df.schema().get_names().iter().map(|s| s.to_string()).collect()
+    /// message shown in place of rows of cached window, None if it has rows
+    pub fn empty_message(&self) -> Option<&'static str> {
+        let (shown, total) = self.cache_row_counts;
+        self.cache_df.as_ref().and_then(|_| empty_message(shown, total))
+    }
+
+    /// format data of current window and store it, along with its raw values, in the cache
+    ///
+    /// a transposed window is cached as its transposed frame, so that scrolling and inspection
+    /// follow the displayed rows
+    pub fn cache_window(
+        &mut self,
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<(), EtopError> {
+        self.check_layout();
+        self.cache_panes = self.format_panes(render_height, render_width)?;
+        if self.panes_shown() {
+            // the table is hidden, so inspection and scrolling find no table to act on
+            self.cache_frame = DataFrame::default();
+            self.cache_df = None;
+            self.cache_df_render = None;
+            self.cache_render_key = None;
+            self.cache_row_counts = (0, 0);
+            self.selected_cell = None;
+            return Ok(())
+        }
+        // the dataset styles rows by their data, including columns that are not displayed
+        let (unselected, n_rows) = self.unselected_window_frame()?;
+        let df = self.select_displayed_columns(unselected.clone())?;
+        let row_counts = (df.height(), n_rows);
+        self.follow_selected_row(&df)?;
+        self.cache_frame = df.clone();
+        let df = self.scrolled_frame(df)?;
+        if let Some((row, column)) = self.selected_cell {
+            let column = column.min(df.width().saturating_sub(1)).max(self.first_data_column());
+            self.selected_cell = Some((row, column));
+        }
+        let key = self.render_key(render_height, render_width);
+        let previous = self.cache_render_key.as_ref().zip(self.cache_df.as_ref());
+        let plan = crate::plan_render(previous, &key, &df);
+        self.cache_render_key = Some(key);
+        if let RenderPlan::Append(n_appended) = plan {
+            if self.append_window(
+                df.clone(),
+                &unselected,
+                n_appended,
+                row_counts,
+                render_height,
+                render_width,
+            )? {
+                return Ok(())
+            }
+        }
+        let fmt = self.frame_format(&df, render_height, render_width)?;
+        let transposed = fmt.is_transposed(&df);
+        let (fmt, df) = if transposed { fmt.transpose_frame(&df)? } else { (fmt, df) };
+        let visible_rows = fmt.visible_rows(&df)?;
+        let row_styles = row_styles(&fmt, &df)?;
+        // rows of a transposed frame are columns of the data
+        let data_styles = match transposed {
+            true => vec![None; visible_rows.len()],
+            false => self.data_styles(&unselected, visible_rows.clone())?,
         };
+        let cell_spans = fmt.cell_spans(&df)?;
+        let header_lines = fmt.header_height(&df)?;
+        let mut render = fmt.format(df.clone())?;
+        if let Some(message) = empty_message(row_counts.0, row_counts.1) {
+            render = place_message(&render, message, header_lines, render_height);
+        }
+        self.cache_row_styles = row_styles[visible_rows.clone()].to_vec();
+        self.cache_data_styles = data_styles;
+        self.cache_cell_spans = cell_spans;
+        self.scroll_offset = visible_rows.start;
+        self.cache_visible_rows = visible_rows;
+        self.cache_header_lines = header_lines;
+        self.cache_row_counts = row_counts;
+        self.cache_df = Some(df);
+        self.cache_df_render = Some(render);
+        Ok(())
+    }
+
+    /// cache frame whose leading rows are those of the cached frame, returning false if it needs
+    /// a full render
+    ///
+    /// if the table showed the last row, the viewport shifts to follow the appended rows, and
+    /// only the appended rows in view are formatted, below the cached lines of the rows kept in
+    /// view, otherwise the rows in view are unchanged and so is the cached table
+    fn append_window(
+        &mut self,
+        df: DataFrame,
+        unselected: &DataFrame,
+        n_appended: usize,
+        row_counts: (usize, usize),
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<bool, EtopError> {
+        let fmt = self.frame_format(&df, render_height, render_width)?;
+        // ranks, data bars, and aligned suffixes depend on every row of a column
+        let whole_column = |c: &ColumnFormatShorthand| {
+            c.rank_highlight.is_some() || c.data_bar.is_some() || c.align_suffixes
+        };
+        if fmt.is_transposed(&df) || fmt.column_formats.iter().flatten().any(whole_column) {
+            return Ok(false)
+        }
+        let previous_rows = df.height() - n_appended;
+        let previous_visible = self.cache_visible_rows.clone();
+        if previous_visible.end == previous_rows {
+            let render = match self.cache_df_render.as_ref() {
+                Some(render) => render,
+                None => return Ok(false),
+            };
+            let fmt =
+                DataFrameFormat { row_offset: self.scroll_offset + n_appended, ..fmt.clone() };
+            let visible = fmt.visible_rows(&df)?;
+            let appended = previous_rows..visible.end;
+            let appended_lines = match fmt.format_rows(&df, appended, &self.cache_cell_spans)? {
+                Some(lines) => lines,
+                None => return Ok(false),
+            };
+            let n_kept = previous_rows.saturating_sub(visible.start);
+            let lines: Vec<&str> = render.lines().collect();
+            let (header, data) = lines.split_at(self.cache_header_lines.min(lines.len()));
+            let kept = &data[data.len().saturating_sub(n_kept)..];
+            let mut lines: Vec<String> = header.iter().chain(kept).map(|s| s.to_string()).collect();
+            lines.extend(appended_lines);
+            self.cache_df_render = Some(lines.join("\n"));
+            self.cache_visible_rows = visible;
+        }
+        self.scroll_offset = self.cache_visible_rows.start;
+        self.cache_row_styles = row_styles(&fmt, &df)?[self.cache_visible_rows.clone()].to_vec();
+        self.cache_data_styles = self.data_styles(unselected, self.cache_visible_rows.clone())?;
+        self.cache_row_counts = row_counts;
+        self.cache_df = Some(df);
+        Ok(true)
+    }
+
+    /// style given by the dataset to each of the given rows of frame, which holds every column
+    fn data_styles(
+        &self,
+        df: &DataFrame,
+        rows: std::ops::Range<usize>,
+    ) -> Result<Vec<Option<String>>, EtopError> {
+        let dataspec = self.dataspec()?;
+        Ok(rows.map(|row| dataspec.row_style(df, row)).collect())
+    }
+
+    pub(crate) fn frame_format(
+        &self,
+        df: &DataFrame,
+        render_height: usize,
+        render_width: usize,
+    ) -> Result<DataFrameFormat, EtopError> {
+        let dataspec = crate::load_dataspec(self.dataset.clone())?;
 
         // load column formats
         let column_formats: HashMap<String, ColumnFormatShorthand> =
             dataspec.default_column_formats().unwrap_or(HashMap::new());
 
+        let annotated_rows = self.annotated_rows(df)?;
         let mut columns = Vec::new();
-        for column_name in column_names.into_iter() {
-            if let Some(column_format) = column_formats.get(column_name.as_str()) {
-                columns.push(column_format.clone())
+        for ((column_name, dtype), marked_rows) in df.schema().iter().zip(annotated_rows) {
+            let mut column_format = match column_name.as_str() {
+                ROW_NUMBER_COLUMN if self.layout.show_row_numbers => ColumnFormatShorthand::new()
+                    .name(ROW_NUMBER_COLUMN)
+                    .set_format(NumberFormat::new().precision(0).right_align()),
+                _ => self.resolve_column_format(&column_formats, column_name, dtype)?,
+            };
+            column_format = column_format.marked_rows(marked_rows);
+            if let Some(width) =
+                self.locked_widths.as_ref().and_then(|w| w.get(column_name.as_str()))
+            {
+                column_format = column_format.width(*width);
+            }
+            if self.redact_all && matches!(dtype, DataType::Binary | DataType::Utf8) {
+                column_format = column_format.redact(RedactStyle::Full);
+            }
+            if self.layout.highlight.as_ref() == Some(&column_name.to_string()) {
+                let styles = RANK_STYLES.iter().map(|s| s.to_string()).collect();
+                columns.push(column_format.rank_highlight(RANK_STYLES.len(), styles));
             } else {
-                let dtype: DataType = df
-                    .schema()
-                    .get(column_name.as_str())
-                    .ok_or(EtopError::ColumnMissing(column_name.to_string()))?
-                    .clone();
-                columns.push(get_default_format(column_name, dtype)?);
+                columns.push(column_format);
             }
         }
-        // let columns: Result<Vec<ColumnFormatShorthand>, EtopError> = columns
-        //     .iter()
-        //     .map(|name| {
-        //         column_formats.get(name).ok_or(EtopError::ColumnMissing(name.to_string())).
-        // cloned()     })
-        //     .collect::<Result<Vec<_>, _>>();
-        // let columns = columns?;
+
+        // summarize boolean columns in footer
+        let has_bool_columns = df.dtypes().contains(&DataType::Boolean);
+
+        // keep pinned columns, which scrolled_frame moves to the end
+        let pinned = self.layout.pinned_columns.iter().filter(|c| df.column(c).is_ok()).count();
 
         let fmt = DataFrameFormat {
             column_formats: Some(columns),
-            render_height: Some(render_height - 1),
+            render_height: Some(render_height),
             max_render_width: Some(render_width),
+            row_offset: self.scroll_offset,
+            transpose: self.layout.transpose,
+            include_summary_row: has_bool_columns,
+            include_summary_separator_row: has_bool_columns,
+            pinned_columns: pinned,
+            column_groups: self
+                .layout
+                .column_groups
+                .iter()
+                .map(|(label, columns)| ColumnGroup {
+                    label: label.clone(),
+                    columns: columns.clone(),
+                })
+                .collect(),
+
+            border_style: self.layout.border_style,
+            ascii_borders: self.layout.ascii_borders,
 
             include_header_separator_row: true,
             column_delimiter: "   ".to_string(),
             header_separator_delimiter: "───".to_string(),
             ..Default::default()
         };
-        Ok(fmt.format(df)?)
+        Ok(fmt)
+    }
+}
+
+// scrolling
+impl EtopState {
+    /// scroll table by given number of rows, keeping the last row reachable
+    pub fn scroll(&mut self, rows: i64) {
+        let n_rows = self.cache_df.as_ref().map(|df| df.height()).unwrap_or(0);
+        let max_offset = n_rows.saturating_sub(self.cache_visible_rows.len());
+        self.scroll_offset =
+            (self.scroll_offset as i64 + rows).clamp(0, max_offset as i64) as usize;
+    }
+
+    /// scroll table by given number of pages
+    pub fn scroll_pages(&mut self, pages: i64) {
+        let page = self.cache_visible_rows.len().max(1) as i64;
+        self.scroll(pages * page);
+    }
+
+    /// position of table within frame, e.g. `[120/4000]` for a table starting at row 120
+    pub fn scroll_indicator(&self) -> String {
+        let n_rows = self.cache_df.as_ref().map(|df| df.height()).unwrap_or(0);
+        let first = if n_rows == 0 { 0 } else { self.cache_visible_rows.start + 1 };
+        format!("[{}/{}]", first, n_rows)
+    }
+
+    /// summary of sort, filters, row counts, and view, e.g.
+    /// `sort: gas_used↓ | filter: value>0 | 120/4000 rows | view: transactions`
+    ///
+    /// sort and filter are omitted when not applied, row counts before the window is cached
+    pub fn status_line(&self) -> String {
+        let mut parts = vec![];
+        if !self.layout.sort.is_empty() {
+            let keys: Vec<String> = self
+                .layout
+                .sort
+                .iter()
+                .map(|key| format!("{}{}", key.column, if key.descending { '↓' } else { '↑' }))
+                .collect();
+            parts.push(format!("sort: {}", keys.join(", ")));
+        }
+        if !self.layout.filters.is_empty() {
+            let filters: Vec<String> = self
+                .layout
+                .filters
+                .iter()
+                .map(|filter| match filter.parse::<crate::FilterExpr>() {
+                    Ok(expr) => expr.to_string(),
+                    Err(_) => filter.clone(),
+                })
+                .collect();
+            parts.push(format!("filter: {}", filters.join(" & ")));
+        }
+        if self.cache_df.is_some() {
+            let (shown, total) = self.cache_row_counts;
+            parts.push(format!("{}/{} rows", shown, total));
+        }
+        if let (Some(start_block), Some(end_block)) =
+            (self.window.start_block, self.window.end_block)
+        {
+            let granularity = self.dataspec().map(|spec| spec.granularity()).unwrap_or_default();
+            parts.push(granularity.describe_range(start_block, end_block));
+        }
+        parts.push(format!("view: {}", self.dataset));
+        parts.join(" | ")
+    }
+
+    /// fraction of frame scrolled past, and fraction of frame visible in table
+    ///
+    /// a frame that fits entirely within the table is fully visible
+    pub fn scroll_fractions(&self) -> (f64, f64) {
+        let n_rows = self.cache_df.as_ref().map(|df| df.height()).unwrap_or(0);
+        let visible = &self.cache_visible_rows;
+        if n_rows <= visible.len() {
+            return (0.0, 1.0)
+        }
+        (visible.start as f64 / n_rows as f64, visible.len() as f64 / n_rows as f64)
+    }
+
+    /// scrollbar track of given height, one char per line, with `█` marking the visible part
+    pub fn scroll_bar(&self, height: usize) -> Vec<char> {
+        let (offset, fill) = self.scroll_fractions();
+        let thumb = ((fill * height as f64).round() as usize).max(1).min(height);
+        let n_rows = self.cache_df.as_ref().map(|df| df.height()).unwrap_or(0);
+        let start = if self.cache_visible_rows.end >= n_rows {
+            height - thumb
+        } else {
+            ((offset * height as f64).round() as usize).min(height - thumb)
+        };
+        (0..height).map(|i| if i >= start && i < start + thumb { '█' } else { '│' }).collect()
+    }
+
+    /// scroll just enough for the inspected cell to be visible
+    fn scroll_to_selected(&mut self) {
+        let height = self.cache_visible_rows.len();
+        if let Some((row, _)) = self.selected_cell {
+            if row < self.scroll_offset {
+                self.scroll_offset = row;
+            } else if height > 0 && row >= self.scroll_offset + height {
+                self.scroll_offset = row + 1 - height;
+            }
+        }
+    }
+}
+
+// horizontal scrolling
+impl EtopState {
+    /// scroll table by given number of columns, keeping the last scrollable column reachable
+    pub fn scroll_columns(&mut self, columns: i64) {
+        // the cached frame omits the columns scrolled past
+        let n_columns =
+            self.cache_df.as_ref().map(|df| df.width()).unwrap_or(0) + self.column_offset;
+        let n_fixed = match &self.cache_df {
+            Some(df) => {
+                let pinned = self.layout.pinned_columns.iter().filter(|c| df.column(c).is_ok());
+                pinned.count() + self.layout.frozen_columns + self.layout.show_row_numbers as usize
+            }
+            None => 0,
+        };
+        let max_offset = n_columns.saturating_sub(n_fixed + 1);
+        self.column_offset =
+            (self.column_offset as i64 + columns).clamp(0, max_offset as i64) as usize;
+    }
+
+    /// pin column to always be shown at the right of the table
+    pub fn pin_column(&mut self, column: String) -> Result<(), EtopError> {
+        self.require_column(&column)?;
+        if !self.layout.pinned_columns.contains(&column) {
+            self.layout.pinned_columns.push(column);
+        }
+        Ok(())
+    }
+
+    /// unpin column, returning whether it was pinned
+    pub fn unpin_column(&mut self, column: &str) -> bool {
+        let n_pinned = self.layout.pinned_columns.len();
+        self.layout.pinned_columns.retain(|c| c != column);
+        self.layout.pinned_columns.len() != n_pinned
+    }
+
+    /// frame with columns in display order: row numbers if shown, then frozen leading columns,
+    /// then the other columns from column_offset on, then pinned columns
+    fn scrolled_frame(&self, df: DataFrame) -> Result<DataFrame, EtopError> {
+        if self.layout.show_row_numbers {
+            check_row_numbers(&df)?;
+        }
+        let names: Vec<String> = df.get_column_names().iter().map(|s| s.to_string()).collect();
+        let pinned: Vec<String> =
+            self.layout.pinned_columns.iter().filter(|c| names.contains(c)).cloned().collect();
+        let rest: Vec<String> = names.into_iter().filter(|c| !pinned.contains(c)).collect();
+        let n_frozen = self.layout.frozen_columns.min(rest.len());
+        let offset = self.column_offset.min(rest.len().saturating_sub(n_frozen + 1));
+        let columns: Vec<String> = rest[..n_frozen]
+            .iter()
+            .chain(rest.iter().skip(n_frozen + offset))
+            .chain(pinned.iter())
+            .cloned()
+            .collect();
+        let df = df.select(columns)?;
+        match self.layout.show_row_numbers {
+            true => with_row_numbers(df),
+            false => Ok(df),
+        }
+    }
+}
+
+// row styles
+impl EtopState {
+    /// style of displayed row, with selection taking precedence over rank highlights, rank
+    /// highlights over styles given by the dataset, and those over zebra striping
+    ///
+    /// stripes follow the row's position in the frame, so they do not shift while scrolling
+    pub fn row_style(&self, row: usize) -> RowStyle {
+        let frame_row = self.cache_visible_rows.start + row;
+        if matches!(self.selected_cell, Some((selected, _)) if selected == frame_row) {
+            return RowStyle::Selected
+        }
+        if let Some(Some(style)) = self.cache_row_styles.get(row) {
+            return RowStyle::Rank(style.clone())
+        }
+        if let Some(Some(style)) = self.cache_data_styles.get(row) {
+            return RowStyle::Data(style.clone())
+        }
+        if self.layout.zebra && frame_row % 2 == 1 {
+            RowStyle::Zebra
+        } else {
+            RowStyle::Plain
+        }
     }
 }
 
-fn get_default_format(
+// inspection
+impl EtopState {
+    /// exact representation of the raw value underlying a rendered cell
+    ///
+    /// binary values are given as full hex, floats at full precision, and unit-converted
+    /// columns as their unconverted value
+    pub fn inspect_cell(&self, row: usize, column: usize) -> Result<String, EtopError> {
+        let df = self.cache_df.as_ref().ok_or(EtopError::MissingData("no data".to_string()))?;
+        let series = df.get_columns().get(column).ok_or(EtopError::ColumnMissing(format!(
+            "column index {} out of bounds for {} columns",
+            column,
+            df.width()
+        )))?;
+        cell_value(series, row)
+    }
+
+    /// index of first column of the cached frame holding data, after any row numbers
+    fn first_data_column(&self) -> usize {
+        usize::from(self.layout.show_row_numbers)
+    }
+
+    /// move inspected cell by given row and column offsets, staying inside the data columns of
+    /// the cached frame
+    pub fn move_selected_cell(&mut self, rows: i64, columns: i64) {
+        let first_column = self.first_data_column();
+        let (n_rows, n_columns) = match self.cache_df.as_ref() {
+            Some(df) if df.height() > 0 && df.width() > first_column => df.shape(),
+            _ => {
+                self.selected_cell = None;
+                return
+            }
+        };
+        if let Some((row, column)) = self.selected_cell {
+            let row = (row as i64 + rows).clamp(0, n_rows as i64 - 1) as usize;
+            let column = (column as i64 + columns).clamp(first_column as i64, n_columns as i64 - 1);
+            let column = column as usize;
+            self.selected_cell = Some((row, column));
+        }
+        self.scroll_to_selected();
+    }
+
+    /// move inspected cell to the row of frame with the key of its row in the cached frame, so
+    /// that it stays on the same row when rows move, such as when a refresh re-sorts them
+    ///
+    /// frame is the unscrolled frame replacing the cached frame, and if none of its rows has the
+    /// key, the inspected cell keeps its position, clamped to frame
+    ///
+    /// nothing moves while the frame is unchanged, so that paging away from the inspected cell
+    /// is not undone
+    fn follow_selected_row(&mut self, df: &DataFrame) -> Result<(), EtopError> {
+        let (row, column) = match self.selected_cell {
+            Some(cell) if !self.layout.transpose && df.height() > 0 && df.width() > 0 => cell,
+            _ => return Ok(()),
+        };
+        if df.frame_equal_missing(&self.cache_frame) {
+            return Ok(())
+        }
+        let row_key = self.cell_key(row, column).ok().map(|(row_key, _)| row_key);
+        let new_row = match (row_key, self.key_column()) {
+            (Some(row_key), Ok(key_column)) => {
+                let keys = df.column(key_column.as_str())?;
+                (0..df.height()).find(|r| cell_value(keys, *r).ok().as_ref() == Some(&row_key))
+            }
+            _ => None,
+        };
+        let row = new_row.unwrap_or(row).min(df.height() - 1);
+        self.selected_cell = Some((row, column));
+        self.scroll_to_selected();
+        Ok(())
+    }
+
+    /// toggle inspection of cell values
+    pub fn toggle_inspect(&mut self) {
+        self.selected_cell = match self.selected_cell {
+            Some(_) => None,
+            None => Some((0, 0)),
+        };
+        self.move_selected_cell(0, 0);
+    }
+
+    /// move inspected cell to the next cell containing text, wrapping around at the end
+    pub fn search(&mut self, text: &str) -> Result<(), EtopError> {
+        let (n_rows, n_columns) =
+            self.cache_df.as_ref().ok_or(EtopError::MissingData("no data".to_string()))?.shape();
+        let n_cells = n_rows * n_columns;
+        let start = match self.selected_cell {
+            Some((row, column)) => row * n_columns + column + 1,
+            None => 0,
+        };
+        for offset in 0..n_cells {
+            let index = (start + offset) % n_cells;
+            let (row, column) = (index / n_columns, index % n_columns);
+            if column < self.first_data_column() {
+                continue
+            }
+            if self.inspect_cell(row, column)?.contains(text) {
+                self.selected_cell = Some((row, column));
+                self.scroll_to_selected();
+                return Ok(())
+            }
+        }
+        Err(EtopError::MissingData(format!("no cell contains {}", text)))
+    }
+
+    /// byte range of the inspected cell in the line of displayed row, None if the row does not
+    /// contain it or its column does not fit
+    pub fn selected_cell_range(&self, row: usize) -> Option<std::ops::Range<usize>> {
+        let (selected, column) = self.selected_cell?;
+        if selected != self.cache_visible_rows.start + row {
+            return None
+        }
+        let name = self.cache_df.as_ref()?.get_columns().get(column)?.name();
+        let (_, span) = self.cache_cell_spans.iter().find(|(c, _)| c == name)?;
+        let line = self.cache_df_render.as_ref()?.lines().nth(self.cache_header_lines + row)?;
+        let start = etop_format::truncate_graphemes(line, span.start).len();
+        let end = etop_format::truncate_graphemes(line, span.end).len();
+        Some(start..end)
+    }
+
+    /// status line describing the inspected cell
+    pub fn inspect_status(&self) -> Option<String> {
+        let (row, column) = self.selected_cell?;
+        let series = self.cache_df.as_ref()?.get_columns().get(column)?;
+        let name = series.name().to_string();
+        let mut value = self.inspect_cell(row, column).ok()?;
+        if self.redact_all && matches!(series.dtype(), DataType::Binary | DataType::Utf8) {
+            value = RedactStyle::Full.redact(&value);
+        }
+        let status = format!("[{}, {}] {}: {}", row, column, name, value);
+        match self.annotation(row, column) {
+            Some(annotation) if !annotation.note.is_empty() => {
+                Some(format!("{}  {} {}", status, etop_format::CELL_MARKER, annotation.note))
+            }
+            _ => Some(status),
+        }
+    }
+}
+
+/// exact representation of raw value of series at row
+pub(crate) fn cell_value(series: &Series, row: usize) -> Result<String, EtopError> {
+    let value = series.get(row).map_err(|_| {
+        EtopError::MissingData(format!("row index {} out of bounds for {} rows", row, series.len()))
+    })?;
+    let s = match value {
+        AnyValue::Null => "null".to_string(),
+        AnyValue::Binary(bytes) => etop_format::format_bytes_hex(bytes, true, false),
+        AnyValue::Utf8(s) => s.to_string(),
+        AnyValue::Float32(x) => x.to_string(),
+        AnyValue::Float64(x) => x.to_string(),
+        value => value.to_string(),
+    };
+    Ok(s)
+}
+
+/// message for window with no rows shown, telling apart rows filtered out from no rows at all
+fn empty_message(shown: usize, total: usize) -> Option<&'static str> {
+    match (shown, total) {
+        (0, 0) => Some(NO_DATA_IN_RANGE),
+        (0, _) => Some(NO_MATCHING_ROWS),
+        _ => None,
+    }
+}
+
+/// place message below header of rendered table, centered in the rows left for data
+fn place_message(render: &str, message: &str, header_lines: usize, render_height: usize) -> String {
+    let data_lines = render_height.saturating_sub(header_lines);
+    if data_lines == 0 {
+        return render.to_string()
+    }
+    let table_width = render.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let indent = table_width.saturating_sub(message.chars().count()) / 2;
+    let mut lines: Vec<String> = render.lines().map(String::from).collect();
+    lines.extend(std::iter::repeat(String::new()).take((data_lines - 1) / 2));
+    lines.push(format!("{}{}", " ".repeat(indent), message));
+    lines.join("\n")
+}
+
+/// style of each row of frame, from the first column with a rank highlight, else from the
+/// status of each row of a diff
+fn row_styles(fmt: &DataFrameFormat, df: &DataFrame) -> Result<Vec<Option<String>>, EtopError> {
+    match fmt.column_formats.iter().flatten().find(|c| c.rank_highlight.is_some()) {
+        Some(column) => {
+            let series = df.column(column.name.as_str())?;
+            Ok(column.clone().finalize(series.dtype())?.rank_styles(series)?)
+        }
+        None => Ok(crate::diff_styles(df).unwrap_or_else(|| vec![None; df.height()])),
+    }
+}
+
+/// frame with a leading column numbering its rows from 1
+///
+/// fails if frame has a column named ROW_NUMBER_COLUMN
+pub(crate) fn with_row_numbers(mut df: DataFrame) -> Result<DataFrame, EtopError> {
+    check_row_numbers(&df)?;
+    let numbers: Vec<u32> = (1..=df.height() as u32).collect();
+    df.insert_column(0, Series::new(ROW_NUMBER_COLUMN, numbers))?;
+    Ok(df)
+}
+
+/// fail if frame has a column named ROW_NUMBER_COLUMN, which row numbers would clash with
+fn check_row_numbers(df: &DataFrame) -> Result<(), EtopError> {
+    match df.column(ROW_NUMBER_COLUMN) {
+        Ok(_) => Err(EtopError::InvalidSpecification(format!(
+            "cannot number rows of data with a column named {}, turn off row numbers",
+            ROW_NUMBER_COLUMN
+        ))),
+        Err(_) => Ok(()),
+    }
+}
+
+pub(crate) fn get_default_format(
     column_name: String,
     dtype: DataType,
 ) -> Result<ColumnFormatShorthand, EtopError> {
@@ -258,6 +1072,14 @@ fn get_default_format(
             .name(column_name)
             .newline_underscores()
             .set_format(etop_format::StringFormat::new()),
+        DataType::Boolean => ColumnFormatShorthand::new()
+            .name(column_name)
+            .newline_underscores()
+            .set_format(etop_format::BoolFormat::new()),
+        // finalized into a duration format scaled by the time unit
+        DataType::Duration(_) => {
+            ColumnFormatShorthand::new().name(column_name).newline_underscores()
+        }
         _ => return Err(EtopError::UnsupportedDatatype(format!("{}", dtype))),
     };
     Ok(fmt)
@@ -0,0 +1,651 @@
+#[cfg(test)]
+mod ui_tests {
+    use crate::{EtopError, EtopState, RowStyle, SortKey, ROW_NUMBER_COLUMN};
+    use etop_format::{ColumnFormatShorthand, SortKind};
+    use polars::prelude::*;
+
+    fn state() -> EtopState {
+        let df = df!(
+            "address" => [vec![0x12u8; 20], vec![0xabu8; 20]],
+            "eth_sent" => [1_500_000_000_000_000_000.0, 2.0],
+        )
+        .unwrap();
+        EtopState { cache_df: Some(df), ..Default::default() }
+    }
+
+    #[test]
+    fn inspect_truncated_cell() {
+        let state = state();
+        let expected = format!("0x{}", "ab".repeat(20));
+        assert_eq!(state.inspect_cell(1, 0).unwrap(), expected);
+    }
+
+    #[test]
+    fn inspect_converted_cell() {
+        let state = state();
+        assert_eq!(state.inspect_cell(0, 1).unwrap(), "1500000000000000000");
+        assert_eq!(state.inspect_cell(1, 1).unwrap(), "2");
+    }
+
+    #[test]
+    fn inspect_out_of_bounds() {
+        let state = state();
+        assert!(state.inspect_cell(2, 0).is_err());
+        assert!(state.inspect_cell(0, 2).is_err());
+        assert!(EtopState::default().inspect_cell(0, 0).is_err());
+    }
+
+    #[test]
+    fn move_selected_cell() {
+        let mut state = state();
+        state.toggle_inspect();
+        assert_eq!(state.selected_cell, Some((0, 0)));
+        state.move_selected_cell(5, 1);
+        assert_eq!(state.selected_cell, Some((1, 1)));
+        state.move_selected_cell(-5, -5);
+        assert_eq!(state.selected_cell, Some((0, 0)));
+        assert_eq!(state.inspect_status(), Some(format!("[0, 0] address: 0x{}", "12".repeat(20))));
+        state.toggle_inspect();
+        assert_eq!(state.selected_cell, None);
+    }
+
+    #[test]
+    fn selected_cell_range() {
+        let df = df!("a" => [1u32, 22_222, 3], "b" => ["x", "y", "z"]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.selected_cell_range(0), None);
+
+        // cursor stays on the frame at its edges
+        state.toggle_inspect();
+        state.move_selected_cell(-1, -1);
+        assert_eq!(state.selected_cell, Some((0, 0)));
+        state.move_selected_cell(10, 10);
+        assert_eq!(state.selected_cell, Some((2, 1)));
+        assert_eq!(state.inspect_status(), Some("[2, 1] b: z".to_string()));
+
+        // only the focused cell of the selected row is given a range
+        let line = state.cache_df_render.as_ref().unwrap().lines().nth(4).unwrap().to_string();
+        let range = state.selected_cell_range(2).unwrap();
+        assert_eq!(line[range].trim(), "z");
+        assert_eq!(state.selected_cell_range(1), None);
+        state.move_selected_cell(-1, -1);
+        let line = state.cache_df_render.as_ref().unwrap().lines().nth(3).unwrap().to_string();
+        assert_eq!(&line[state.selected_cell_range(1).unwrap()], "22.2K");
+    }
+
+    #[test]
+    fn selection_follows_row() {
+        let mut state = EtopState { dataset: "transactions".to_string(), ..Default::default() };
+        let df = df!("transaction_hash" => ["a", "b", "c", "d"], "gas" => [4u32, 3, 2, 1]).unwrap();
+        state.warehouse.data.insert("transactions".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        state.toggle_inspect();
+        state.move_selected_cell(1, 1);
+        assert_eq!(state.inspect_status(), Some("[1, 1] gas: 3".to_string()));
+
+        // after a refresh re-sorts the rows, the selection stays on row b
+        let df = df!("transaction_hash" => ["e", "d", "c", "b", "a"], "gas" => [5u32, 1, 2, 3, 4])
+            .unwrap();
+        state.warehouse.data.insert("transactions".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.selected_cell, Some((3, 1)));
+        assert_eq!(state.inspect_status(), Some("[3, 1] gas: 3".to_string()));
+
+        // once the row is gone, the position is kept within the frame
+        let df = df!("transaction_hash" => ["x", "y"], "gas" => [7u32, 8]).unwrap();
+        state.warehouse.data.insert("transactions".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.selected_cell, Some((1, 1)));
+
+        // paging away from the inspected cell holds while the data is unchanged
+        let hashes: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let df =
+            df!("transaction_hash" => hashes, "gas" => (0..20u32).collect::<Vec<_>>()).unwrap();
+        state.warehouse.data.insert("transactions".to_string(), df);
+        state.cache_window(6, 40).unwrap();
+        state.scroll_pages(2);
+        state.cache_window(6, 40).unwrap();
+        assert_eq!(state.scroll_offset, 6);
+        assert_eq!(state.selected_cell, Some((1, 1)));
+    }
+
+    #[test]
+    fn search() {
+        let mut state = state();
+        state.search("abab").unwrap();
+        assert_eq!(state.selected_cell, Some((1, 0)));
+        state.search("15").unwrap();
+        assert_eq!(state.selected_cell, Some((0, 1)));
+        state.search("15").unwrap();
+        assert_eq!(state.selected_cell, Some((0, 1)));
+        assert!(state.search("zz").is_err());
+    }
+
+    #[test]
+    fn scroll_keeps_header_fixed() {
+        let df = df!("n" => (0u32..10).collect::<Vec<_>>()).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let lines = |state: &EtopState| -> Vec<String> {
+            let render = state.cache_df_render.clone().unwrap();
+            render.lines().map(|line| line.trim().to_string()).collect()
+        };
+
+        // 6 lines of table leave 4 rows for data below the header and its separator
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(state.cache_visible_rows, 0..4);
+        assert_eq!(lines(&state), vec!["n", "─", "0", "1", "2", "3"]);
+
+        state.scroll(3);
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(lines(&state), vec!["n", "─", "3", "4", "5", "6"]);
+
+        // last row reachable
+        state.scroll_pages(5);
+        assert_eq!(state.scroll_offset, 6);
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(lines(&state), vec!["n", "─", "6", "7", "8", "9"]);
+
+        // moving the inspected cell out of view scrolls
+        state.toggle_inspect();
+        assert_eq!(state.scroll_offset, 0);
+        state.move_selected_cell(5, 0);
+        assert_eq!(state.scroll_offset, 2);
+
+        // only the header fits
+        state.cache_window(2, 20).unwrap();
+        assert!(state.cache_visible_rows.is_empty());
+        assert_eq!(lines(&state), vec!["n", "─"]);
+    }
+
+    #[test]
+    fn scroll_indicator() {
+        let df = df!("n" => (0u32..20).collect::<Vec<_>>()).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let bar = |state: &EtopState| state.scroll_bar(4).into_iter().collect::<String>();
+
+        // 7 lines of table leave 5 rows for data
+        state.cache_window(7, 20).unwrap();
+        assert_eq!(state.scroll_indicator(), "[1/20]");
+        assert_eq!(state.scroll_fractions(), (0.0, 0.25));
+        assert_eq!(bar(&state), "█│││");
+
+        state.scroll(10);
+        state.cache_window(7, 20).unwrap();
+        assert_eq!(state.scroll_indicator(), "[11/20]");
+        assert_eq!(state.scroll_fractions(), (0.5, 0.25));
+        assert_eq!(bar(&state), "││█│");
+
+        state.scroll(100);
+        state.cache_window(7, 20).unwrap();
+        assert_eq!(state.scroll_indicator(), "[16/20]");
+        assert_eq!(bar(&state), "│││█");
+
+        // frame smaller than table gives a full bar
+        state.cache_window(30, 20).unwrap();
+        assert_eq!(state.scroll_indicator(), "[1/20]");
+        assert_eq!(state.scroll_fractions(), (0.0, 1.0));
+        assert_eq!(bar(&state), "████");
+
+        assert_eq!(EtopState::default().scroll_indicator(), "[0/0]");
+        assert_eq!(EtopState::default().scroll_bar(2), vec!['█', '█']);
+    }
+
+    #[test]
+    fn rank_highlight() {
+        let df = df!("n" => [3u32, 8, 5, 8, 1, 6]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(state.cache_row_styles, vec![None; 4]);
+
+        // ties share gold, leaving no silver
+        state.layout.highlight = Some("n".to_string());
+        state.cache_window(6, 20).unwrap();
+        let gold = Some("#ffd700".to_string());
+        let bronze = Some("#cd7f32".to_string());
+        assert_eq!(state.cache_row_styles, vec![None, gold.clone(), None, gold.clone()]);
+
+        // ranks follow sort and scroll
+        state.layout.sort = vec![crate::SortKey { column: "n".to_string(), descending: false }];
+        state.scroll(2);
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(state.cache_row_styles, vec![None, bronze, gold.clone(), gold]);
+    }
+
+    #[test]
+    fn redact_all() {
+        let df = df!("name" => ["alice", "bob"], "n" => [1u32, 2]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let cells = |state: &mut EtopState| -> Vec<String> {
+            state.cache_window(10, 40).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            render
+                .lines()
+                .skip(2)
+                .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect()
+        };
+        assert_eq!(cells(&mut state), vec!["alice 1", "bob 2"]);
+        state.redact_all = true;
+        assert_eq!(cells(&mut state), vec!["**** 1", "**** 2"]);
+        assert_eq!(state.inspect_cell(0, 0).unwrap(), "alice");
+        state.toggle_inspect();
+        assert_eq!(state.inspect_status(), Some("[0, 0] name: ****".to_string()));
+    }
+
+    #[test]
+    fn zebra_rows() {
+        let df = df!("n" => [3u32, 8, 5, 1, 6, 2]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.layout.zebra = true;
+        state.cache_window(6, 20).unwrap();
+        let styles = |state: &EtopState| -> Vec<RowStyle> {
+            (0..state.cache_visible_rows.len()).map(|row| state.row_style(row)).collect()
+        };
+        let (plain, zebra) = (RowStyle::Plain, RowStyle::Zebra);
+        assert_eq!(
+            styles(&state),
+            vec![plain.clone(), zebra.clone(), plain.clone(), zebra.clone()]
+        );
+
+        // stripes stay with frame rows while scrolling
+        state.scroll(1);
+        state.cache_window(6, 20).unwrap();
+        assert_eq!(
+            styles(&state),
+            vec![zebra.clone(), plain.clone(), zebra.clone(), plain.clone()]
+        );
+
+        // selection wins over rank highlight, which wins over stripes
+        state.layout.highlight = Some("n".to_string());
+        state.selected_cell = Some((1, 0));
+        state.cache_window(6, 20).unwrap();
+        let silver = RowStyle::Rank("#c0c0c0".to_string());
+        let bronze = RowStyle::Rank("#cd7f32".to_string());
+        assert_eq!(styles(&state), vec![RowStyle::Selected, bronze, zebra, silver]);
+
+        state.layout.zebra = false;
+        state.selected_cell = None;
+        state.layout.highlight = None;
+        state.cache_window(6, 20).unwrap();
+        assert!(styles(&state).iter().all(|style| style == &plain));
+    }
+
+    #[test]
+    fn data_row_styles() {
+        let df = df!(
+            "transaction_hash" => ["a", "b", "c", "d"],
+            "block_number" => [1u32, 1, 2, 2],
+            "gas_used" => [5u64, 9, 7, 1],
+            "success" => [true, false, true, false],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "transactions".to_string(), ..Default::default() };
+        state.warehouse.data.insert("transactions".to_string(), df.clone());
+        state.layout.zebra = true;
+        state.cache_window(10, 40).unwrap();
+        let styles = |state: &EtopState| -> Vec<RowStyle> {
+            (0..state.cache_visible_rows.len()).map(|row| state.row_style(row)).collect()
+        };
+        let (plain, zebra, red) =
+            (RowStyle::Plain, RowStyle::Zebra, RowStyle::Data("red".to_string()));
+
+        // failed transactions are red, over stripes
+        assert_eq!(styles(&state), vec![plain.clone(), red.clone(), plain.clone(), red.clone()]);
+
+        // styles follow rows when sorted, and selection, which follows its row, wins over them
+        state.layout.sort = vec![SortKey { column: "gas_used".to_string(), descending: false }];
+        state.selected_cell = Some((3, 0));
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(
+            styles(&state),
+            vec![RowStyle::Selected, zebra.clone(), plain.clone(), red.clone()]
+        );
+
+        // styles follow the data of rows, even of hidden columns
+        state.layout.columns = Some(vec!["transaction_hash".to_string(), "gas_used".to_string()]);
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(styles(&state), vec![RowStyle::Selected, zebra, plain, red.clone()]);
+
+        // other datasets give no row styles
+        state.dataset = "logs".to_string();
+        state.warehouse.data.insert("logs".to_string(), df);
+        state.cache_window(10, 40).unwrap();
+        assert!(!styles(&state).contains(&red));
+    }
+
+    #[test]
+    fn bool_footer() {
+        let df = df!("n" => [1u32, 2, 3], "ok" => [true, true, false]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.cache_window(6, 40).unwrap();
+        assert_eq!(state.cache_header_lines, 2);
+        assert_eq!(state.cache_visible_rows, 0..2);
+        let render = state.cache_df_render.clone().unwrap();
+        assert_eq!(render.lines().count(), 6);
+        assert!(render.lines().last().unwrap().trim_end().ends_with("█████ 100%"));
+
+        state.cache_window(10, 40).unwrap();
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.lines().last().unwrap().trim_end().ends_with("░ 66%"));
+    }
+
+    #[test]
+    fn transpose() {
+        let df = df!("name" => ["alice"], "n" => [7u32]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.layout.transpose = true;
+        state.layout.highlight = Some("n".to_string());
+        state.cache_window(10, 40).unwrap();
+
+        let df = state.cache_df.clone().unwrap();
+        assert_eq!(df.get_column_names(), vec!["field", "value"]);
+        assert_eq!(state.cache_visible_rows, 0..2);
+        assert_eq!(state.cache_row_styles, vec![None, None]);
+        assert_eq!(state.inspect_cell(1, 1).unwrap(), "7");
+        let render = state.cache_df_render.clone().unwrap();
+        let lines: Vec<String> =
+            render.lines().map(|l| l.split_whitespace().collect::<Vec<_>>().join(" ")).collect();
+        assert_eq!(lines[2..], ["name alice", "n 7"]);
+    }
+
+    #[test]
+    fn pinned_columns() {
+        let df = df!(
+            "a" => [1u32], "b" => [2u32], "c" => [3u32], "d" => [4u32], "value" => [5u32],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let header = |state: &mut EtopState| -> Vec<String> {
+            state.cache_window(5, 13).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            render.lines().next().unwrap().split_whitespace().map(String::from).collect()
+        };
+
+        // without pinning, scrolling past value hides it
+        assert_eq!(header(&mut state), vec!["a", "b", "c", "d"]);
+        state.scroll_columns(1);
+        assert_eq!(header(&mut state), vec!["b", "c", "d"]);
+
+        // pinned column is shown at every offset
+        state.pin_column("value".to_string()).unwrap();
+        state.column_offset = 0;
+        let mut headers = vec![];
+        for _ in 0..6 {
+            headers.push(header(&mut state));
+            state.scroll_columns(1);
+        }
+        assert_eq!(
+            headers,
+            vec![
+                vec!["a", "b", "value"],
+                vec!["b", "c", "value"],
+                vec!["c", "d", "value"],
+                vec!["d", "value"],
+                vec!["d", "value"],
+                vec!["d", "value"],
+            ]
+        );
+        assert_eq!(state.column_offset, 3);
+
+        // frozen columns stay left of the scrolled columns
+        state.layout.frozen_columns = 1;
+        state.column_offset = 1;
+        assert_eq!(header(&mut state), vec!["a", "c", "value"]);
+
+        // pinning more columns than fit keeps the first pinned columns, even over frozen ones
+        for column in ["d", "c", "b"] {
+            state.pin_column(column.to_string()).unwrap();
+        }
+        assert_eq!(header(&mut state), vec!["value", "d", "c"]);
+
+        assert!(state.pin_column("missing".to_string()).is_err());
+        assert!(state.unpin_column("d"));
+        assert!(!state.unpin_column("d"));
+        assert_eq!(state.layout.pinned_columns, vec!["value", "c", "b"]);
+    }
+
+    #[test]
+    fn status_line() {
+        let df = df!("gas_used" => [5u32, 30, 20, 10], "value" => [0u32, 1, 2, 0]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        assert_eq!(state.status_line(), "view: test_blocks");
+
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(state.status_line(), "4/4 rows | view: test_blocks");
+
+        state.layout.sort =
+            vec![crate::SortKey { column: "gas_used".to_string(), descending: true }];
+        state.layout.filters = vec!["value > 0".to_string()];
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(
+            state.status_line(),
+            "sort: gas_used↓ | filter: value>0 | 2/4 rows | view: test_blocks"
+        );
+
+        state.layout.sort.push(crate::SortKey { column: "value".to_string(), descending: false });
+        state.layout.filters.push("gas_used <= 20".to_string());
+        state.cache_window(10, 40).unwrap();
+        assert_eq!(
+            state.status_line(),
+            "sort: gas_used↓, value↑ | filter: value>0 & gas_used<=20 | 1/4 rows | view: test_blocks"
+        );
+    }
+
+    #[test]
+    fn status_granularity() {
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.window.start_block = Some(100);
+        state.window.end_block = Some(120);
+        assert_eq!(state.status_line(), "blocks 100–120 | view: test_blocks");
+
+        state.window.start_block = Some(120);
+        assert_eq!(state.status_line(), "block 120 | view: test_blocks");
+
+        // views of transactions and logs describe the range in their own unit
+        state.window.start_block = Some(100);
+        state.dataset = "transactions_by_to_address".to_string();
+        assert_eq!(state.dataspec().unwrap().granularity(), crate::Granularity::Transaction);
+        assert_eq!(state.status_line(), "txs in blocks 100–120 | view: transactions_by_to_address");
+        state.dataset = "erc20_transfers_by_erc20".to_string();
+        assert_eq!(state.status_line(), "logs in blocks 100–120 | view: erc20_transfers_by_erc20");
+    }
+
+    #[test]
+    fn stable_sort() {
+        let df = df!(
+            "id" => ["a", "b", "c", "d", "e"],
+            "gas_used" => [2u64, 1, 2, 1, 2],
+            "n_txs" => [1u32, 1, 1, 2, 1],
+        )
+        .unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let ids = |state: &EtopState| -> Vec<String> {
+            let df = state.window_frame().unwrap();
+            assert_eq!(df.get_column_names(), vec!["id", "gas_used", "n_txs"]);
+            df.column("id").unwrap().utf8().unwrap().into_no_null_iter().map(String::from).collect()
+        };
+        let key = |column: &str, descending: bool| SortKey { column: column.into(), descending };
+
+        // ties keep their original order, in either direction
+        state.layout.sort = vec![key("gas_used", false)];
+        assert_eq!(ids(&state), vec!["b", "d", "a", "c", "e"]);
+        state.layout.sort = vec![key("gas_used", true)];
+        assert_eq!(ids(&state), vec!["a", "c", "e", "b", "d"]);
+
+        // and across multiple keys
+        state.layout.sort = vec![key("n_txs", true), key("gas_used", true)];
+        assert_eq!(ids(&state), vec!["d", "a", "c", "e", "b"]);
+    }
+
+    #[test]
+    fn sort_as() {
+        let df = df!("id" => ["item10", "item2", "item1"], "n" => [1u32, 2, 3]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        let ids = |state: &EtopState| -> Vec<String> {
+            let df = state.window_frame().unwrap();
+            assert_eq!(df.get_column_names(), vec!["id", "n"]);
+            df.column("id").unwrap().utf8().unwrap().into_no_null_iter().map(String::from).collect()
+        };
+        state.layout.sort = vec![SortKey { column: "id".to_string(), descending: false }];
+        assert_eq!(ids(&state), vec!["item1", "item10", "item2"]);
+
+        let natural = ColumnFormatShorthand::new().name("id").sort_as(SortKind::Natural);
+        state.column_formats.insert("id".to_string(), natural);
+        assert_eq!(ids(&state), vec!["item1", "item2", "item10"]);
+        state.layout.sort[0].descending = true;
+        assert_eq!(ids(&state), vec!["item10", "item2", "item1"]);
+    }
+
+    #[test]
+    fn column_groups() {
+        let df = df!("n" => [1u32], "gas_used" => [10u32], "gas_limit" => [20u32]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.layout.column_groups =
+            vec![("gas".into(), vec!["gas_used".into(), "gas_limit".into()])];
+        let first_line = |state: &mut EtopState| {
+            state.cache_window(10, 80).unwrap();
+            state.cache_df_render.clone().unwrap().lines().next().unwrap().trim().to_string()
+        };
+        assert!(first_line(&mut state).contains(" gas "));
+        assert_eq!(state.cache_header_lines, 4);
+
+        // group is dropped along with its members
+        state.layout.columns = Some(vec!["n".into()]);
+        assert_eq!(first_line(&mut state), "n");
+    }
+
+    #[test]
+    fn empty_window_message() {
+        let df = df!("number" => [1u32, 2], "gas" => [10u32, 20], "miner" => ["a", "b"]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df.clone());
+        let lines = |state: &mut EtopState| -> Vec<String> {
+            state.cache_window(8, 40).unwrap();
+            let render = state.cache_df_render.clone().unwrap();
+            render.lines().map(|line| line.trim_end().to_string()).collect()
+        };
+        assert_eq!(state.empty_message(), None);
+
+        // rows filtered out, header kept and message centered in the 6 data lines
+        state.layout.filters = vec!["gas > 100".to_string()];
+        let filtered = lines(&mut state);
+        assert_eq!(filtered.len(), 5);
+        assert_eq!(filtered[2..4], ["", ""]);
+        assert_eq!(filtered[0], "number   gas   miner");
+        assert_eq!(filtered[4], "   no rows match");
+        assert_eq!(state.empty_message(), Some(crate::NO_MATCHING_ROWS));
+        assert!(state.format_window(8, 40).unwrap().ends_with("no rows match"));
+
+        // no rows before filters
+        state.layout.filters.clear();
+        state.warehouse.data.insert("test_blocks".to_string(), df.head(Some(0)));
+        assert_eq!(lines(&mut state).last().unwrap().trim(), crate::NO_DATA_IN_RANGE);
+        assert_eq!(state.empty_message(), Some(crate::NO_DATA_IN_RANGE));
+    }
+
+    #[test]
+    fn current_frame() {
+        let df = df!("number" => [1u32, 2, 3], "gas" => [10u32, 20, 30]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df.clone());
+        assert_eq!(state.current_frame().height(), 0);
+        state.cache_window(8, 40).unwrap();
+        assert!(state.current_frame().frame_equal(&df));
+
+        state.layout.filters = vec!["gas > 10".to_string()];
+        state.layout.sort = vec![SortKey { column: "gas".to_string(), descending: true }];
+        state.cache_window(8, 40).unwrap();
+        let expected = df!("number" => [3u32, 2], "gas" => [30u32, 20]).unwrap();
+        assert!(state.current_frame().frame_equal(&expected));
+        assert!(state.current_frame_owned().frame_equal(&expected));
+    }
+
+    #[test]
+    fn view_schema() {
+        let mut state = EtopState { dataset: "blocks".to_string(), ..Default::default() };
+        assert!(state.view_schema().is_empty());
+
+        let blocks = df!(
+            "block_number" => [1u32, 2],
+            "timestamp" => [100u32, 112],
+            "gas_used" => [30u64, 10],
+            "author" => [vec![3u8; 20], vec![4u8; 20]],
+        )
+        .unwrap();
+        let txs = df!("block_number" => [1u32, 1, 2]).unwrap();
+        state.warehouse.data.insert("blocks".to_string(), blocks);
+        state.warehouse.data.insert("transactions".to_string(), txs);
+
+        // n_txs is computed by the transform
+        let schema = state.view_schema();
+        let expected = [
+            ("block_number", DataType::UInt32),
+            ("timestamp", DataType::UInt32),
+            ("n_txs", DataType::UInt32),
+            ("gas_used", DataType::UInt64),
+            ("author", DataType::Binary),
+        ];
+        let expected: Vec<(String, DataType)> =
+            expected.into_iter().map(|(name, dtype)| (name.to_string(), dtype)).collect();
+        assert_eq!(schema, expected);
+    }
+
+    #[test]
+    fn row_numbers() {
+        let df = df!("n" => [3u32, 8, 5, 1, 6], "m" => [1u32, 2, 3, 4, 5]).unwrap();
+        let mut state = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.layout.show_row_numbers = true;
+        state.layout.filters = vec!["n > 2".to_string()];
+        state.layout.sort = vec![SortKey { column: "n".to_string(), descending: true }];
+        state.cache_window(10, 80).unwrap();
+        let column = |state: &EtopState, name: &str| -> Vec<u32> {
+            let df = state.cache_df.as_ref().unwrap();
+            df.column(name).unwrap().u32().unwrap().into_no_null_iter().collect()
+        };
+        assert_eq!(column(&state, ROW_NUMBER_COLUMN), vec![1, 2, 3, 4]);
+        assert_eq!(column(&state, "n"), vec![8, 6, 5, 3]);
+        let render = state.cache_df_render.clone().unwrap();
+        assert!(render.lines().next().unwrap().trim_start().starts_with('#'));
+        assert_eq!(render.lines().nth(2), Some("1   8   2"));
+
+        // row numbers stay in view while scrolling horizontally
+        state.scroll_columns(1);
+        state.cache_window(10, 80).unwrap();
+        let df = state.cache_df.as_ref().unwrap();
+        assert_eq!(df.get_column_names(), vec![ROW_NUMBER_COLUMN, "m"]);
+
+        // the current frame and exports omit them
+        assert_eq!(state.current_frame().get_column_names(), vec!["n", "m"]);
+
+        // the inspect cursor skips them
+        state.toggle_inspect();
+        assert_eq!(state.selected_cell, Some((0, 1)));
+        state.move_selected_cell(0, -1);
+        assert_eq!(state.selected_cell, Some((0, 1)));
+        state.search("1").unwrap();
+        assert_eq!(state.selected_cell, Some((3, 1)));
+        assert!(state.cell_key(0, 0).is_err());
+
+        // data with a column of the same name cannot be numbered
+        let df = df!("#" => [1u32, 2], "n" => [3u32, 4]).unwrap();
+        state.warehouse.data.insert("test_blocks".to_string(), df);
+        state.layout.filters = vec![];
+        state.layout.sort = vec![];
+        assert!(matches!(state.cache_window(10, 80), Err(EtopError::InvalidSpecification(_))));
+    }
+}
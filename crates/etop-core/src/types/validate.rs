@@ -0,0 +1,67 @@
+#[cfg(test)]
+#[path = "validate_tests.rs"]
+mod tests;
+
+use crate::{DataSource, DatasetQuery, EtopError, EtopState, InputDataset};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+impl EtopState {
+    /// check that the source provides the columns required by the view's dataset, fetching a
+    /// single block of each input instead of the whole window
+    ///
+    /// derived inputs are queried with a value from the sample of the input they derive from,
+    /// and are skipped if that sample has no values. problems of every input are reported
+    /// together rather than stopping at the first
+    pub async fn validate(&self, source: &dyn DataSource) -> Result<(), EtopError> {
+        let dataspec = self.dataspec()?;
+        let block = self.window.end_block.or(self.latest_block).ok_or_else(|| {
+            EtopError::MissingData("no block to sample, specify a block".to_string())
+        })?;
+        let required = dataspec.required_columns();
+        let mut samples: HashMap<String, DataFrame> = HashMap::new();
+        let mut problems = vec![];
+        for input in dataspec.inputs() {
+            let name = input.name();
+            let query = match &input {
+                InputDataset::Raw(_) => DatasetQuery::Block(input.clone(), vec![block]),
+                InputDataset::Derived { derived_from, derived_from_column, .. } => {
+                    let sample = samples.get(derived_from);
+                    match sample.and_then(|df| first_value(df, derived_from_column)) {
+                        Some(value) => DatasetQuery::Address(input.clone(), vec![value]),
+                        None => continue,
+                    }
+                }
+            };
+            match source.fetch(&query).await {
+                Ok(df) => {
+                    let missing: Vec<String> = required
+                        .get(&name)
+                        .into_iter()
+                        .flatten()
+                        .filter(|column| df.column(column).is_err())
+                        .cloned()
+                        .collect();
+                    if !missing.is_empty() {
+                        problems.push(format!("{} is missing {}", name, missing.join(", ")));
+                    }
+                    samples.insert(name, df);
+                }
+                Err(e) => problems.push(format!("could not fetch {}: {:?}", name, e)),
+            }
+        }
+        if !problems.is_empty() {
+            return Err(EtopError::InvalidSpecification(format!(
+                "source does not match view {}: {}",
+                self.dataset,
+                problems.join("; ")
+            )))
+        }
+        Ok(())
+    }
+}
+
+/// first non-null value of string column
+fn first_value(df: &DataFrame, column: &str) -> Option<String> {
+    df.column(column).ok()?.utf8().ok()?.into_iter().flatten().next().map(String::from)
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod validate_tests {
+    use crate::{DataSource, DatasetQuery, EtopError, EtopState};
+    use polars::prelude::*;
+
+    /// source whose transactions lack some columns
+    struct PartialSource {
+        transaction_columns: Vec<&'static str>,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for PartialSource {
+        async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+            let (dataset, blocks) = match query {
+                DatasetQuery::Block(dataset, blocks) => (dataset.name(), blocks.clone()),
+                _ => return Err(EtopError::MissingData("not a block query".to_string())),
+            };
+            assert_eq!(blocks.len(), 1);
+            let columns = match dataset.as_str() {
+                "blocks" => vec!["block_number", "gas_used"],
+                "transactions" => self.transaction_columns.clone(),
+                _ => return Err(EtopError::MissingData(format!("no dataset {}", dataset))),
+            };
+            let columns = columns.into_iter().map(|name| Series::new(name, &blocks)).collect();
+            Ok(DataFrame::new(columns)?)
+        }
+    }
+
+    fn state(dataset: &str) -> EtopState {
+        let mut state = EtopState { dataset: dataset.to_string(), ..Default::default() };
+        state.window.end_block = Some(100);
+        state
+    }
+
+    #[tokio::test]
+    async fn validate() {
+        let source = PartialSource { transaction_columns: vec!["block_number", "to_address"] };
+        assert!(state("blocks").validate(&source).await.is_ok());
+
+        // every missing column is reported at once
+        let error = state("transactions_by_to_address").validate(&source).await.unwrap_err();
+        let message = format!("{:?}", error);
+        assert!(message.contains("transactions is missing value_f64, gas_price, gas_used"));
+
+        // fetch failures are reported alongside missing columns
+        let error = state("erc20_transfers_by_erc20").validate(&source).await.unwrap_err();
+        assert!(format!("{:?}", error).contains("could not fetch erc20_transfers"));
+
+        // a block to sample is required
+        let state = EtopState { dataset: "blocks".to_string(), ..Default::default() };
+        assert!(matches!(state.validate(&source).await, Err(EtopError::MissingData(_))));
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+#[path = "views_tests.rs"]
+mod tests;
+
+use crate::{DataSource, DatasetQuery, EtopError, EtopState, Split};
+use polars::prelude::*;
+
+/// set of views shown as tabs, each with its own window, layout, and filters
+#[derive(Debug, Clone)]
+pub struct Views {
+    /// views, in tab order
+    pub views: Vec<EtopState>,
+    /// index of active view
+    pub current: usize,
+    /// whether a refresh fetches inputs of every view, rather than only of the active view
+    pub refresh_all: bool,
+    /// views shown side by side, if split
+    pub split: Option<Split>,
+}
+
+impl Views {
+    /// create views, with the first view active
+    pub fn new(views: Vec<EtopState>) -> Result<Views, EtopError> {
+        if views.is_empty() {
+            return Err(EtopError::ArgumentError("at least one view required".to_string()))
+        }
+        Ok(Views { views, current: 0, refresh_all: false, split: None })
+    }
+
+    /// active view
+    pub fn current(&self) -> &EtopState {
+        &self.views[self.current]
+    }
+
+    /// active view, mutably
+    pub fn current_mut(&mut self) -> &mut EtopState {
+        &mut self.views[self.current]
+    }
+
+    /// names of views, in tab order
+    pub fn names(&self) -> Vec<String> {
+        self.views.iter().map(|view| view.dataset.clone()).collect()
+    }
+
+    /// activate next view, wrapping around after the last
+    pub fn next_view(&mut self) {
+        self.current = (self.current + 1) % self.views.len();
+    }
+
+    /// activate previous view, wrapping around before the first
+    pub fn prev_view(&mut self) {
+        self.current = (self.current + self.views.len() - 1) % self.views.len();
+    }
+
+    /// activate view by name
+    pub fn goto_view(&mut self, name: &str) -> Result<(), EtopError> {
+        match self.views.iter().position(|view| view.dataset == name) {
+            Some(index) => {
+                self.current = index;
+                Ok(())
+            }
+            None => Err(EtopError::UnknownData(format!("no view named {}", name))),
+        }
+    }
+
+    /// indices of views whose inputs are fetched on refresh, both panes of a split including
+    /// the active view
+    pub fn views_to_refresh(&self) -> Vec<usize> {
+        if self.refresh_all {
+            (0..self.views.len()).collect()
+        } else if let Some(split) = self.active_split() {
+            vec![split.left, split.right]
+        } else {
+            vec![self.current]
+        }
+    }
+
+    /// record a newly seen block in every view
+    pub fn see_block(&mut self, seen_block: u32) {
+        for view in self.views.iter_mut() {
+            view.see_block(seen_block);
+        }
+    }
+
+    /// queries for data missing from the views being refreshed
+    ///
+    /// queries of different views for the same dataset are merged, so that each dataset is
+    /// fetched once however many views use it
+    pub fn create_missing_queries(&self) -> Result<Vec<DatasetQuery>, EtopError> {
+        let mut queries: Vec<DatasetQuery> = vec![];
+        for index in self.views_to_refresh() {
+            for query in self.views[index].create_missing_queries()? {
+                merge_query(&mut queries, query);
+            }
+        }
+        Ok(queries)
+    }
+
+    /// add result of query to the warehouse of every view
+    pub fn receive_query(&mut self, query: DatasetQuery, df: DataFrame) -> Result<(), EtopError> {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).ok();
+        for view in self.views.iter_mut() {
+            view.warehouse.add_dataset(query.clone().dataset(), df.clone())?;
+            view.cache_pane_frames = None;
+            view.fetched_at = now.map(|now| now.as_secs());
+        }
+        Ok(())
+    }
+
+    /// record that n fetches started, in every view
+    pub fn start_fetch(&mut self, n: usize) {
+        self.views.iter_mut().for_each(|view| view.fetch.start(n));
+    }
+
+    /// record that a fetch completed, in every view
+    pub fn complete_fetch(&mut self) {
+        self.views.iter_mut().for_each(|view| view.fetch.complete());
+    }
+
+    /// record that a fetch failed, in every view
+    pub fn fail_fetch(&mut self, error: String) {
+        self.views.iter_mut().for_each(|view| view.fetch.fail(error.clone()));
+    }
+
+    /// fetch data missing from the views being refreshed, returning number of queries fetched
+    pub async fn fetch_missing<S: DataSource + ?Sized>(
+        &mut self,
+        source: &S,
+    ) -> Result<usize, EtopError> {
+        let queries = self.create_missing_queries()?;
+        for query in queries.iter() {
+            let df = source.fetch(query).await?;
+            self.receive_query(query.clone(), df)?;
+        }
+        Ok(queries.len())
+    }
+}
+
+fn merge_query(queries: &mut Vec<DatasetQuery>, query: DatasetQuery) {
+    let name = query.clone().dataset().name();
+    let existing = queries.iter_mut().find(|q| (*q).clone().dataset().name() == name);
+    match (existing, query) {
+        (Some(DatasetQuery::Block(_, blocks)), DatasetQuery::Block(_, new_blocks)) => {
+            blocks.extend(new_blocks);
+            blocks.sort();
+            blocks.dedup();
+        }
+        (Some(DatasetQuery::Address(_, addresses)), DatasetQuery::Address(_, new_addresses)) => {
+            addresses.extend(new_addresses);
+            addresses.sort();
+            addresses.dedup();
+        }
+        (_, query) => queries.push(query),
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod views_tests {
+    use crate::{DataSource, DatasetQuery, EtopError, EtopState, Views, Window};
+    use polars::prelude::*;
+    use std::sync::Mutex;
+
+    fn views() -> Views {
+        let views = ["transactions", "logs", "blocks"]
+            .iter()
+            .map(|name| EtopState { dataset: name.to_string(), ..Default::default() })
+            .collect();
+        Views::new(views).unwrap()
+    }
+
+    #[test]
+    fn switch_views() {
+        let mut views = views();
+        assert_eq!(views.names(), vec!["transactions", "logs", "blocks"]);
+        assert_eq!(views.current().dataset, "transactions");
+
+        views.next_view();
+        assert_eq!(views.current().dataset, "logs");
+        views.next_view();
+        views.next_view();
+        assert_eq!(views.current().dataset, "transactions");
+
+        views.prev_view();
+        assert_eq!(views.current().dataset, "blocks");
+
+        views.goto_view("logs").unwrap();
+        assert_eq!(views.current, 1);
+        assert!(views.goto_view("missing").is_err());
+        assert_eq!(views.current, 1);
+
+        assert!(Views::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn views_keep_own_state() {
+        let mut views = views();
+        views.current_mut().layout.filters.push("value > 0".to_string());
+        views.next_view();
+        assert!(views.current().layout.filters.is_empty());
+        views.prev_view();
+        assert_eq!(views.current().layout.filters, vec!["value > 0"]);
+
+        views.see_block(100);
+        assert!(views.views.iter().all(|view| view.latest_block == Some(100)));
+    }
+
+    #[test]
+    fn views_to_refresh() {
+        let mut views = views();
+        views.next_view();
+        assert_eq!(views.views_to_refresh(), vec![1]);
+        views.refresh_all = true;
+        assert_eq!(views.views_to_refresh(), vec![0, 1, 2]);
+    }
+
+    struct CountingSource {
+        fetches: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for CountingSource {
+        async fn fetch(&self, query: &DatasetQuery) -> Result<DataFrame, EtopError> {
+            let (name, blocks) = match query {
+                DatasetQuery::Block(dataset, blocks) => (dataset.name(), blocks.clone()),
+                _ => panic!("unexpected query"),
+            };
+            self.fetches.lock().unwrap().push(name);
+            Ok(df!("block_number" => blocks)?)
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_inputs_fetched_once() {
+        let window = Window { start_block: Some(1), end_block: Some(3), ..Default::default() };
+        let views = ["blocks", "transactions_by_to_address"]
+            .iter()
+            .map(|name| EtopState {
+                dataset: name.to_string(),
+                window: window.clone(),
+                ..Default::default()
+            })
+            .collect();
+        let mut views = Views::new(views).unwrap();
+        views.refresh_all = true;
+
+        let source = CountingSource { fetches: Mutex::new(vec![]) };
+        assert_eq!(views.fetch_missing(&source).await.unwrap(), 2);
+        let mut fetches = source.fetches.lock().unwrap().clone();
+        fetches.sort();
+        assert_eq!(fetches, vec!["blocks", "transactions"]);
+        for view in views.views.iter() {
+            assert_eq!(view.warehouse.get_dataset("transactions").unwrap().height(), 3);
+        }
+
+        // nothing left to fetch
+        assert_eq!(views.fetch_missing(&source).await.unwrap(), 0);
+        assert_eq!(source.fetches.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn merge_queries_across_views() {
+        let mut views = views();
+        views.views[1].window =
+            Window { start_block: Some(1), end_block: Some(2), ..Default::default() };
+        views.views[2].window =
+            Window { start_block: Some(2), end_block: Some(4), ..Default::default() };
+        views.views[2].dataset = "logs".to_string();
+        views.refresh_all = true;
+        let queries = views.create_missing_queries().unwrap();
+        assert_eq!(queries.len(), 1);
+        match &queries[0] {
+            DatasetQuery::Block(dataset, blocks) => {
+                assert_eq!(dataset.name(), "logs");
+                assert_eq!(blocks, &vec![1, 2, 3, 4]);
+            }
+            _ => panic!("expected block query"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::types::{BinaryAlign, BinaryFormat};
+use std::collections::HashMap;
 
 impl BinaryFormat {
     /// create new number format
@@ -68,6 +69,26 @@ impl BinaryFormat {
         }
     }
 
+    /// show at most max_bytes bytes, noting the total length of longer data
+    pub fn max_bytes(mut self, max_bytes: usize) -> BinaryFormat {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// show function signature instead of data that starts with its 4-byte selector, such as
+    /// `transfer(address,uint256)` for calldata starting with `0xa9059cbb`
+    pub fn decode_selector(mut self, signatures: &HashMap<[u8; 4], String>) -> BinaryFormat {
+        self.selectors = Some(signatures.clone());
+        self
+    }
+
+    /// show event signature instead of its 32-byte topic hash, such as
+    /// `Transfer(address,address,uint256)` for topic0 of erc20 transfers
+    pub fn decode_topic(mut self, signatures: &HashMap<[u8; 32], String>) -> BinaryFormat {
+        self.topics = Some(signatures.clone());
+        self
+    }
+
     // align
 
     /// left align
@@ -89,4 +110,18 @@ impl BinaryFormat {
         self.fill_char = fill_char;
         self
     }
+
+    // case
+
+    /// uppercase hex digits
+    pub fn uppercase(mut self) -> BinaryFormat {
+        self.uppercase = true;
+        self
+    }
+
+    /// lowercase hex digits
+    pub fn lowercase(mut self) -> BinaryFormat {
+        self.uppercase = false;
+        self
+    }
 }
@@ -1,4 +1,4 @@
 mod builder;
 mod types;
 
-pub use types::BinaryFormat;
+pub use types::{format_bytes_hex, BinaryFormat};
@@ -3,6 +3,9 @@
 mod tests;
 
 use crate::FormatError;
+use std::collections::HashMap;
+use unicode_truncate::UnicodeTruncateStr;
+use unicode_width::UnicodeWidthStr;
 
 /// binary format specification
 #[derive(Debug, Clone)]
@@ -17,6 +20,14 @@ pub struct BinaryFormat {
     pub align: BinaryAlign,
     /// fill padding char
     pub fill_char: char,
+    /// use uppercase hex digits
+    pub uppercase: bool,
+    /// most bytes shown, longer data is cut short and followed by its total length
+    pub max_bytes: Option<usize>,
+    /// function signatures shown instead of data starting with their 4-byte selector
+    pub selectors: Option<HashMap<[u8; 4], String>>,
+    /// event signatures shown instead of their 32-byte topic hashes
+    pub topics: Option<HashMap<[u8; 32], String>>,
 }
 
 impl Default for BinaryFormat {
@@ -27,6 +38,10 @@ impl Default for BinaryFormat {
             max_width: usize::MAX,
             align: BinaryAlign::Right,
             fill_char: ' ',
+            uppercase: false,
+            max_bytes: None,
+            selectors: None,
+            topics: None,
         }
     }
 }
@@ -55,9 +70,16 @@ impl BinaryFormat {
 
     /// format binary data
     pub fn format<T: AsRef<[u8]>>(&self, data: T) -> Result<String, FormatError> {
-        let s = bytes_to_hex(data);
-
-        let (total_length, prefix) = if self.prefix { (s.len() + 2, "0x") } else { (s.len(), "") };
+        let bytes = data.as_ref();
+        let (s, prefix) = match (self.signature(bytes), self.max_bytes) {
+            (Some(signature), _) => (signature.clone(), ""),
+            (None, Some(max_bytes)) if bytes.len() > max_bytes => {
+                let hex = format_bytes_hex(&bytes[..max_bytes], false, self.uppercase);
+                (format!("{}…({} bytes)", hex, bytes.len()), self.prefix_str())
+            }
+            (None, _) => (format_bytes_hex(bytes, false, self.uppercase), self.prefix_str()),
+        };
+        let total_length = s.width() + prefix.len();
 
         if total_length < self.min_width {
             let pad = self.fill_char.to_string().repeat(self.min_width - total_length);
@@ -73,8 +95,8 @@ impl BinaryFormat {
                     "min_width too small for clipping".to_string(),
                 ));
             };
-            match s.get(0..(self.max_width - 3 - prefix.len())) {
-                Some(s) => Ok(format!("{}{}...", prefix, s)),
+            match self.max_width.checked_sub(3 + prefix.len()) {
+                Some(n_columns) => Ok(format!("{}{}...", prefix, s.unicode_truncate(n_columns).0)),
                 None => {
                     Err(FormatError::InvalidFormat("could not take slice of string".to_string()))
                 }
@@ -83,14 +105,46 @@ impl BinaryFormat {
             Ok(format!("{}{}", prefix, s))
         }
     }
+
+    /// prefix of hex data
+    fn prefix_str(&self) -> &'static str {
+        if self.prefix {
+            "0x"
+        } else {
+            ""
+        }
+    }
+
+    /// signature of event whose topic is data, or of function whose selector begins data
+    ///
+    /// topics are whole values, so longer data starting with a topic, such as calldata or log
+    /// data, is not an event
+    fn signature(&self, bytes: &[u8]) -> Option<&String> {
+        let topic = match (self.topics.as_ref(), bytes.len()) {
+            (Some(topics), 32) => lookup_signature(topics, bytes),
+            _ => None,
+        };
+        topic.or_else(|| lookup_signature(self.selectors.as_ref()?, bytes))
+    }
 }
 
-/// convert bytes to raw hex string
-fn bytes_to_hex<T: AsRef<[u8]>>(data: T) -> String {
-    let hex_chars = "0123456789abcdef".as_bytes();
-    let bytes = data.as_ref();
+/// signature whose N-byte hash begins bytes
+fn lookup_signature<'a, const N: usize>(
+    signatures: &'a HashMap<[u8; N], String>,
+    bytes: &[u8],
+) -> Option<&'a String> {
+    let hash: [u8; N] = bytes.get(..N)?.try_into().ok()?;
+    signatures.get(&hash)
+}
 
-    let mut hex_string = String::with_capacity(bytes.len() * 2);
+/// encode bytes as hex, optionally with a `0x` prefix and uppercase digits
+pub fn format_bytes_hex(bytes: &[u8], prefix: bool, uppercase: bool) -> String {
+    let hex_chars = if uppercase { "0123456789ABCDEF" } else { "0123456789abcdef" }.as_bytes();
+
+    let mut hex_string = String::with_capacity(bytes.len() * 2 + 2);
+    if prefix {
+        hex_string.push_str("0x");
+    }
 
     for &byte in bytes {
         hex_string.push(hex_chars[(byte >> 4) as usize] as char);
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod types_tests {
-    use crate::binary_format::BinaryFormat;
+    use crate::binary_format::{format_bytes_hex, BinaryFormat};
+    use std::collections::HashMap;
 
     #[test]
     fn default() {
@@ -18,6 +19,92 @@ mod types_tests {
         assert_eq!(b.format(vec![2, 4, 6, 8]).unwrap(), "02040608");
     }
 
+    #[test]
+    fn bytes_hex() {
+        assert_eq!(format_bytes_hex(&[], false, false), "");
+        assert_eq!(format_bytes_hex(&[], true, false), "0x");
+        assert_eq!(format_bytes_hex(&[0x0a, 0xbc], false, false), "0abc");
+        assert_eq!(format_bytes_hex(&[0x0a, 0xbc], true, false), "0x0abc");
+        assert_eq!(format_bytes_hex(&[0x0a, 0xbc], false, true), "0ABC");
+        assert_eq!(format_bytes_hex(&[0x0a, 0xbc], true, true), "0x0ABC");
+    }
+
+    #[test]
+    fn uppercase() {
+        let b = BinaryFormat::new().uppercase();
+        assert_eq!(b.format(vec![0xab, 0xcd]).unwrap(), "0xABCD");
+        assert_eq!(b.lowercase().format(vec![0xab, 0xcd]).unwrap(), "0xabcd");
+    }
+
+    #[test]
+    fn max_bytes() {
+        let b = BinaryFormat::new().max_bytes(4);
+        assert_eq!(b.format(vec![]).unwrap(), "0x");
+        assert_eq!(b.format(vec![1, 2, 3]).unwrap(), "0x010203");
+
+        // exactly max_bytes bytes has no note
+        assert_eq!(b.format(vec![1, 2, 3, 4]).unwrap(), "0x01020304");
+
+        let long: Vec<u8> = (0..=255).cycle().take(1234).collect();
+        assert_eq!(b.format(long).unwrap(), "0x00010203…(1234 bytes)");
+        let b = b.no_prefix().uppercase();
+        assert_eq!(b.format(vec![0xab; 5]).unwrap(), "ABABABAB…(5 bytes)");
+
+        // character widths still apply to the note
+        let b = BinaryFormat::new().max_bytes(2).max_width(12);
+        assert_eq!(b.format(vec![0xab; 5]).unwrap(), "0xabab…(5...");
+    }
+
+    #[test]
+    fn decode_selector() {
+        let signatures: HashMap<[u8; 4], String> =
+            [([0xa9, 0x05, 0x9c, 0xbb], "transfer(address,uint256)".to_string())].into();
+        let b = BinaryFormat::new().decode_selector(&signatures);
+        let mut calldata = vec![0xa9, 0x05, 0x9c, 0xbb];
+        assert_eq!(b.format(&calldata).unwrap(), "transfer(address,uint256)");
+        calldata.extend([0u8; 64]);
+        assert_eq!(b.format(&calldata).unwrap(), "transfer(address,uint256)");
+
+        // unknown selectors and short data fall back to hex
+        assert_eq!(b.format(vec![0x12, 0x34, 0x56, 0x78, 0x9a]).unwrap(), "0x123456789a");
+        assert_eq!(b.format(vec![0xa9, 0x05, 0x9c]).unwrap(), "0xa9059c");
+
+        // widths still apply to signatures
+        let b = b.max_width(11);
+        assert_eq!(b.format(&calldata).unwrap(), "transfer...");
+    }
+
+    #[test]
+    fn decode_topic() {
+        let transfer: [u8; 32] = [
+            0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37,
+            0x8d, 0xaa, 0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d,
+            0xf5, 0x23, 0xb3, 0xef,
+        ];
+        let signatures: HashMap<[u8; 32], String> =
+            [(transfer, "Transfer(address,address,uint256)".to_string())].into();
+        let b = BinaryFormat::new().decode_topic(&signatures);
+        assert_eq!(b.format(transfer).unwrap(), "Transfer(address,address,uint256)");
+
+        // unknown topics render as hex, which may be truncated
+        let mut unknown = transfer;
+        unknown[31] = 0;
+        let expected = format!("0x{}00", format_bytes_hex(&transfer[..31], false, false));
+        assert_eq!(b.format(unknown).unwrap(), expected);
+        let b = b.max_bytes(4);
+        assert_eq!(b.format(unknown).unwrap(), "0xddf252ad…(32 bytes)");
+        assert_eq!(b.format(transfer).unwrap(), "Transfer(address,address,uint256)");
+
+        // longer data starting with a topic is not an event
+        let mut data = transfer.to_vec();
+        data.extend([0u8; 4]);
+        let b = BinaryFormat::new().decode_topic(&signatures);
+        assert_eq!(
+            b.format(&data).unwrap(),
+            format!("0x{}", format_bytes_hex(&data, false, false))
+        );
+    }
+
     // alignment tests
 
     #[test]
@@ -4,6 +4,12 @@ mod tests;
 
 use crate::FormatError;
 
+/// filled part of a ratio bar
+const BAR_FILLED: char = '█';
+
+/// empty part of a ratio bar
+const BAR_EMPTY: char = '░';
+
 /// bool format specification
 #[derive(Debug, Clone)]
 pub struct BoolFormat {
@@ -82,4 +88,38 @@ impl BoolFormat {
             Ok(s.to_string())
         }
     }
+
+    /// bar of given width showing the share of true values, such as `████░░ 66%`
+    ///
+    /// nulls are excluded from the share and noted separately, such as `██░░ 50% (1 null)`, and
+    /// the share is rounded down so that 100% means that all values are true
+    pub fn ratio_bar<I: IntoIterator<Item = Option<bool>>>(values: I, width: usize) -> String {
+        let (mut n_true, mut n_false, mut n_null) = (0, 0, 0);
+        for value in values {
+            match value {
+                Some(true) => n_true += 1,
+                Some(false) => n_false += 1,
+                None => n_null += 1,
+            }
+        }
+        let n_values = n_true + n_false;
+        let mut bar = match n_values {
+            0 => format!("{} n/a", BAR_EMPTY.to_string().repeat(width)),
+            _ => {
+                let n_filled = n_true * width / n_values;
+                format!(
+                    "{}{} {}%",
+                    BAR_FILLED.to_string().repeat(n_filled),
+                    BAR_EMPTY.to_string().repeat(width - n_filled),
+                    n_true * 100 / n_values
+                )
+            }
+        };
+        match n_null {
+            0 => {}
+            1 => bar.push_str(" (1 null)"),
+            n => bar.push_str(format!(" ({} nulls)", n).as_str()),
+        }
+        bar
+    }
 }
@@ -2,6 +2,20 @@
 mod types_tests {
     use crate::bool_format::BoolFormat;
 
+    #[test]
+    fn ratio_bar() {
+        let bar = |values: &[Option<bool>]| BoolFormat::ratio_bar(values.iter().copied(), 6);
+        assert_eq!(bar(&[Some(false), Some(false)]), "░░░░░░ 0%");
+        assert_eq!(bar(&[Some(true), Some(false)]), "███░░░ 50%");
+        assert_eq!(bar(&[Some(true), Some(true), Some(true)]), "██████ 100%");
+        assert_eq!(bar(&[Some(true), Some(true), Some(false)]), "████░░ 66%");
+
+        // nulls are left out of the share
+        assert_eq!(bar(&[Some(true), None, Some(false)]), "███░░░ 50% (1 null)");
+        assert_eq!(bar(&[None, None]), "░░░░░░ n/a (2 nulls)");
+        assert_eq!(bar(&[]), "░░░░░░ n/a");
+    }
+
     #[test]
     fn default() {
         let b = BoolFormat::default();
@@ -1,4 +1,7 @@
-use super::types::{FormatType, NumberAlign, NumberFormat, Sign, Timezone};
+use super::types::{
+    FormatType, NegativeColor, NegativeColorScope, NumberAlign, NumberFormat, OverflowMode,
+    PercentMode, PrecisionMode, Sign, Timezone,
+};
 
 impl NumberFormat {
     /// create new number format
@@ -8,9 +11,11 @@ impl NumberFormat {
 
     // zero padding
 
-    /// add zero padding
+    /// add zero padding, placing zeros between the sign and the digits
     pub fn zero_padding(mut self) -> NumberFormat {
         self.zero_padding = true;
+        self.fill = '0';
+        self.align = NumberAlign::SignedRight;
         self
     }
 
@@ -30,6 +35,12 @@ impl NumberFormat {
 
     // align
 
+    /// set alignment
+    pub fn align(mut self, align: NumberAlign) -> NumberFormat {
+        self.align = align;
+        self
+    }
+
     /// left align
     pub fn left_align(mut self) -> NumberFormat {
         self.align = NumberAlign::Left;
@@ -56,6 +67,12 @@ impl NumberFormat {
 
     // sign
 
+    /// set sign
+    pub fn sign(mut self, sign: Sign) -> NumberFormat {
+        self.sign = sign;
+        self
+    }
+
     /// always add sign
     pub fn unsigned(mut self) -> NumberFormat {
         self.sign = Sign::OnlyNegative;
@@ -89,21 +106,24 @@ impl NumberFormat {
     }
 
     // width
+    //
+    // min_width is the width that output is padded up to (the `width` of a format pattern),
+    // max_width is the width beyond which output is clipped. width sets both at once.
 
-    /// set width
+    /// set width, padding output up to and clipping output beyond this width
     pub fn width(mut self, width: usize) -> NumberFormat {
         self.min_width = width;
         self.max_width = width;
         self
     }
 
-    /// set min_width
+    /// set min_width, padding output up to this width
     pub fn min_width(mut self, min_width: usize) -> NumberFormat {
         self.min_width = min_width;
         self
     }
 
-    /// set max_width
+    /// set max_width, clipping output beyond this width
     pub fn max_width(mut self, max_width: usize) -> NumberFormat {
         self.max_width = max_width;
         self
@@ -136,6 +156,14 @@ impl NumberFormat {
         }
     }
 
+    // overflow
+
+    /// set how values wider than max_width are rendered
+    pub fn overflow(mut self, overflow: OverflowMode) -> NumberFormat {
+        self.overflow = overflow;
+        self
+    }
+
     // commas
 
     /// show commas
@@ -150,6 +178,18 @@ impl NumberFormat {
         self
     }
 
+    /// set number of digits between commas
+    pub fn group_size(mut self, group_size: usize) -> NumberFormat {
+        self.group_size = group_size;
+        self
+    }
+
+    /// only show commas for numbers with at least min_digits integer digits
+    pub fn min_group_digits(mut self, min_digits: usize) -> NumberFormat {
+        self.min_group_digits = min_digits;
+        self
+    }
+
     // precision
 
     /// set precision
@@ -158,6 +198,58 @@ impl NumberFormat {
         self
     }
 
+    // scale
+
+    /// set multiplier applied to input before formatting
+    pub fn scale(mut self, scale: f64) -> NumberFormat {
+        self.scale = scale;
+        self
+    }
+
+    // prefix
+
+    /// set text placed between the sign and the digits, such as a currency
+    pub fn prefix<S: AsRef<str>>(mut self, prefix: S) -> NumberFormat {
+        self.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    // suffix
+
+    /// set text appended to the number, such as a unit
+    pub fn suffix<S: AsRef<str>>(mut self, suffix: S) -> NumberFormat {
+        self.suffix = suffix.as_ref().to_string();
+        self
+    }
+
+    // nan
+
+    /// render NaN values as null_repr
+    pub fn nan_as_null(mut self) -> NumberFormat {
+        self.nan_as_null = true;
+        self
+    }
+
+    /// render NaN values as `NaN`, distinct from nulls
+    pub fn no_nan_as_null(mut self) -> NumberFormat {
+        self.nan_as_null = false;
+        self
+    }
+
+    /// set text shown in place of NaN values
+    pub fn null_repr<S: AsRef<str>>(mut self, null_repr: S) -> NumberFormat {
+        self.null_repr = null_repr.as_ref().to_string();
+        self
+    }
+
+    // negative color
+
+    /// color negative numbers, or only their sign, with ansi foreground color code
+    pub fn negative_color(mut self, code: u8, scope: NegativeColorScope) -> NumberFormat {
+        self.negative_color = Some(NegativeColor { code, scope });
+        self
+    }
+
     // timezone
 
     /// use local timezone
@@ -180,7 +272,7 @@ impl NumberFormat {
         self
     }
 
-    /// format as SI (order of magnitude)
+    /// format as SI (order of magnitude), with precision counting significant digits
     pub fn si(mut self) -> NumberFormat {
         self.format_type = FormatType::SI;
         self
@@ -192,6 +284,24 @@ impl NumberFormat {
         self
     }
 
+    /// set character between the integer and fractional digits, e.g. `,` for `1.234,5`
+    pub fn decimal(mut self, decimal_char: char) -> NumberFormat {
+        self.decimal_char = Some(decimal_char);
+        self
+    }
+
+    /// set whether precision counts decimals or significant digits
+    pub fn precision_mode(mut self, precision_mode: PrecisionMode) -> NumberFormat {
+        self.precision_mode = precision_mode;
+        self
+    }
+
+    /// set whether percentages are given as ratios or as percentage points
+    pub fn percent_mode(mut self, percent_mode: PercentMode) -> NumberFormat {
+        self.percent_mode = percent_mode;
+        self
+    }
+
     /// format as binary
     pub fn binary(mut self) -> NumberFormat {
         self.format_type = FormatType::Binary;
@@ -206,7 +316,7 @@ impl NumberFormat {
 
     /// format as hex
     pub fn hex(mut self) -> NumberFormat {
-        self.format_type = FormatType::Octal;
+        self.format_type = FormatType::Hex;
         self
     }
 
@@ -228,6 +338,24 @@ impl NumberFormat {
         self
     }
 
+    /// format as multiplier, e.g. `2×`
+    pub fn multiplier(mut self) -> NumberFormat {
+        self.format_type = FormatType::Multiplier;
+        self
+    }
+
+    /// format seconds as duration, e.g. `01:02:05`
+    pub fn duration(mut self) -> NumberFormat {
+        self.format_type = FormatType::Duration;
+        self
+    }
+
+    /// format seconds as short duration, e.g. `1h 2m`
+    pub fn short_duration(mut self) -> NumberFormat {
+        self.format_type = FormatType::DurationShort;
+        self
+    }
+
     /// set format type
     pub fn format_type(mut self, format_type: &FormatType) -> NumberFormat {
         self.format_type = format_type.clone();
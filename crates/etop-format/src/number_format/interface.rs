@@ -1,6 +1,9 @@
 use super::{
     process,
-    types::{FormatType, NumberAlign, NumberFormat, Sign, Timezone, DECIMAL_CHAR, PREFIXES},
+    types::{
+        FormatType, NegativeColorScope, NumberAlign, NumberFormat, OverflowMode, PercentMode,
+        PrecisionMode, Sign, Timezone, DECIMAL_CHAR, GROUP_DELIMITER_CHAR, PREFIXES,
+    },
 };
 use crate::FormatError;
 use chrono::{Local, NaiveDateTime, TimeZone, Utc};
@@ -15,24 +18,118 @@ pub fn format<P: TryInto<NumberFormat, Error = FormatError>, T: Into<f64>>(
     number_format(&pattern.try_into()?, input)
 }
 
+/// Format a pair of numbers as a ratio `a:b`. Integer pairs are reduced by their greatest
+/// common divisor, so `format_ratio(6, 4)` gives `"3:2"`.
+pub fn format_ratio<A: Into<f64>, B: Into<f64>>(a: A, b: B) -> String {
+    let (a, b): (f64, f64) = (a.into(), b.into());
+    let is_integer = |x: f64| x.fract() == 0.0 && x.abs() < u64::MAX as f64;
+    if is_integer(a) && is_integer(b) {
+        let divisor = process::gcd(a.abs() as u64, b.abs() as u64).max(1) as f64;
+        format!("{}:{}", a / divisor, b / divisor)
+    } else {
+        format!("{}:{}", a, b)
+    }
+}
+
+/// Format a duration in seconds, as a clock `HH:MM:SS` or, if `short`, as its two largest units
+/// such as `1h 2m`. Negative durations keep their sign, e.g. `-00:01:30` or `-1m 30s`.
+pub fn format_duration(seconds: f64, short: bool) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    if short && seconds < 1.0 && seconds > 0.0 {
+        return format!("{}{}ms", sign, (seconds * 1000.0).round() as u64)
+    }
+    let total = seconds.round() as u64;
+    if !short {
+        return format!("{}{:02}:{:02}:{:02}", sign, total / 3600, total / 60 % 60, total % 60)
+    }
+    let units = [("d", total / 86400), ("h", total / 3600 % 24), ("m", total / 60 % 60)];
+    let parts: Vec<String> = units
+        .into_iter()
+        .chain([("s", total % 60)])
+        .skip_while(|(unit, n)| *n == 0 && *unit != "s")
+        .take(2)
+        .filter(|(_, n)| *n > 0)
+        .map(|(unit, n)| format!("{}{}", n, unit))
+        .collect();
+    match parts.is_empty() {
+        true => "0s".to_string(),
+        false => format!("{}{}", sign, parts.join(" ")),
+    }
+}
+
+/// Format a number and then enforce width bounds: output narrower than `min_width` is padded,
+/// output wider than `max_width` is clipped.
 pub(crate) fn number_format<T: Into<f64>>(
     number_format: &NumberFormat,
     input: T,
 ) -> Result<String, FormatError> {
-    let input_f64: f64 = input.into();
+    let input: f64 = input.into();
+    if input.is_nan() && number_format.nan_as_null {
+        return process::apply_width_bounds(number_format, number_format.null_repr.clone())
+    }
+    let mut formatted = format_value(number_format, input)?;
+    if process::visible_width(&formatted) > number_format.max_width &&
+        number_format.format_type == FormatType::SI
+    {
+        // drop significant digits until the value fits, e.g. `999.999k` to `1M`, before overflow
+        for precision in (1..number_format.precision).rev() {
+            formatted = format_value(&number_format.clone().precision(precision), input)?;
+            if process::visible_width(&formatted) <= number_format.max_width {
+                break
+            }
+        }
+    }
+    if process::visible_width(&formatted) > number_format.max_width &&
+        number_format.overflow == OverflowMode::Scientific
+    {
+        // retry in scientific notation, dropping precision until the value fits
+        let exponent = NumberFormat {
+            format_type: FormatType::Exponent,
+            commas: false,
+            ..number_format.clone()
+        };
+        for precision in (0..=number_format.precision).rev() {
+            let formatted = format_value(&exponent.clone().precision(precision), input)?;
+            if process::visible_width(&formatted) <= number_format.max_width {
+                return process::apply_width_bounds(number_format, formatted)
+            }
+        }
+    }
+    process::apply_width_bounds(number_format, formatted)
+}
+
+fn format_value(number_format: &NumberFormat, input: f64) -> Result<String, FormatError> {
+    let input_f64: f64 = input * number_format.scale;
+    let with_suffix =
+        |value: String| format!("{}{}{}", number_format.prefix, value, number_format.suffix);
+    let decimal_char = number_format.decimal_char.unwrap_or(DECIMAL_CHAR);
+    let with_decimal_char = |value: String| match decimal_char {
+        DECIMAL_CHAR => value,
+        c => value.replacen(DECIMAL_CHAR, c.to_string().as_str(), 1),
+    };
+    let fixed_point = |value: f64| match number_format.precision_mode {
+        PrecisionMode::Decimals => format!("{:.1$}", value, number_format.precision),
+        PrecisionMode::Significant => process::format_significant(value, number_format.precision),
+    };
     let mut value_is_negative: bool = input_f64.is_sign_negative();
 
-    let mut decimal_part = String::new();
     let mut si_prefix_exponent: &str = "";
     let unit_of_measurement: &str = match number_format.format_type {
         FormatType::Percentage => "%",
+        FormatType::Multiplier => "×",
         _ => "",
     };
 
-    let mut value = match number_format.format_type {
+    let value = match number_format.format_type {
         FormatType::Percentage => {
-            format!("{:.1$}", input_f64.abs() * 100_f64, number_format.precision)
+            let points = match number_format.percent_mode {
+                PercentMode::Ratio => input_f64.abs() * 100_f64,
+                PercentMode::Points => input_f64.abs(),
+            };
+            fixed_point(points)
         }
+        FormatType::Multiplier => process::trim_trailing_zeros(fixed_point(input_f64.abs())),
         FormatType::Binary => format!("{:#b}", input_f64.abs() as i64)[2..].into(),
         FormatType::Octal | FormatType::OctalUppercase => {
             format!("{:#o}", input_f64.abs() as i64)[2..].into()
@@ -67,12 +164,16 @@ pub(crate) fn number_format<T: Into<f64>>(
         }
         FormatType::IntegerOrderOfMagnitude => {
             if input_f64 < -1.0 {
-                return Ok("neg".to_string());
+                return Ok(with_suffix("neg".to_string()));
             } else if input_f64 > 900_000_000_000_000_000.0 {
-                return Ok("big".to_string());
+                return Ok(with_suffix("big".to_string()));
             } else {
                 let (prefix, prevalue) = match input_f64 {
-                    value if value <= 1_000.0 => return Ok(input_f64.round().to_string()),
+                    value if value <= 1_000.0 => {
+                        return Ok(with_suffix(process::drop_negative_zero(
+                            input_f64.round().to_string(),
+                        )))
+                    }
                     value if value <= 9_999.0 => ("", value),
                     value if value < 1_000_000.0 => ("K", value / 1_000.0),
                     value if value < 1_000_000_000.0 => ("M", value / 1_000_000.0),
@@ -84,29 +185,16 @@ pub(crate) fn number_format<T: Into<f64>>(
                     _ => return Err(FormatError::InvalidFormat("number too big".to_string())),
                 };
 
-                let result = match number_format.precision {
-                    0 => format!("{:.0}{}", prevalue, prefix),
-                    1 => format!("{:.1}{}", prevalue, prefix),
-                    2 => format!("{:.2}{}", prevalue, prefix),
-                    3 => format!("{:.3}{}", prevalue, prefix),
-                    4 => format!("{:.4}{}", prevalue, prefix),
-                    5 => format!("{:.5}{}", prevalue, prefix),
-                    6 => format!("{:.6}{}", prevalue, prefix),
-                    7 => format!("{:.7}{}", prevalue, prefix),
-                    8 => format!("{:.8}{}", prevalue, prefix),
-                    9 => format!("{:.9}{}", prevalue, prefix),
-                    10 => format!("{:.10}{}", prevalue, prefix),
-                    _ => return Err(FormatError::InvalidFormat("invalid precision".to_string())),
-                };
+                let result = format!("{:.2$}{}", prevalue, prefix, number_format.precision);
 
-                return Ok(result);
+                return Ok(with_suffix(with_decimal_char(process::drop_negative_zero(result))));
             }
         }
         FormatType::FloatOrderOfMagnitude => {
             if input_f64 < -1.0 {
-                return Ok("neg".to_string());
+                return Ok(with_suffix("neg".to_string()));
             } else if input_f64 > 900_000_000_000_000_000.0 {
-                return Ok("big".to_string());
+                return Ok(with_suffix("big".to_string()));
             } else {
                 let (prefix, prevalue) = match input_f64 {
                     value if value <= 1_000.0 => ("", value),
@@ -121,45 +209,33 @@ pub(crate) fn number_format<T: Into<f64>>(
                     _ => return Err(FormatError::InvalidFormat("number too big".to_string())),
                 };
 
-                let result = match number_format.precision {
-                    0 => format!("{:.0}{}", prevalue, prefix),
-                    1 => format!("{:.1}{}", prevalue, prefix),
-                    2 => format!("{:.2}{}", prevalue, prefix),
-                    3 => format!("{:.3}{}", prevalue, prefix),
-                    4 => format!("{:.4}{}", prevalue, prefix),
-                    5 => format!("{:.5}{}", prevalue, prefix),
-                    6 => format!("{:.6}{}", prevalue, prefix),
-                    7 => format!("{:.7}{}", prevalue, prefix),
-                    8 => format!("{:.8}{}", prevalue, prefix),
-                    9 => format!("{:.9}{}", prevalue, prefix),
-                    10 => format!("{:.10}{}", prevalue, prefix),
-                    _ => return Err(FormatError::InvalidFormat("invalid precision".to_string())),
-                };
+                let result = format!("{:.2$}{}", prevalue, prefix, number_format.precision);
 
-                return Ok(result);
+                return Ok(with_suffix(with_decimal_char(process::drop_negative_zero(result))));
             }
         }
+        FormatType::Duration => return Ok(with_suffix(format_duration(input_f64, false))),
+        FormatType::DurationShort => return Ok(with_suffix(format_duration(input_f64, true))),
         FormatType::TimestampPretty => {
             let datetime = NaiveDateTime::from_timestamp_opt(input_f64 as i64, 0)
                 .ok_or(FormatError::InvalidFormat("could not get timestamp".to_string()))?;
-            return match number_format.timezone {
+            let formatted = match number_format.timezone {
                 Timezone::Utc => {
-                    Ok(Utc.from_utc_datetime(&datetime).format("%Y-%m-%d %H:%M:%S").to_string())
+                    Utc.from_utc_datetime(&datetime).format("%Y-%m-%d %H:%M:%S").to_string()
                 }
                 Timezone::Local => {
-                    Ok(Local.from_utc_datetime(&datetime).format("%Y-%m-%d %H:%M:%S").to_string())
+                    Local.from_utc_datetime(&datetime).format("%Y-%m-%d %H:%M:%S").to_string()
                 }
             };
+            return Ok(with_suffix(formatted));
         }
-        _ => format!("{:.1$}", input_f64.abs(), number_format.precision),
+        _ => fixed_point(input_f64.abs()),
     };
 
-    // If a negative value rounds to zero after formatting, and no explicit positive sign is
-    // requested, hide the sign.
-    if number_format.format_type != FormatType::Hex &&
-        number_format.format_type != FormatType::HexUppercase &&
-        value_is_negative &&
-        value.parse::<f64>() == Ok(0_f64) &&
+    // If a negative value (including -0.0) rounds to zero after formatting, and no explicit
+    // positive sign is requested, hide the sign.
+    if value_is_negative &&
+        process::is_zero_magnitude(&value) &&
         (number_format.sign != Sign::Always)
     {
         value_is_negative = false;
@@ -179,43 +255,78 @@ pub(crate) fn number_format<T: Into<f64>>(
         false => "",
     };
 
-    // Split the integer part of the value for grouping purposes and attach the decimal part as
-    // suffix.
-    for (i, c) in value.chars().enumerate() {
-        if "0123456789".find(c).is_none() {
-            decimal_part = value[i..].to_owned();
-            value = value[..i].to_owned();
-            break;
-        }
-    }
+    // Split the integer part of the value for grouping purposes and attach the decimal part and
+    // exponent as suffix.
+    let value = with_decimal_char(value);
+    let exponent = match number_format.format_type {
+        FormatType::Exponent => Some('e'),
+        FormatType::ExponentUppercase => Some('E'),
+        _ => None,
+    };
+    let integer_end = process::integer_part_end(&value, decimal_char, exponent);
+    let decimal_part = value[integer_end..].to_owned();
+    let mut value = value[..integer_end].to_owned();
 
     // Compute the prefix and suffix.
-    let prefix = format!("{}{}", sign_prefix, leading_part);
-    let suffix = format!("{}{}{}", decimal_part, si_prefix_exponent, unit_of_measurement);
+    let prefix = format!("{}{}{}", sign_prefix, number_format.prefix, leading_part);
+    let suffix = format!(
+        "{}{}{}{}",
+        decimal_part, si_prefix_exponent, unit_of_measurement, number_format.suffix
+    );
 
     // If should group and filling character is different than "0",
     // group digits before applying padding.
     if number_format.commas && !number_format.zero_padding {
-        value = process::group_value(&value, 0)
+        value = process::group_value(
+            &value,
+            0,
+            number_format.group_size,
+            number_format.min_group_digits,
+        )
     }
 
     // Compute the padding.
-    let length = prefix.len() + value.to_string().len() + suffix.len();
-    let mut padding = if length < number_format.min_width {
-        vec![number_format.fill.to_string(); number_format.min_width - length].join("")
-    } else {
-        "".to_owned()
-    };
+    let length = prefix.chars().count() + value.chars().count() + suffix.chars().count();
+    let mut padding =
+        number_format.fill.to_string().repeat(number_format.min_width.saturating_sub(length));
 
     // If "0" is the filling character, grouping is applied after computing padding.
     if number_format.commas && number_format.zero_padding {
         value = process::group_value(
             format!("{}{}", &padding, value).as_str(),
-            if !padding.is_empty() { number_format.min_width - suffix.len() } else { 0 },
+            if !padding.is_empty() {
+                number_format.min_width.saturating_sub(suffix.len())
+            } else {
+                0
+            },
+            number_format.group_size,
+            number_format.min_group_digits,
         );
         padding = "".to_owned();
     };
 
+    // Group with `.` where `,` is the decimal character.
+    if decimal_char == GROUP_DELIMITER_CHAR {
+        value = value.replace(GROUP_DELIMITER_CHAR, DECIMAL_CHAR.to_string().as_str());
+    }
+
+    // Color negative numbers once padding is computed, so that color codes take no width.
+    let (prefix, suffix) = match number_format.negative_color {
+        Some(color) if value_is_negative => {
+            let (start, end) = (format!("\x1b[{}m", color.code), "\x1b[0m");
+            match color.scope {
+                NegativeColorScope::Whole => {
+                    (format!("{}{}", start, prefix), format!("{}{}", suffix, end))
+                }
+                NegativeColorScope::SignOnly => {
+                    let rest = format!("{}{}", number_format.prefix, leading_part);
+                    (format!("{}{}{}{}", start, sign_prefix, end, rest), suffix)
+                }
+            }
+        }
+        _ => (prefix, suffix),
+    };
+
     let formatted = match number_format.align {
         NumberAlign::Left => format!("{}{}{}{}", prefix, value, suffix, padding),
         NumberAlign::SignedRight => format!("{}{}{}{}", prefix, padding, value, suffix),
@@ -230,18 +341,5 @@ pub(crate) fn number_format<T: Into<f64>>(
         ),
     };
 
-    if formatted.len() > number_format.max_width {
-        if number_format.max_width < 3 {
-            Err(FormatError::InvalidFormat("min_width too small for clipping".to_string()))
-        } else {
-            match formatted.get(0..(number_format.max_width - 3)) {
-                Some(s) => Ok(format!("{}...", s)),
-                None => {
-                    Err(FormatError::InvalidFormat("could not take slice of string".to_string()))
-                }
-            }
-        }
-    } else {
-        Ok(formatted)
-    }
+    Ok(formatted)
 }
@@ -2,8 +2,13 @@ mod builder;
 /// public interface for formatting
 pub mod interface;
 mod process;
+pub(crate) use process::group_value;
 mod str_convert;
 mod types;
+pub(crate) use types::{ORDER_OF_MAGNITUDE_SUFFIXES, PREFIXES};
 
-pub use interface::format;
-pub use types::{FormatType, NumberAlign, NumberFormat, Sign};
+pub use interface::{format, format_duration, format_ratio};
+pub use types::{
+    FormatType, NegativeColor, NegativeColorScope, NumberAlign, NumberFormat, OverflowMode,
+    PercentMode, PrecisionMode, Sign,
+};
@@ -1,4 +1,6 @@
-use super::types::{Sign, DECIMAL_CHAR, GROUP_DELIMITER_CHAR};
+use super::types::{
+    NumberAlign, NumberFormat, OverflowMode, Sign, DECIMAL_CHAR, GROUP_DELIMITER_CHAR,
+};
 use crate::FormatError;
 use std::cmp::{max, min};
 
@@ -46,8 +48,11 @@ pub(crate) fn decompose_to_coefficient_and_exponent(
     };
 
     let exp_tokens: Vec<&str> = formatted_value.split('e').collect::<Vec<&str>>();
-    let exponent =
-        exp_tokens[1].parse().map_err(|_| FormatError::CouldNotDecomposeCoefficientExponent)?;
+    // non-finite values, such as `inf`, have no exponent
+    let exponent = exp_tokens
+        .get(1)
+        .and_then(|exponent| exponent.parse().ok())
+        .ok_or(FormatError::CouldNotDecomposeCoefficientExponent)?;
 
     // The `formatted_num` can have 2 shapes: `1e2` and `1.2e2`. Remove the decimal character
     // in case it's in the latter form.
@@ -63,6 +68,22 @@ pub(crate) fn decompose_to_coefficient_and_exponent(
     }
 }
 
+/// Format non-negative value in fixed point with the given number of significant digits, e.g.
+/// format_significant(1234.5, 3) returns "1230" and format_significant(0.0012345, 3) returns
+/// "0.00123".
+pub(crate) fn format_significant(value: f64, significant_digits: usize) -> String {
+    let significant_digits = significant_digits.max(1);
+    if value == 0.0 || !value.is_finite() {
+        return format!("{:.1$}", value, significant_digits - 1)
+    }
+    // round in exponent form, so that e.g. 9.996 to 3 digits carries over to 10.0
+    let rounded = format!("{:.1$e}", value, significant_digits - 1);
+    let exponent: i64 = rounded.split('e').nth(1).and_then(|e| e.parse().ok()).unwrap_or(0);
+    let value: f64 = rounded.parse().unwrap_or(value);
+    let decimals = (significant_digits as i64 - 1 - exponent).max(0) as usize;
+    format!("{:.1$}", value, decimals)
+}
+
 /// Compute the [SI prefix](https://en.wikipedia.org/wiki/Metric_prefix) of the number and scale it accordingly.
 pub(crate) fn format_si_prefix(
     value: f64,
@@ -85,20 +106,16 @@ pub(crate) fn format_si_prefix(
     } else {
         // less than 1 yocto
         let inner_precision = precision
-            .map(|p| (p as i32 - i.abs() as i32 - 1) as usize)
+            .map(|p| max(0, p as i32 - i.abs() as i32 - 1) as usize)
             .ok_or(FormatError::CouldNotDecomposeCoefficientExponent)?;
-        let coefficient = decompose_to_coefficient_and_exponent(
-            value,
-            precision.and(Some(max(0, inner_precision))),
-        )?
-        .0;
+        let coefficient = decompose_to_coefficient_and_exponent(value, Some(inner_precision))?.0;
         let coefficient =
             format!("0{}{}{}", DECIMAL_CHAR, "0".repeat(i.unsigned_abs()), coefficient);
         Ok((coefficient, prefix_exponent))
     }
 }
 
-/// Group digits using the `group_delimiter` character.
+/// Group digits using the `group_delimiter` character, `group_size` digits at a time.
 ///
 /// A width is going to be specified (>0) only when the formatted value should be filled in
 /// with "0" characters before the number itself (e.g. using a "020f" or "0=12f" pattern).
@@ -109,14 +126,24 @@ pub(crate) fn format_si_prefix(
 /// is not a valid representation.
 ///
 /// If width = 0, the result will group all passed digits without truncating any of them.
-pub(crate) fn group_value(value: &str, width: usize) -> String {
+///
+/// Values with fewer than `min_digits` digits, not counting leading zeros, are not grouped.
+pub(crate) fn group_value(
+    value: &str,
+    width: usize,
+    group_size: usize,
+    min_digits: usize,
+) -> String {
+    if value.trim_start_matches('0').chars().count() < min_digits {
+        return value.to_string()
+    }
     let mut reversed_chars: Vec<&[char]> = Vec::new();
     let input_chars: Vec<char> = value.chars().rev().collect();
     let separator: [char; 1] = [GROUP_DELIMITER_CHAR];
 
     // After the below loop, an input of "1234" is going to be
     // transformed into `vec![['4', '3', '2'], [','], ['1'], [',']]`.
-    for group in input_chars.chunks(3) {
+    for group in input_chars.chunks(group_size.max(1)) {
         reversed_chars.push(group);
         reversed_chars.push(&separator);
     }
@@ -141,6 +168,21 @@ pub(crate) fn group_value(value: &str, width: usize) -> String {
     }
 }
 
+/// Find the byte index where the integer part of a formatted value ends.
+///
+/// The value is split at the decimal character if present, otherwise at the exponent marker
+/// (e.g. 15e+03), so that grouping never reaches into the mantissa fraction or the exponent.
+/// Values with neither fall back to the first character that is not a decimal digit.
+pub(crate) fn integer_part_end(value: &str, decimal_char: char, exponent: Option<char>) -> usize {
+    match value.find(decimal_char) {
+        Some(index) => index,
+        None => match exponent.and_then(|marker| value.find(marker)) {
+            Some(index) => index,
+            None => value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len()),
+        },
+    }
+}
+
 /// Format the number using scientific notation. The exponent is always represented with
 /// the corresponding sign and at least 2 digits (e.g. 1e+01, 2.1e-02, 42.12e+210).
 ///
@@ -193,3 +235,97 @@ pub(crate) fn get_sign_prefix(is_negative: bool, format_sign: &Sign) -> &'static
         ""
     }
 }
+
+/// Whether a formatted magnitude represents zero, e.g. "0", "0.00", or "0.0e+00".
+///
+/// A negative value whose magnitude formats as zero should not display a negative sign.
+pub(crate) fn is_zero_magnitude(value: &str) -> bool {
+    let mantissa = value.split(['e', 'E']).next().unwrap_or_default();
+    !mantissa.is_empty() && mantissa.chars().all(|c| c == '0' || c == DECIMAL_CHAR)
+}
+
+/// Remove the negative sign of a formatted number that rounded to zero (e.g. "-0.00" -> "0.00").
+pub(crate) fn drop_negative_zero(formatted: String) -> String {
+    match formatted.strip_prefix('-') {
+        Some(magnitude) if is_zero_magnitude(magnitude) => magnitude.to_string(),
+        _ => formatted,
+    }
+}
+
+/// Remove trailing zeros after the decimal point, and the decimal point if nothing follows it
+/// (e.g. "1.500" -> "1.5", "2.00" -> "2").
+pub(crate) fn trim_trailing_zeros(value: String) -> String {
+    if !value.contains(DECIMAL_CHAR) {
+        return value
+    }
+    value.trim_end_matches('0').trim_end_matches(DECIMAL_CHAR).to_string()
+}
+
+/// Greatest common divisor, with `gcd(0, 0) == 0`.
+pub(crate) fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+/// Pad `formatted` with the fill char up to `min_width` and clip it beyond `max_width`, either
+/// with `...` or with hashes depending on the overflow mode. Widths count chars, so multi-byte
+/// units such as `µ` occupy one column.
+/// remove ansi color codes of the form `\x1b[...m`
+pub(crate) fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// number of chars of text shown on a terminal, not counting ansi color codes
+pub(crate) fn visible_width(text: &str) -> usize {
+    match text.contains('\x1b') {
+        true => strip_ansi(text).chars().count(),
+        false => text.chars().count(),
+    }
+}
+
+pub(crate) fn apply_width_bounds(
+    number_format: &NumberFormat,
+    formatted: String,
+) -> Result<String, FormatError> {
+    let length = visible_width(&formatted);
+    if length < number_format.min_width {
+        let n_pad = number_format.min_width - length;
+        let fill = |n: usize| number_format.fill.to_string().repeat(n);
+        let padded = match number_format.align {
+            NumberAlign::Left => format!("{}{}", formatted, fill(n_pad)),
+            NumberAlign::Center => {
+                format!("{}{}{}", fill(n_pad / 2), formatted, fill(n_pad - n_pad / 2))
+            }
+            NumberAlign::Right | NumberAlign::SignedRight => {
+                format!("{}{}", fill(n_pad), formatted)
+            }
+        };
+        Ok(padded)
+    } else if length > number_format.max_width && number_format.overflow != OverflowMode::Truncate {
+        Ok("#".repeat(number_format.max_width))
+    } else if length > number_format.max_width {
+        match number_format.max_width {
+            0 => Ok("".to_string()),
+            1 => Ok(".".to_string()),
+            2 => Ok("..".to_string()),
+            max_width => {
+                // color codes are dropped rather than clipped partway through
+                let clipped: String = strip_ansi(&formatted).chars().take(max_width - 3).collect();
+                Ok(format!("{}...", clipped))
+            }
+        }
+    } else {
+        Ok(formatted)
+    }
+}
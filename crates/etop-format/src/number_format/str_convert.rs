@@ -1,5 +1,8 @@
 use super::types::{
-    FormatType, NumberAlign, NumberFormat, Sign, DEFAULT_PRECISION, DEFAULT_TIMEZONE,
+    FormatType, NumberAlign, NumberFormat, Sign, DEFAULT_GROUP_SIZE, DEFAULT_MIN_GROUP_DIGITS,
+    DEFAULT_NAN_AS_NULL, DEFAULT_NULL_REPR, DEFAULT_OVERFLOW, DEFAULT_PERCENT_MODE,
+    DEFAULT_PRECISION, DEFAULT_PRECISION_MODE, DEFAULT_PREFIX, DEFAULT_SCALE, DEFAULT_SUFFIX,
+    DEFAULT_TIMEZONE,
 };
 use crate::FormatError;
 use regex::{Captures, Regex};
@@ -56,6 +59,71 @@ impl FromStr for FormatType {
     }
 }
 
+impl FormatType {
+    /// char of format type in a pattern string, None for types that have no pattern char
+    pub fn pattern_char(&self) -> Option<char> {
+        match self {
+            FormatType::Exponent => Some('e'),
+            FormatType::ExponentUppercase => Some('E'),
+            FormatType::FixedPoint => Some('f'),
+            FormatType::SI => Some('s'),
+            FormatType::Percentage => Some('%'),
+            FormatType::Binary => Some('b'),
+            FormatType::Octal => Some('o'),
+            FormatType::OctalUppercase => Some('O'),
+            FormatType::Decimal => Some('d'),
+            FormatType::Hex => Some('x'),
+            FormatType::HexUppercase => Some('X'),
+            _ => None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Reconstruct the canonical pattern string `[[fill]align][sign][#][0][width][,][.precision]
+    /// [type]` of this format, such that parsing the pattern gives back an equal format.
+    ///
+    /// Settings without pattern syntax (max_width, group_size, min_group_digits, scale, timezone,
+    /// overflow, precision_mode, decimal_char, percent_mode, prefix, suffix, negative_color, and
+    /// format types without a pattern char) are left out.
+    pub fn to_pattern(&self) -> String {
+        let mut pattern = String::new();
+        if !self.zero_padding && (self.fill != ' ' || self.align != NumberAlign::Right) {
+            if self.fill != ' ' {
+                pattern.push(self.fill);
+            }
+            pattern.push(match self.align {
+                NumberAlign::Left => '<',
+                NumberAlign::Right => '>',
+                NumberAlign::Center => '^',
+                NumberAlign::SignedRight => '=',
+            });
+        }
+        match self.sign {
+            Sign::OnlyNegative => {}
+            Sign::Always => pattern.push('+'),
+            Sign::SpaceOrDash => pattern.push(' '),
+        }
+        if self.type_prefix {
+            pattern.push('#');
+        }
+        if self.zero_padding {
+            pattern.push('0');
+        }
+        if self.min_width > 0 {
+            pattern.push_str(&self.min_width.to_string());
+        }
+        if self.commas {
+            pattern.push(',');
+        }
+        pattern.push_str(&format!(".{}", self.precision));
+        if let Some(c) = self.format_type.pattern_char() {
+            pattern.push(c);
+        }
+        pattern
+    }
+}
+
 impl TryFrom<&str> for NumberFormat {
     type Error = FormatError;
 
@@ -94,6 +162,12 @@ impl From<Captures<'_>> for NumberFormat {
             c.get(9).and_then(|s| s.as_str().parse().ok()).unwrap_or(FormatType::None);
 
         let timezone = DEFAULT_TIMEZONE;
+        let scale = DEFAULT_SCALE;
+        let overflow = DEFAULT_OVERFLOW;
+        let group_size = DEFAULT_GROUP_SIZE;
+        let min_group_digits = DEFAULT_MIN_GROUP_DIGITS;
+        let prefix = DEFAULT_PREFIX.to_string();
+        let suffix = DEFAULT_SUFFIX.to_string();
 
         let max_width = usize::MAX;
         let mut spec = Self {
@@ -105,9 +179,21 @@ impl From<Captures<'_>> for NumberFormat {
             min_width,
             max_width,
             commas,
+            group_size,
+            min_group_digits,
             precision,
             format_type,
             timezone,
+            scale,
+            overflow,
+            precision_mode: DEFAULT_PRECISION_MODE,
+            decimal_char: None,
+            percent_mode: DEFAULT_PERCENT_MODE,
+            prefix,
+            suffix,
+            negative_color: None,
+            null_repr: DEFAULT_NULL_REPR.to_string(),
+            nan_as_null: DEFAULT_NAN_AS_NULL,
         };
 
         // If zero fill is specified, padding goes after sign and before digits.
@@ -7,11 +7,14 @@ use crate::FormatError;
 pub(crate) const PREFIXES: [&str; 17] =
     ["y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y"];
 
+/// suffixes of order of magnitude formats
+pub(crate) const ORDER_OF_MAGNITUDE_SUFFIXES: [&str; 5] = ["K", "M", "B", "T", "Q"];
+
 pub(crate) const DECIMAL_CHAR: char = '.';
 pub(crate) const GROUP_DELIMITER_CHAR: char = ',';
 
 // default number format settings
-pub(crate) const DEFAULT_ZERO_PADDING: bool = true;
+pub(crate) const DEFAULT_ZERO_PADDING: bool = false;
 pub(crate) const DEFAULT_FILL: char = ' ';
 pub(crate) const DEFAULT_ALIGN: NumberAlign = NumberAlign::Right;
 pub(crate) const DEFAULT_SIGN: Sign = Sign::OnlyNegative;
@@ -19,12 +22,22 @@ pub(crate) const DEFAULT_TYPE_PREFIX: bool = false;
 pub(crate) const DEFAULT_MIN_WIDTH: usize = 0;
 pub(crate) const DEFAULT_MAX_WIDTH: usize = usize::MAX;
 pub(crate) const DEFAULT_COMMAS: bool = false;
+pub(crate) const DEFAULT_GROUP_SIZE: usize = 3;
+pub(crate) const DEFAULT_MIN_GROUP_DIGITS: usize = 0;
 pub(crate) const DEFAULT_PRECISION: usize = 6;
+pub(crate) const DEFAULT_PRECISION_MODE: PrecisionMode = PrecisionMode::Decimals;
 pub(crate) const DEFAULT_FORMAT_TYPE: FormatType = FormatType::None;
 pub(crate) const DEFAULT_TIMEZONE: Timezone = Timezone::Utc;
+pub(crate) const DEFAULT_SCALE: f64 = 1.0;
+pub(crate) const DEFAULT_OVERFLOW: OverflowMode = OverflowMode::Truncate;
+pub(crate) const DEFAULT_PERCENT_MODE: PercentMode = PercentMode::Ratio;
+pub(crate) const DEFAULT_PREFIX: &str = "";
+pub(crate) const DEFAULT_SUFFIX: &str = "";
+pub(crate) const DEFAULT_NULL_REPR: &str = "-";
+pub(crate) const DEFAULT_NAN_AS_NULL: bool = true;
 
 /// Represents a destructured specification of a provided format pattern string.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NumberFormat {
     /// zero padding
     pub zero_padding: bool,
@@ -36,47 +49,56 @@ pub struct NumberFormat {
     pub sign: Sign,
     /// type prefix
     pub type_prefix: bool,
-    /// min_width
+    /// min_width, output is padded up to this width
     pub min_width: usize,
-    /// max_width
+    /// max_width, output is clipped beyond this width
     pub max_width: usize,
     /// commas
     pub commas: bool,
-    /// decimals
+    /// number of digits between commas
+    pub group_size: usize,
+    /// fewest integer digits a number needs to be grouped, e.g. 5 to leave years ungrouped
+    pub min_group_digits: usize,
+    /// digits after the decimal point, or significant digits for SI format
     pub precision: usize,
+    /// whether precision counts decimals or significant digits of fixed point values
+    pub precision_mode: PrecisionMode,
+    /// character between the integer and fractional digits, None for `.`
+    ///
+    /// with `,` as decimal character, digits are grouped with `.`
+    pub decimal_char: Option<char>,
     /// format type
     pub format_type: FormatType,
     /// timezone
     pub timezone: Timezone,
+    /// multiplier applied to input before formatting, e.g. 1e-9 to display wei as gwei
+    pub scale: f64,
+    /// how to render values wider than max_width
+    pub overflow: OverflowMode,
+    /// whether percentages are given as ratios or already as percentage points
+    pub percent_mode: PercentMode,
+    /// text placed between the sign and the digits, such as a currency, e.g. "$"
+    pub prefix: String,
+    /// text appended to the number, such as a unit, e.g. " gwei"
+    pub suffix: String,
+    /// ansi color of negative numbers, for printing to a terminal
+    pub negative_color: Option<NegativeColor>,
+    /// text shown in place of null values in tables, and of NaN values when nan_as_null is set
+    pub null_repr: String,
+    /// render NaN values, such as ratios divided by zero, as null_repr instead of `NaN`
+    pub nan_as_null: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Timezone {
     Local,
     Utc,
 }
 
 impl NumberFormat {
-    /// format number value
+    /// format number value, padding up to min_width and clipping beyond max_width
     pub fn format<T: Into<f64>>(&self, input: T) -> Result<String, FormatError> {
-        let s = super::interface::number_format(self, input)?;
-        if s.len() < self.min_width {
-            match self.align {
-                NumberAlign::Left => Ok(format!("{:<width$}", s, width = self.min_width)),
-                NumberAlign::Right => Ok(format!("{:>width$}", s, width = self.min_width)),
-                _ => todo!(),
-            }
-        } else if s.len() > self.max_width {
-            if self.max_width == 1 {
-                Ok(".".to_string())
-            } else if self.max_width == 2 {
-                Ok("..".to_string())
-            } else {
-                Ok(format!("{}...", &s[0..(self.max_width - 3)]))
-            }
-        } else {
-            Ok(s)
-        }
+        super::interface::number_format(self, input)
     }
 
     /// format option of binary data
@@ -103,9 +125,21 @@ impl Default for NumberFormat {
             min_width: DEFAULT_MIN_WIDTH,
             max_width: DEFAULT_MAX_WIDTH,
             commas: DEFAULT_COMMAS,
+            group_size: DEFAULT_GROUP_SIZE,
+            min_group_digits: DEFAULT_MIN_GROUP_DIGITS,
             precision: DEFAULT_PRECISION,
+            precision_mode: PrecisionMode::default(),
+            decimal_char: None,
             format_type: FormatType::default(),
             timezone: DEFAULT_TIMEZONE,
+            scale: DEFAULT_SCALE,
+            overflow: OverflowMode::default(),
+            percent_mode: PercentMode::default(),
+            prefix: DEFAULT_PREFIX.to_string(),
+            suffix: DEFAULT_SUFFIX.to_string(),
+            negative_color: None,
+            null_repr: DEFAULT_NULL_REPR.to_string(),
+            nan_as_null: DEFAULT_NAN_AS_NULL,
         }
     }
 }
@@ -146,6 +180,71 @@ impl Default for Sign {
     }
 }
 
+/// ansi color of negative numbers, and the part of them it applies to
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NegativeColor {
+    /// ansi foreground color code, e.g. 31 for red
+    pub code: u8,
+    /// part of number that is colored
+    pub scope: NegativeColorScope,
+}
+
+/// part of a negative number that its color applies to
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NegativeColorScope {
+    /// sign, digits, and suffix
+    Whole,
+    /// only the minus sign, keeping the digits readable
+    SignOnly,
+}
+
+/// how to render a value that does not fit in max_width
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OverflowMode {
+    /// fall back to scientific notation, or hashes if that does not fit either
+    Scientific,
+    /// fill max_width with `#`, like a spreadsheet
+    Hashes,
+    /// clip the value and end it with `...`
+    Truncate,
+}
+
+impl Default for OverflowMode {
+    fn default() -> OverflowMode {
+        DEFAULT_OVERFLOW
+    }
+}
+
+/// scale of values formatted as percentages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PercentMode {
+    /// value is a ratio, multiplied by 100, e.g. `0.5` as `50%`
+    Ratio,
+    /// value is already in percentage points, e.g. `50` as `50%`
+    Points,
+}
+
+impl Default for PercentMode {
+    fn default() -> PercentMode {
+        DEFAULT_PERCENT_MODE
+    }
+}
+
+/// meaning of the precision of fixed point values
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PrecisionMode {
+    /// precision is the number of digits after the decimal point, e.g. `.3` gives `1234.500`
+    Decimals,
+    /// precision is the number of significant digits, e.g. `.3` gives `1230` and `0.00123`
+    Significant,
+}
+
+impl Default for PrecisionMode {
+    fn default() -> PrecisionMode {
+        DEFAULT_PRECISION_MODE
+    }
+}
+
 /// format type
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FormatType {
@@ -177,6 +276,12 @@ pub enum FormatType {
     FloatOrderOfMagnitude,
     /// timestamp pretty
     TimestampPretty,
+    /// multiplier with trailing zeros trimmed, e.g. `1.5×`
+    Multiplier,
+    /// duration in seconds as a clock, e.g. `01:02:05`
+    Duration,
+    /// duration in seconds as its two largest units, e.g. `1h 2m`
+    DurationShort,
     /// no format
     None,
 }
@@ -199,6 +304,9 @@ impl FormatType {
             FormatType::IntegerOrderOfMagnitude,
             FormatType::FloatOrderOfMagnitude,
             FormatType::TimestampPretty,
+            FormatType::Multiplier,
+            FormatType::Duration,
+            FormatType::DurationShort,
             FormatType::None,
         ]
     }
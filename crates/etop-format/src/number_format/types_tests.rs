@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod types_tests {
-    use crate::number_format::{format, process};
+    use crate::{
+        number_format::{format, process},
+        NumberFormat, PercentMode, PrecisionMode,
+    };
 
     #[test]
     fn integer_oom() {
@@ -181,6 +184,40 @@ mod types_tests {
         assert_eq!(format("^21,.0%", -122).unwrap(), "      -12,200%       ");
     }
 
+    #[test]
+    fn percent_modes() {
+        let percent = NumberFormat::new().percentage().precision(1);
+        assert_eq!(percent.format(0.5).unwrap(), "50.0%");
+        let points = percent.clone().percent_mode(PercentMode::Points);
+        assert_eq!(points.format(50.0).unwrap(), "50.0%");
+        assert_eq!(points.format(-12.34).unwrap(), "-12.3%");
+        assert_eq!(percent.percent_mode(PercentMode::Ratio).format(0.5).unwrap(), "50.0%");
+    }
+
+    #[test]
+    fn precision_modes() {
+        let decimals = NumberFormat::new().precision(3);
+        let significant = decimals.clone().precision_mode(PrecisionMode::Significant);
+        let cases = [
+            (1234.5, "1234.500", "1230"),
+            (12.345, "12.345", "12.3"),
+            (0.0012345, "0.001", "0.00123"),
+            (9.9996, "10.000", "10.0"),
+            (-0.5, "-0.500", "-0.500"),
+            (0.0, "0.000", "0.00"),
+        ];
+        for (value, as_decimals, as_significant) in cases {
+            assert_eq!(decimals.format(value).unwrap(), as_decimals);
+            assert_eq!(significant.format(value).unwrap(), as_significant);
+        }
+
+        // other fixed point formats follow the mode
+        let percent = significant.clone().percentage();
+        assert_eq!(percent.format(0.012345).unwrap(), "1.23%");
+        let commas = significant.commas();
+        assert_eq!(commas.format(1_234_567.0).unwrap(), "1,230,000");
+    }
+
     #[test]
     fn grouping() {
         assert_eq!(format("01,.0d", 0).unwrap(), "0");
@@ -225,7 +262,7 @@ mod types_tests {
             "9999999999999999464902769475481793196872414789632"
         );
         assert_eq!(
-            format(".0f", 9.9999999999999987e+49).unwrap(),
+            format(".0f", 9.999999999999999e49).unwrap(),
             "99999999999999986860582406952576489172979654066176"
         );
         assert_eq!(
@@ -586,7 +623,7 @@ mod types_tests {
         assert_eq!(format(".3s", 1500.5).unwrap(), "1.50k");
         assert_eq!(format(".3s", 42e6).unwrap(), "42.0M");
         assert_eq!(format(".3s", 145500000).unwrap(), "146M");
-        assert_eq!(format(".3s", 145999999.999999347).unwrap(), "146M");
+        assert_eq!(format(".3s", 145999999.99999934).unwrap(), "146M");
         assert_eq!(format(".3s", 1e26).unwrap(), "100Y");
         assert_eq!(format(".3s", 0.000001).unwrap(), "1.00µ");
         assert_eq!(format(".3s", 0.009995).unwrap(), "10.0m");
@@ -595,6 +632,116 @@ mod types_tests {
         assert_eq!(format(".4s", 0.009995).unwrap(), "9.995m");
     }
 
+    #[test]
+    fn si_prefix_explicit_sign() {
+        // precision of `s` is in significant digits, as in d3-format, rather than digits after
+        // the decimal point, so `+1.2k` takes `+.2s` and `+.1s` keeps a single digit
+        assert_eq!(format("+.2s", 1200.0).unwrap(), "+1.2k");
+        assert_eq!(format("+.2s", -1200.0).unwrap(), "-1.2k");
+        assert_eq!(format("+.1s", 1200.0).unwrap(), "+1k");
+        assert_eq!(format("+.3s", 0.0012).unwrap(), "+1.20m");
+        assert_eq!(format("+.2s", -0.0004).unwrap(), "-400µ");
+        assert_eq!(format("+.2s", 999.99).unwrap(), "+1.0k");
+        assert_eq!(format("+.2s", 0.0).unwrap(), "+0.0");
+        assert_eq!(format("+.2s", -0.0).unwrap(), "-0.0");
+
+        // sign is placed before padding, and with the builder
+        assert_eq!(format("+08.2s", 1200.0).unwrap(), "+0001.2k");
+        assert_eq!(format("+8.2s", -1200.0).unwrap(), "   -1.2k");
+        let delta = NumberFormat::new().si().precision(2).signed();
+        assert_eq!(delta.format(1200.0).unwrap(), "+1.2k");
+        assert_eq!(delta.format(-1200.0).unwrap(), "-1.2k");
+
+        // values below the smallest prefix, or not finite, do not panic
+        assert_eq!(format("+.2s", -1e-30).unwrap(), "-0.000001y");
+        assert!(format("+.2s", f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn suffix() {
+        let gwei = NumberFormat::new().precision(0).suffix(" gwei");
+        assert_eq!(gwei.format(12).unwrap(), "12 gwei");
+        assert_eq!(gwei.clone().min_width(10).format(5).unwrap(), "    5 gwei");
+        assert_eq!(gwei.clone().min_width(10).left_align().format(5).unwrap(), "5 gwei    ");
+        assert_eq!(gwei.clone().min_width(10).zero_padding().format(5).unwrap(), "00005 gwei");
+        assert_eq!(
+            NumberFormat::new().percentage().precision(0).suffix("!").format(0.5).unwrap(),
+            "50%!"
+        );
+        let txs = NumberFormat::new().integer_oom().precision(1).suffix(" tx");
+        assert_eq!(txs.format(15000).unwrap(), "15.0K tx");
+    }
+
+    #[test]
+    fn decimal_char() {
+        let euro = NumberFormat::new().precision(2).decimal(',');
+        assert_eq!(euro.format(1234.5).unwrap(), "1234,50");
+        assert_eq!(euro.format(-0.25).unwrap(), "-0,25");
+
+        // digits are grouped with `.` when `,` is the decimal character
+        let grouped = euro.clone().commas().suffix(" €");
+        assert_eq!(grouped.format(1_234_567.891).unwrap(), "1.234.567,89 €");
+        assert_eq!(
+            grouped.clone().zero_padding().min_width(12).format(1234.5).unwrap(),
+            "001.234,50 €"
+        );
+
+        // other formats with a decimal point follow it
+        assert_eq!(euro.clone().si().precision(3).format(1234.5).unwrap(), "1,23k");
+        assert_eq!(euro.clone().float_oom().precision(1).format(12345.6).unwrap(), "12,3K");
+        assert_eq!(euro.clone().percentage().precision(1).format(0.125).unwrap(), "12,5%");
+        assert_eq!(NumberFormat::new().precision(2).decimal('\'').format(1.5).unwrap(), "1'50");
+    }
+
+    #[test]
+    fn integer_fraction_split() {
+        // only the digits before the decimal character or the exponent are grouped
+        let sci = NumberFormat::new().scientific_notation().commas();
+        assert_eq!(sci.clone().precision(4).format(12345678.0).unwrap(), "1.2346e+07");
+        assert_eq!(sci.clone().precision(0).format(12345678.0).unwrap(), "1e+07");
+        assert_eq!(sci.clone().precision(2).decimal(',').format(12345.0).unwrap(), "1,23e+04");
+        assert_eq!(
+            sci.clone().precision(0).zero_padding().min_width(10).format(12345.0).unwrap(),
+            "00,001e+04"
+        );
+
+        // a custom decimal character still splits off the fraction
+        let fmt = NumberFormat::new().commas().precision(2);
+        assert_eq!(fmt.clone().decimal('\'').format(1234567.5).unwrap(), "1,234,567'50");
+        assert_eq!(fmt.clone().decimal(',').format(1234567.5).unwrap(), "1.234.567,50");
+        assert_eq!(
+            fmt.clone().percentage().precision(1).decimal(',').format(12.345).unwrap(),
+            "1.234,5%"
+        );
+    }
+
+    #[test]
+    fn prefix_with_suffix() {
+        let usd = NumberFormat::new().commas().precision(2).prefix("$").suffix(" USD");
+        assert_eq!(usd.format(1234.56).unwrap(), "$1,234.56 USD");
+        assert_eq!(usd.format(-1234.56).unwrap(), "-$1,234.56 USD");
+
+        // prefix and suffix count towards width
+        assert_eq!(usd.clone().min_width(15).format(1234.56).unwrap(), "  $1,234.56 USD");
+        assert_eq!(usd.clone().min_width(15).left_align().format(5).unwrap(), "$5.00 USD      ");
+        assert_eq!(usd.clone().max_width(13).format(1234.56).unwrap(), "$1,234.56 USD");
+    }
+
+    #[test]
+    fn si_prefix_significant_digits() {
+        // precision counts significant digits, matching d3-format
+        assert_eq!(format(".3s", 12345).unwrap(), "12.3k");
+        assert_eq!(format(".3s", -12345).unwrap(), "-12.3k");
+        assert_eq!(format(".3s", 123456789).unwrap(), "123M");
+        assert_eq!(format(".3s", 0.0012345).unwrap(), "1.23m");
+        assert_eq!(format(".3s", 1.5).unwrap(), "1.50");
+        assert_eq!(format(".3s", 999999).unwrap(), "1.00M");
+        assert_eq!(format(".1s", 12345).unwrap(), "10k");
+        assert_eq!(format(".2s", 12345).unwrap(), "12k");
+        assert_eq!(format(".5s", 12345).unwrap(), "12.345k");
+        assert_eq!(NumberFormat::new().si().precision(3).format(12345).unwrap(), "12.3k");
+    }
+
     #[test]
     fn si_prefix_numbers_smaller_than_one_yocto() {
         assert_eq!(format(".8s", 1.29e-30).unwrap(), "0.0000013y"); // Note: rounded!
@@ -684,4 +831,374 @@ mod types_tests {
         assert_eq!(format("+f", -1e-12).unwrap(), "-0.000000");
         assert_eq!(format("+f", 1e-12).unwrap(), "+0.000000");
     }
+
+    #[test]
+    fn content_wider_than_width() {
+        assert_eq!(format("5,.0f", 1234567.0).unwrap(), "1,234,567");
+        assert_eq!(format("05,.0f", 1234567.0).unwrap(), "1,234,567");
+        assert_eq!(format("^3.2f", 1234.5).unwrap(), "1234.50");
+        assert_eq!(format("<2%", 12).unwrap(), "1200.000000%");
+    }
+
+    #[test]
+    fn scale() {
+        let fmt = crate::number_format::NumberFormat::new().scale(1e-9).precision(2);
+        assert_eq!(fmt.format(1_500_000_000.0).unwrap(), "1.50");
+        let fmt = crate::number_format::NumberFormat::new().float_oom().precision(1).scale(1e-18);
+        assert_eq!(fmt.format(25_000_000_000_000_000_000_000.0).unwrap(), "25.0K");
+    }
+
+    #[test]
+    fn negative_zero_consistent_across_signs() {
+        for input in [-0.0, -0.0001] {
+            assert_eq!(format(".2f", input).unwrap(), "0.00");
+            assert_eq!(format("-.2f", input).unwrap(), "0.00");
+            assert_eq!(format(" .2f", input).unwrap(), " 0.00");
+            assert_eq!(format("+.2f", input).unwrap(), "-0.00");
+            assert_eq!(format(".2%", input / 100.0).unwrap(), "0.00%");
+            assert_eq!(format("x", input).unwrap(), "0");
+            assert_eq!(format("d", input).unwrap(), "0");
+        }
+        assert_eq!(format(".2e", -0.0).unwrap(), "0.00e+00");
+        assert_eq!(format("+.2e", -0.0).unwrap(), "-0.00e+00");
+    }
+
+    #[test]
+    fn negative_zero_oom() {
+        let integer_oom = crate::number_format::NumberFormat::new().integer_oom().precision(0);
+        let float_oom = crate::number_format::NumberFormat::new().float_oom().precision(2);
+        for input in [-0.0, -0.0001] {
+            assert_eq!(integer_oom.format(input).unwrap(), "0");
+            assert_eq!(float_oom.format(input).unwrap(), "0.00");
+        }
+        assert_eq!(float_oom.format(-0.5).unwrap(), "-0.50");
+    }
+
+    #[test]
+    fn space_sign() {
+        assert_eq!(format(" .2f", 3.0).unwrap(), " 3.00");
+        assert_eq!(format(" .2f", -3.0).unwrap(), "-3.00");
+        assert_eq!(format(" 8.2f", 3.0).unwrap(), "    3.00");
+        assert_eq!(format(" 8.2f", -3.0).unwrap(), "   -3.00");
+        assert_eq!(format("< 8.2f", 3.0).unwrap(), " 3.00   ");
+        assert_eq!(format("< 8.2f", -3.0).unwrap(), "-3.00   ");
+        assert_eq!(format(" 08.2f", 3.0).unwrap(), " 0003.00");
+        assert_eq!(format(" 08.2f", -3.0).unwrap(), "-0003.00");
+        assert_eq!(format(" ,.0f", 1234.0).unwrap(), " 1,234");
+        assert_eq!(format(" ,.0f", -1234.0).unwrap(), "-1,234");
+        for pattern in [" .2f", " 8.2f", "< 8.2f", " 08.2f", " ,.0f", " .1%", " .3s"] {
+            let positive = format(pattern, 1234.5).unwrap();
+            let negative = format(pattern, -1234.5).unwrap();
+            assert_eq!(positive.chars().count(), negative.chars().count(), "{}", pattern);
+        }
+        assert_eq!(format(" 8.3s", 1e-6).unwrap(), "   1.00µ");
+        assert_eq!(format(" 8.3s", -1e-6).unwrap(), "  -1.00µ");
+    }
+
+    #[test]
+    fn builder_padding() {
+        // built formats pad with spaces, as patterns do, unless zero padding is requested
+        let integer = NumberFormat::new().precision(0);
+        assert_eq!(integer.clone().min_width(6).format(-42).unwrap(), "   -42");
+        assert_eq!(integer.clone().commas().min_width(7).format(1234).unwrap(), "  1,234");
+        assert_eq!(integer.clone().min_width(6).zero_padding().format(-42).unwrap(), "-00042");
+        assert_eq!(integer.min_width(6).format(-42).unwrap(), format("6.0f", -42).unwrap());
+    }
+
+    #[test]
+    fn builder_width_align_fill_sign() {
+        use crate::number_format::{NumberAlign, NumberFormat, Sign};
+
+        let fmt = NumberFormat::new().zero_padding().min_width(10).commas().precision(0);
+        assert_eq!(fmt.format(1234567).unwrap(), "01,234,567");
+
+        let fmt = NumberFormat::new().align(NumberAlign::Right).fill('0').min_width(8).precision(1);
+        assert_eq!(fmt.format(-12.34).unwrap(), "000-12.3");
+
+        let fmt = NumberFormat::new().min_width(10).commas().precision(0);
+        assert_eq!(fmt.format(1234567).unwrap(), " 1,234,567");
+
+        let fmt = NumberFormat::new()
+            .align(NumberAlign::Left)
+            .fill('*')
+            .sign(Sign::Always)
+            .width(8)
+            .precision(2);
+        assert_eq!(fmt.format(4.5678).unwrap(), "+4.57***");
+        assert_eq!(fmt.format(-4.5678).unwrap(), "-4.57***");
+    }
+
+    #[test]
+    fn builder_hex() {
+        // hex formats as hexadecimal, as the `x` type of a pattern does
+        assert_eq!(NumberFormat::new().hex().format(255).unwrap(), "ff");
+        assert_eq!(NumberFormat::new().hex().format(255).unwrap(), format("x", 255).unwrap());
+    }
+
+    #[test]
+    fn min_width_and_max_width() {
+        use crate::number_format::{FormatType, NumberAlign, NumberFormat};
+
+        // min_width pads, max_width clips
+        let fmt = NumberFormat::new().min_width(8).max_width(10).precision(2);
+        assert_eq!(fmt.format(3.5).unwrap(), "    3.50");
+        assert_eq!(fmt.format(123456789.0).unwrap(), "1234567...");
+        assert_eq!(fmt.clone().max_width(2).format(123456789.0).unwrap(), "..");
+
+        // a pattern width is a min_width
+        let fmt: NumberFormat = "8.2f".try_into().unwrap();
+        assert_eq!(fmt.min_width, 8);
+        assert_eq!(fmt.max_width, usize::MAX);
+        assert_eq!(fmt.format(123456789.0).unwrap(), "123456789.00");
+
+        // bounds also apply to types that bypass sign and grouping
+        let fmt = NumberFormat::new()
+            .format_type(&FormatType::IntegerOrderOfMagnitude)
+            .precision(1)
+            .min_width(7);
+        assert_eq!(fmt.format(2500.0).unwrap(), " 2500.0");
+        assert_eq!(fmt.clone().align(NumberAlign::Left).format(25000.0).unwrap(), "25.0K  ");
+        assert_eq!(fmt.clone().align(NumberAlign::Center).format(7.0).unwrap(), "   7   ");
+        assert_eq!(fmt.min_width(0).max_width(4).format(123456789.0).unwrap(), "1...");
+    }
+
+    #[test]
+    fn overflow_modes() {
+        use crate::number_format::{NumberFormat, OverflowMode};
+
+        let fmt = NumberFormat::new().max_width(8).precision(2);
+        assert_eq!(fmt.format(123456789.0).unwrap(), "12345...");
+        assert_eq!(fmt.format(12345.0).unwrap(), "12345.00");
+
+        let hashes = fmt.clone().overflow(OverflowMode::Hashes);
+        assert_eq!(hashes.format(123456789.0).unwrap(), "########");
+        assert_eq!(hashes.format(12345.0).unwrap(), "12345.00");
+
+        let scientific = fmt.overflow(OverflowMode::Scientific);
+        assert_eq!(scientific.format(123456789.0).unwrap(), "1.23e+08");
+        assert_eq!(scientific.format(12345.0).unwrap(), "12345.00");
+
+        // the negative sign takes a slot, costing a digit of precision
+        assert_eq!(scientific.format(-123456789.0).unwrap(), "-1.2e+08");
+
+        // hashes when even the shortest scientific form does not fit
+        let narrow = scientific.max_width(5);
+        assert_eq!(narrow.format(123456789.0).unwrap(), "1e+08");
+        assert_eq!(narrow.format(-123456789.0).unwrap(), "#####");
+    }
+
+    #[test]
+    fn multiplier_and_ratio() {
+        use crate::number_format::{format_ratio, NumberFormat};
+
+        let fmt = NumberFormat::new().multiplier().precision(2);
+        assert_eq!(fmt.format(2.0).unwrap(), "2×");
+        assert_eq!(fmt.format(1.5).unwrap(), "1.5×");
+        assert_eq!(fmt.format(1.234).unwrap(), "1.23×");
+        assert_eq!(fmt.format(-0.5).unwrap(), "-0.5×");
+        assert_eq!(fmt.clone().commas().format(12000.0).unwrap(), "12,000×");
+        assert_eq!(fmt.min_width(5).format(2.0).unwrap(), "   2×");
+
+        assert_eq!(format_ratio(6, 4), "3:2");
+        assert_eq!(format_ratio(3, 1), "3:1");
+        assert_eq!(format_ratio(-6, 4), "-3:2");
+        assert_eq!(format_ratio(0, 5), "0:1");
+        assert_eq!(format_ratio(0, 0), "0:0");
+        assert_eq!(format_ratio(1.5, 1), "1.5:1");
+    }
+
+    #[test]
+    fn format_type_from_pattern_char() {
+        use crate::number_format::{FormatType, NumberFormat};
+
+        let expected = [
+            ("e", FormatType::Exponent),
+            ("E", FormatType::ExponentUppercase),
+            ("f", FormatType::FixedPoint),
+            ("s", FormatType::SI),
+            ("%", FormatType::Percentage),
+            ("b", FormatType::Binary),
+            ("o", FormatType::Octal),
+            ("O", FormatType::OctalUppercase),
+            ("d", FormatType::Decimal),
+            ("x", FormatType::Hex),
+            ("X", FormatType::HexUppercase),
+        ];
+        for (c, format_type) in expected {
+            assert_eq!(c.parse::<FormatType>().unwrap(), format_type, "{}", c);
+            let fmt: NumberFormat = format!(",.2{}", c).as_str().try_into().unwrap();
+            assert_eq!(fmt.format_type, format_type, "{}", c);
+            assert!(FormatType::all_variants().contains(&format_type));
+        }
+
+        // chars are case sensitive
+        assert!("F".parse::<FormatType>().is_err());
+        assert!("ee".parse::<FormatType>().is_err());
+        assert!("".parse::<FormatType>().is_err());
+
+        // a pattern without a type char has no format type
+        let fmt: NumberFormat = ",.2".try_into().unwrap();
+        assert_eq!(fmt.format_type, FormatType::None);
+
+        // octal variants differ only in their prefix
+        assert_eq!(format("#o", 8).unwrap(), "0o10");
+        assert_eq!(format("#O", 8).unwrap(), "0O10");
+    }
+
+    #[test]
+    fn to_pattern_round_trip() {
+        use crate::number_format::{FormatType, NumberFormat};
+
+        let parse = |pattern: &str| -> NumberFormat { pattern.try_into().unwrap() };
+        assert_eq!(parse(",.2f").to_pattern(), ",.2f");
+        assert_eq!(parse("*^+#10,.3e").to_pattern(), "*^+#10,.3e");
+        assert_eq!(parse("08.1%").to_pattern(), "08.1%");
+        assert_eq!(parse("0=8.1%").to_pattern(), "08.1%");
+        assert_eq!(parse("d").to_pattern(), ".0d");
+        assert_eq!(parse("").to_pattern(), ".6");
+
+        // generate specs from pseudorandom combinations of pattern components
+        let fill_aligns = ["", "<", ">", "^", "=", "*<", "*^", "0=", "<<"];
+        let signs = ["", "-", "+", " "];
+        let widths = ["", "1", "12"];
+        let precisions = ["", ".0", ".3"];
+        let types: Vec<String> = FormatType::all_variants()
+            .iter()
+            .map(|t| t.pattern_char().map(|c| c.to_string()).unwrap_or_default())
+            .collect();
+        let mut seed: u64 = 12345;
+        let mut pick = |n: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) as usize) % n
+        };
+        for _ in 0..500 {
+            let pattern = format!(
+                "{}{}{}{}{}{}{}{}",
+                fill_aligns[pick(fill_aligns.len())],
+                signs[pick(signs.len())],
+                ["", "#"][pick(2)],
+                ["", "0"][pick(2)],
+                widths[pick(widths.len())],
+                ["", ","][pick(2)],
+                precisions[pick(precisions.len())],
+                types[pick(types.len())],
+            );
+            let spec = parse(&pattern);
+            assert_eq!(parse(&spec.to_pattern()), spec, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn group_sizes() {
+        use crate::number_format::NumberFormat;
+
+        assert_eq!(process::group_value("1234567", 0, 3, 0), "1,234,567");
+        assert_eq!(process::group_value("1234567", 0, 2, 0), "1,23,45,67");
+        assert_eq!(process::group_value("1234567", 0, 4, 0), "123,4567");
+        assert_eq!(process::group_value("00001234", 6, 4, 0), "0,1234");
+
+        let fmt = NumberFormat::new().commas().precision(0);
+        assert_eq!(fmt.format(1234567).unwrap(), "1,234,567");
+        assert_eq!(fmt.clone().group_size(2).format(1234567).unwrap(), "1,23,45,67");
+        assert_eq!(fmt.clone().group_size(4).format(12345678).unwrap(), "1234,5678");
+        assert_eq!(fmt.clone().group_size(4).format(-12345.5).unwrap(), "-1,2346");
+
+        // zero padding is grouped with the same size
+        let padded = fmt.zero_padding().min_width(12);
+        assert_eq!(padded.clone().group_size(4).format(12345678).unwrap(), "00,1234,5678");
+        assert_eq!(padded.group_size(2).format(1234).unwrap(), "0,00,00,12,34");
+    }
+
+    #[test]
+    fn min_group_digits() {
+        use crate::number_format::NumberFormat;
+
+        assert_eq!(process::group_value("2024", 0, 3, 5), "2024");
+        assert_eq!(process::group_value("12345", 0, 3, 5), "12,345");
+        assert_eq!(process::group_value("00002024", 8, 3, 5), "00002024");
+
+        let fmt = NumberFormat::new().commas().precision(0).min_group_digits(5);
+        assert_eq!(fmt.format(2024).unwrap(), "2024");
+        assert_eq!(fmt.format(9999).unwrap(), "9999");
+        assert_eq!(fmt.format(10000).unwrap(), "10,000");
+        assert_eq!(fmt.format(-9999).unwrap(), "-9999");
+        assert_eq!(fmt.format(-10000).unwrap(), "-10,000");
+        assert_eq!(fmt.format(18_000_000).unwrap(), "18,000,000");
+
+        // only integer digits count
+        let fmt = fmt.precision(2);
+        assert_eq!(fmt.format(1234.56).unwrap(), "1234.56");
+        assert_eq!(fmt.format(12345.6).unwrap(), "12,345.60");
+
+        // padding zeros do not count
+        let padded = NumberFormat::new().commas().precision(0).min_group_digits(5);
+        let padded = padded.zero_padding().min_width(8);
+        assert_eq!(padded.format(2024).unwrap(), "00002024");
+        assert_eq!(padded.format(12345).unwrap(), "0,012,345");
+    }
+
+    #[test]
+    fn negative_color() {
+        use crate::number_format::{NegativeColorScope, NumberFormat};
+
+        let fmt = NumberFormat::new().commas().precision(1);
+        let sign_only = fmt.clone().negative_color(31, NegativeColorScope::SignOnly);
+        assert_eq!(sign_only.format(-1234.5).unwrap(), "\x1b[31m-\x1b[0m1,234.5");
+        assert_eq!(sign_only.format(1234.5).unwrap(), "1,234.5");
+
+        let whole = fmt.negative_color(31, NegativeColorScope::Whole);
+        assert_eq!(whole.format(-1234.5).unwrap(), "\x1b[31m-1,234.5\x1b[0m");
+        assert_eq!(whole.format(1234.5).unwrap(), "1,234.5");
+
+        // color codes take no width
+        let padded = sign_only.clone().min_width(10);
+        assert_eq!(padded.format(-1234.5).unwrap(), "  \x1b[31m-\x1b[0m1,234.5");
+        let clipped = sign_only.min_width(0).max_width(6);
+        assert_eq!(clipped.format(-1234.5).unwrap(), "-1,...");
+
+        // values rounding to zero are not negative
+        let sign_only =
+            NumberFormat::new().precision(0).negative_color(31, NegativeColorScope::SignOnly);
+        assert_eq!(sign_only.format(-0.1).unwrap(), "0");
+        assert_eq!(process::strip_ansi("\x1b[1;31m-\x1b[0m5"), "-5");
+    }
+
+    #[test]
+    fn nan_as_null() {
+        let fmt = NumberFormat::new().precision(2).min_width(4);
+        assert_eq!(fmt.format(f64::NAN).unwrap(), "   -");
+        assert_eq!(fmt.clone().null_repr("n/a").format(f64::NAN).unwrap(), " n/a");
+        assert_eq!(fmt.clone().no_nan_as_null().format(f64::NAN).unwrap(), " NaN");
+        assert_eq!(fmt.format(1.5).unwrap(), "1.50");
+    }
+
+    #[test]
+    fn durations() {
+        use crate::number_format::format_duration;
+
+        assert_eq!(format_duration(0.0, false), "00:00:00");
+        assert_eq!(format_duration(90_061.0, false), "25:01:01");
+        assert_eq!(format_duration(-59.6, false), "-00:01:00");
+        assert_eq!(format_duration(0.0, true), "0s");
+        assert_eq!(format_duration(0.25, true), "250ms");
+        assert_eq!(format_duration(45.0, true), "45s");
+        assert_eq!(format_duration(86_460.0, true), "1d");
+        assert_eq!(format_duration(90_061.0, true), "1d 1h");
+        assert_eq!(format_duration(-3_725.0, true), "-1h 2m");
+        assert_eq!(NumberFormat::new().duration().format(61).unwrap(), "00:01:01");
+    }
+
+    #[test]
+    fn si_overflow_drops_precision() {
+        let fmt = NumberFormat::new().si().precision(6);
+        assert_eq!(fmt.format(999_999).unwrap(), "999.999k");
+        assert_eq!(fmt.clone().max_width(5).format(999_999).unwrap(), "1.00M");
+        assert_eq!(fmt.clone().max_width(2).format(999_999).unwrap(), "1M");
+        assert_eq!(fmt.clone().max_width(5).format(123_456).unwrap(), "123k");
+        assert_eq!(fmt.clone().max_width(6).format(123_456).unwrap(), "123.5k");
+        assert_eq!(fmt.clone().max_width(6).format(12).unwrap(), "12.000");
+
+        // truncation only once a single significant digit does not fit
+        assert_eq!(fmt.max_width(1).format(999_999).unwrap(), ".");
+    }
 }
@@ -1,4 +1,4 @@
-use super::types::{StringAlign, StringFormat};
+use super::types::{BigIntFormat, StringAlign, StringFormat};
 
 impl StringFormat {
     /// create new number format
@@ -68,6 +68,12 @@ impl StringFormat {
         self
     }
 
+    /// center align
+    pub fn center_align(mut self) -> StringFormat {
+        self.align = StringAlign::Center;
+        self
+    }
+
     // fill char
 
     /// add fill char
@@ -75,4 +81,26 @@ impl StringFormat {
         self.fill_char = fill_char;
         self
     }
+
+    // empty and null values
+
+    /// set text shown in place of empty strings
+    pub fn empty_repr<S: AsRef<str>>(mut self, empty_repr: S) -> StringFormat {
+        self.empty_repr = empty_repr.as_ref().to_string();
+        self
+    }
+
+    /// set text shown in place of null strings
+    pub fn null_repr<S: AsRef<str>>(mut self, null_repr: S) -> StringFormat {
+        self.null_repr = null_repr.as_ref().to_string();
+        self
+    }
+
+    // big integers
+
+    /// format strings holding big integers, such as u256 values, without loss of precision
+    pub fn big_int(mut self, big_int: BigIntFormat) -> StringFormat {
+        self.big_int = Some(big_int);
+        self
+    }
 }
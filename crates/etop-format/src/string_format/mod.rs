@@ -1,4 +1,4 @@
 mod builder;
 mod types;
 
-pub use types::StringFormat;
+pub use types::{truncate_graphemes, BigIntFormat, StringAlign, StringFormat};
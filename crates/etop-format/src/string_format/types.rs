@@ -3,6 +3,14 @@
 mod tests;
 
 use crate::FormatError;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// default text shown in place of empty strings, so that they can be told apart from nulls
+pub(crate) const DEFAULT_EMPTY_REPR: &str = "∅";
+
+/// default text shown in place of null strings
+pub(crate) const DEFAULT_NULL_REPR: &str = "-";
 
 /// string format specification
 #[derive(Debug, Clone)]
@@ -11,10 +19,16 @@ pub struct StringFormat {
     pub min_width: usize,
     /// max_width of string, for padding
     pub max_width: usize,
-    /// align string to left or right
+    /// align string to left, right, or center
     pub align: StringAlign,
     /// fill padding char
     pub fill_char: char,
+    /// text shown in place of empty strings
+    pub empty_repr: String,
+    /// text shown in place of null strings
+    pub null_repr: String,
+    /// format of strings holding big integers, None to show strings as they are
+    pub big_int: Option<BigIntFormat>,
 }
 
 impl Default for StringFormat {
@@ -24,8 +38,114 @@ impl Default for StringFormat {
             max_width: usize::MAX,
             align: StringAlign::Right,
             fill_char: ' ',
+            empty_repr: DEFAULT_EMPTY_REPR.to_string(),
+            null_repr: DEFAULT_NULL_REPR.to_string(),
+            big_int: None,
+        }
+    }
+}
+
+/// format of strings holding integers too large for floats, such as u256 values, which are
+/// converted digit by digit so that no precision is lost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigIntFormat {
+    /// digits per group of the integer part, 0 for no grouping
+    pub group_size: usize,
+    /// number of places the decimal point is shifted left, e.g. 18 to convert wei to ether
+    pub decimals: usize,
+    /// digits after the decimal point, rounded half up, None to keep every nonzero digit
+    pub precision: Option<usize>,
+}
+
+impl Default for BigIntFormat {
+    fn default() -> BigIntFormat {
+        BigIntFormat { group_size: 3, decimals: 0, precision: None }
+    }
+}
+
+impl BigIntFormat {
+    /// create new big integer format
+    pub fn new() -> BigIntFormat {
+        BigIntFormat::default()
+    }
+
+    /// set digits per group, 0 for no grouping
+    pub fn group_size(mut self, group_size: usize) -> BigIntFormat {
+        self.group_size = group_size;
+        self
+    }
+
+    /// set number of places the decimal point is shifted left
+    pub fn decimals(mut self, decimals: usize) -> BigIntFormat {
+        self.decimals = decimals;
+        self
+    }
+
+    /// set digits after the decimal point
+    pub fn precision(mut self, precision: usize) -> BigIntFormat {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// format string of an optionally signed decimal integer, None if it is not one
+    pub fn format(&self, s: &str) -> Option<String> {
+        let s = s.trim();
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None
+        }
+
+        // shift decimal point, keeping at least one integer digit
+        let digits = format!("{:0>width$}", digits, width = self.decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals);
+        let (integer, fraction) = match self.precision {
+            Some(precision) if fraction.len() > precision => {
+                let kept = format!("{}{}", integer, &fraction[..precision]);
+                let kept = match fraction.as_bytes()[precision] >= b'5' {
+                    true => increment_digits(&kept),
+                    false => kept,
+                };
+                let (integer, fraction) = kept.split_at(kept.len() - precision);
+                (integer.to_string(), fraction.to_string())
+            }
+            Some(precision) => {
+                (integer.to_string(), format!("{:0<width$}", fraction, width = precision))
+            }
+            None => (integer.to_string(), fraction.trim_end_matches('0').to_string()),
+        };
+
+        let integer = match integer.trim_start_matches('0') {
+            "" => "0",
+            integer => integer,
+        };
+        let integer = match self.group_size {
+            0 => integer.to_string(),
+            group_size => crate::number_format::group_value(integer, 0, group_size, 0),
+        };
+        let is_zero = integer == "0" && fraction.bytes().all(|b| b == b'0');
+        let sign = if negative && !is_zero { "-" } else { "" };
+        match fraction.is_empty() {
+            true => Some(format!("{}{}", sign, integer)),
+            false => Some(format!("{}{}.{}", sign, integer, fraction)),
+        }
+    }
+}
+
+/// decimal digits plus one, carrying into a new leading digit if needed
+fn increment_digits(digits: &str) -> String {
+    let mut bytes = digits.as_bytes().to_vec();
+    for byte in bytes.iter_mut().rev() {
+        if *byte == b'9' {
+            *byte = b'0';
+        } else {
+            *byte += 1;
+            return String::from_utf8(bytes).unwrap_or_default()
         }
     }
+    format!("1{}", String::from_utf8(bytes).unwrap_or_default())
 }
 
 /// alignment of string data
@@ -35,6 +155,8 @@ pub enum StringAlign {
     Left,
     /// right align
     Right,
+    /// center align
+    Center,
 }
 
 impl StringFormat {
@@ -50,29 +172,56 @@ impl StringFormat {
         }
     }
 
-    /// format string data
+    /// format string data, measuring widths in terminal columns so that wide characters, such as
+    /// CJK and emoji, count twice and combining marks do not count
+    ///
+    /// empty strings are shown as empty_repr, and with a big_int format, integers are grouped
+    /// while other strings are shown as they are
     pub fn format<T: AsRef<str>>(&self, s: T) -> Result<String, FormatError> {
-        let s = s.as_ref();
-        if s.len() < self.min_width {
-            let pad = self.fill_char.to_string().repeat(self.min_width - s.len());
+        let big_int = self.big_int.as_ref().and_then(|fmt| fmt.format(s.as_ref()));
+        let s = match (s.as_ref(), big_int.as_ref()) {
+            (_, Some(big_int)) => big_int.as_str(),
+            ("", None) => self.empty_repr.as_str(),
+            (s, None) => s,
+        };
+        let width = s.width();
+        if width < self.min_width {
+            if self.fill_char.width() != Some(1) {
+                return Err(FormatError::InvalidFormat(
+                    "fill_char must be one column wide".to_string(),
+                ));
+            };
+            let n_pad = self.min_width - width;
+            let fill = self.fill_char.to_string();
             match &self.align {
-                StringAlign::Left => Ok(format!("{}{}", s, pad)),
-                StringAlign::Right => Ok(format!("{}{}", pad, s)),
+                StringAlign::Left => Ok(format!("{}{}", s, fill.repeat(n_pad))),
+                StringAlign::Right => Ok(format!("{}{}", fill.repeat(n_pad), s)),
+                StringAlign::Center => {
+                    Ok(format!("{}{}{}", fill.repeat(n_pad / 2), s, fill.repeat(n_pad - n_pad / 2)))
+                }
             }
-        } else if s.len() > self.max_width {
+        } else if width > self.max_width {
             if self.max_width < 3 {
                 return Err(FormatError::InvalidFormat(
                     "min_width too small for clipping".to_string(),
                 ));
             };
-            match s.get(0..(self.max_width - 3)) {
-                Some(s) => Ok(format!("{}...", s)),
-                None => {
-                    Err(FormatError::InvalidFormat("could not take slice of string".to_string()))
-                }
-            }
+            Ok(format!("{}...", truncate_graphemes(s, self.max_width - 3)))
         } else {
             Ok(s.to_string())
         }
     }
 }
+
+/// longest prefix of string that fits in width, ending on a grapheme cluster boundary so that
+/// clusters such as flags and emoji with skin tones are kept whole or dropped whole
+pub fn truncate_graphemes(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in s.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            return &s[..i]
+        }
+    }
+    s
+}
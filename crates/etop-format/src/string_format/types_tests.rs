@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod types_tests {
-    use crate::binary_format::BinaryFormat;
+    use crate::{
+        binary_format::BinaryFormat,
+        string_format::{truncate_graphemes, BigIntFormat, StringFormat},
+    };
 
     #[test]
     fn default() {
@@ -103,4 +106,129 @@ mod types_tests {
         assert_eq!(b.format(vec![2]).unwrap(), "    02");
         assert_eq!(b.format(vec![2, 4, 6, 8]).unwrap(), "020...");
     }
+
+    // fill char tests
+
+    #[test]
+    fn string_fill_char_left_align() {
+        let f = StringFormat::new().min_width(8).left_align().fill_char('.');
+        assert_eq!(f.format("name").unwrap(), "name....");
+        assert_eq!(f.format("").unwrap(), "∅.......");
+        assert_eq!(f.format("long_name").unwrap(), "long_name");
+    }
+
+    #[test]
+    fn string_fill_char_right_align() {
+        let f = StringFormat::new().min_width(8).right_align().fill_char('.');
+        assert_eq!(f.format("name").unwrap(), "....name");
+        assert_eq!(f.clone().empty_repr("").format("").unwrap(), "........");
+        assert_eq!(f.format("long_name").unwrap(), "long_name");
+    }
+
+    #[test]
+    fn string_fill_char_center_align() {
+        let f = StringFormat::new().min_width(8).center_align().fill_char('.');
+        assert_eq!(f.format("name").unwrap(), "..name..");
+        assert_eq!(f.format("abc").unwrap(), "..abc...");
+        assert_eq!(f.format("long_name").unwrap(), "long_name");
+    }
+
+    #[test]
+    fn string_empty_and_null() {
+        let f = StringFormat::new();
+        assert_eq!(f.format("").unwrap(), "∅");
+        assert_eq!(f.format_option(Some(""), &f.null_repr).unwrap(), "∅");
+        assert_eq!(f.format_option(None::<&str>, &f.null_repr).unwrap(), "-");
+
+        let f = StringFormat::new().empty_repr("(empty)").null_repr("null").min_width(8);
+        assert_eq!(f.format("").unwrap(), " (empty)");
+        assert_eq!(f.format_option(None::<&str>, &f.null_repr).unwrap(), "null");
+    }
+
+    #[test]
+    fn big_int_grouping() {
+        let f = StringFormat::new().big_int(BigIntFormat::new());
+        let digits = "1234567890123456789012345678901234567890";
+        assert_eq!(digits.len(), 40);
+        assert_eq!(
+            f.format(digits).unwrap(),
+            "1,234,567,890,123,456,789,012,345,678,901,234,567,890"
+        );
+        assert_eq!(f.format("-0001000").unwrap(), "-1,000");
+        assert_eq!(f.format("+999").unwrap(), "999");
+        assert_eq!(f.format("-0").unwrap(), "0");
+
+        // other strings pass through
+        assert_eq!(f.format("0xff").unwrap(), "0xff");
+        assert_eq!(f.format("1.5").unwrap(), "1.5");
+        assert_eq!(f.format("-").unwrap(), "-");
+        assert_eq!(f.format("").unwrap(), "∅");
+
+        let f = StringFormat::new().big_int(BigIntFormat::new().group_size(0)).min_width(6);
+        assert_eq!(f.format("12345").unwrap(), " 12345");
+    }
+
+    #[test]
+    fn big_int_decimals() {
+        // max u256 in wei is exact in ether
+        let max_u256 =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        let ether = BigIntFormat::new().decimals(18);
+        assert_eq!(
+            ether.format(max_u256).unwrap(),
+            "115,792,089,237,316,195,423,570,985,008,687,907,853,269,984,665,640,564,039,457.\
+             584007913129639935"
+        );
+        assert_eq!(ether.format("1500000000000000000").unwrap(), "1.5");
+        assert_eq!(ether.format("1").unwrap(), "0.000000000000000001");
+        assert_eq!(ether.format("-2000000000000000000").unwrap(), "-2");
+
+        // precision rounds half up, carrying into the integer part
+        let ether = ether.precision(3);
+        assert_eq!(ether.format("1234567800000000000000").unwrap(), "1,234.568");
+        assert_eq!(ether.format("999999500000000000000").unwrap(), "1,000.000");
+        assert_eq!(ether.format("2000000000000000000").unwrap(), "2.000");
+        assert_eq!(ether.format("-400000000000000").unwrap(), "0.000");
+        assert_eq!(BigIntFormat::new().precision(2).format("7").unwrap(), "7.00");
+        assert_eq!(BigIntFormat::new().decimals(2).precision(0).format("250").unwrap(), "3");
+    }
+
+    #[test]
+    fn string_fill_char_wide() {
+        let f = StringFormat::new().min_width(8).fill_char('🦀');
+        assert!(f.format("name").is_err());
+        assert_eq!(f.format("long_name").unwrap(), "long_name");
+    }
+
+    #[test]
+    fn string_display_width() {
+        // CJK characters and emoji take two columns, combining marks take none
+        let f = StringFormat::new().min_width(8);
+        assert_eq!(f.format("日本語").unwrap(), "  日本語");
+        assert_eq!(f.format("🦀🦀").unwrap(), "    🦀🦀");
+        assert_eq!(f.clone().left_align().format("cafe\u{301}").unwrap(), "cafe\u{301}    ");
+
+        // clipping keeps whole characters within max_width
+        let f = StringFormat::new().max_width(8);
+        assert_eq!(f.format("日本語です").unwrap(), "日本...");
+        assert_eq!(f.format("a日本語です").unwrap(), "a日本...");
+        assert_eq!(f.format("🦀🦀🦀🦀🦀").unwrap(), "🦀🦀...");
+    }
+
+    #[test]
+    fn string_clip_graphemes() {
+        // a flag is two code points, which are kept together or dropped together
+        let flag = "\u{1F1EF}\u{1F1F5}";
+        let s = format!("go {}", flag);
+        let width = unicode_width::UnicodeWidthStr::width(flag);
+        assert_eq!(truncate_graphemes(&s, 3), "go ");
+        assert_eq!(truncate_graphemes(&s, 3 + width - 1), "go ");
+        assert_eq!(truncate_graphemes(&s, 3 + width), s);
+
+        // ellipsis follows the last whole cluster
+        let f = StringFormat::new().max_width(3 + width + 2);
+        assert_eq!(f.format(format!("{}!!!", s)).unwrap(), "go ...");
+        let thumbs = "\u{1F44D}\u{1F3FD}";
+        assert_eq!(truncate_graphemes(&format!("ok{}", thumbs), 3), "ok");
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     BinaryFormat, BoolFormat, FormatError, FormatType, NumberFormat, StringFormat, UnknownFormat,
 };
-use polars::prelude::DataType;
+use polars::prelude::{DataType, TimeUnit};
 
 /// cell format shorthand
 #[derive(Debug, Clone)]
@@ -16,6 +16,46 @@ pub enum CellFormatShorthand {
     Bool(BoolFormat),
     /// unknown format
     Unknown(UnknownFormat),
+    /// format of each numeric cell chosen by the first rule whose predicate matches its value
+    Conditional(Vec<(Predicate, CellFormatShorthand)>),
+}
+
+/// condition on the value of a numeric cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Predicate {
+    /// any value, including null, for the fallback rule
+    Always,
+    /// value greater than threshold
+    Above(f64),
+    /// value greater than or equal to threshold
+    AtLeast(f64),
+    /// value less than threshold
+    Below(f64),
+    /// value less than or equal to threshold
+    AtMost(f64),
+}
+
+impl Predicate {
+    /// whether value satisfies predicate
+    pub fn matches(&self, value: f64) -> bool {
+        match *self {
+            Predicate::Always => true,
+            Predicate::Above(threshold) => value > threshold,
+            Predicate::AtLeast(threshold) => value >= threshold,
+            Predicate::Below(threshold) => value < threshold,
+            Predicate::AtMost(threshold) => value <= threshold,
+        }
+    }
+}
+
+/// check that last rule of conditional format is a fallback
+fn require_fallback<T>(rules: &[(Predicate, T)]) -> Result<(), FormatError> {
+    match rules.last() {
+        Some((Predicate::Always, _)) => Ok(()),
+        _ => Err(FormatError::InvalidFormat(
+            "conditional format needs a last rule with Predicate::Always".to_string(),
+        )),
+    }
 }
 
 impl From<NumberFormat> for CellFormatShorthand {
@@ -42,6 +82,12 @@ impl From<BoolFormat> for CellFormatShorthand {
     }
 }
 
+impl From<UnknownFormat> for CellFormatShorthand {
+    fn from(format: UnknownFormat) -> CellFormatShorthand {
+        CellFormatShorthand::Unknown(format)
+    }
+}
+
 impl CellFormatShorthand {
     /// set min width
     pub fn min_width(self, min_width: usize) -> CellFormatShorthand {
@@ -59,6 +105,9 @@ impl CellFormatShorthand {
             CellFormatShorthand::Unknown(fmt) => {
                 CellFormatShorthand::Unknown(fmt.min_width(min_width))
             }
+            CellFormatShorthand::Conditional(rules) => CellFormatShorthand::Conditional(
+                rules.into_iter().map(|(p, fmt)| (p, fmt.min_width(min_width))).collect(),
+            ),
         }
     }
 
@@ -78,12 +127,29 @@ impl CellFormatShorthand {
             CellFormatShorthand::Unknown(fmt) => {
                 CellFormatShorthand::Unknown(fmt.max_width(max_width))
             }
+            CellFormatShorthand::Conditional(rules) => CellFormatShorthand::Conditional(
+                rules.into_iter().map(|(p, fmt)| (p, fmt.max_width(max_width))).collect(),
+            ),
         }
     }
 
+    /// format chosen per cell by rules, the last of which must be a Predicate::Always fallback
+    pub fn conditional(
+        rules: Vec<(Predicate, CellFormatShorthand)>,
+    ) -> Result<CellFormatShorthand, FormatError> {
+        require_fallback(&rules)?;
+        Ok(CellFormatShorthand::Conditional(rules))
+    }
+
     /// convert shorthand into formal version
     pub fn finalize(self, dtype: &DataType) -> Result<CellFormat, FormatError> {
         let fmt = match self {
+            CellFormatShorthand::Conditional(rules) => {
+                require_fallback(&rules)?;
+                let rules: Result<Vec<(Predicate, CellFormat)>, FormatError> =
+                    rules.into_iter().map(|(p, fmt)| Ok((p, fmt.finalize(dtype)?))).collect();
+                CellFormat::Conditional(rules?)
+            }
             CellFormatShorthand::Number(fmt) => CellFormat::Number(fmt),
             CellFormatShorthand::Binary(fmt) => CellFormat::Binary(fmt),
             CellFormatShorthand::String(fmt) => CellFormat::String(fmt),
@@ -102,6 +168,10 @@ impl CellFormatShorthand {
                     let fmt = fmt.format_type(&FormatType::Exponent);
                     CellFormat::Number(fmt)
                 }
+                DataType::Duration(unit) => {
+                    let fmt: NumberFormat = fmt.into();
+                    CellFormat::Number(fmt.duration().scale(duration_unit_seconds(unit)))
+                }
                 _ => {
                     return Err(FormatError::UnsupportedDatatype(format!(
                         "Unsupported datatype: {:?}",
@@ -125,6 +195,8 @@ pub enum CellFormat {
     String(StringFormat),
     /// bool format
     Bool(BoolFormat),
+    /// format of each numeric cell chosen by the first rule whose predicate matches its value
+    Conditional(Vec<(Predicate, CellFormat)>),
 }
 
 impl CellFormat {
@@ -135,6 +207,9 @@ impl CellFormat {
             CellFormat::String(fmt) => CellFormat::String(fmt.min_width(min_width)),
             CellFormat::Binary(fmt) => CellFormat::Binary(fmt.min_width(min_width)),
             CellFormat::Bool(fmt) => CellFormat::Bool(fmt.min_width(min_width)),
+            CellFormat::Conditional(rules) => CellFormat::Conditional(
+                rules.into_iter().map(|(p, fmt)| (p, fmt.min_width(min_width))).collect(),
+            ),
         }
     }
 
@@ -145,6 +220,9 @@ impl CellFormat {
             CellFormat::String(fmt) => CellFormat::String(fmt.max_width(max_width)),
             CellFormat::Binary(fmt) => CellFormat::Binary(fmt.max_width(max_width)),
             CellFormat::Bool(fmt) => CellFormat::Bool(fmt.max_width(max_width)),
+            CellFormat::Conditional(rules) => CellFormat::Conditional(
+                rules.into_iter().map(|(p, fmt)| (p, fmt.max_width(max_width))).collect(),
+            ),
         }
     }
 
@@ -155,6 +233,9 @@ impl CellFormat {
             CellFormat::String(fmt) => Some(fmt.min_width),
             CellFormat::Binary(fmt) => Some(fmt.min_width),
             CellFormat::Bool(fmt) => Some(fmt.min_width),
+            CellFormat::Conditional(rules) => {
+                rules.iter().filter_map(|(_, fmt)| fmt.get_min_width()).max()
+            }
         }
     }
 
@@ -165,10 +246,27 @@ impl CellFormat {
             CellFormat::String(fmt) => Some(fmt.max_width),
             CellFormat::Binary(fmt) => Some(fmt.max_width),
             CellFormat::Bool(fmt) => Some(fmt.max_width),
+            CellFormat::Conditional(rules) => {
+                rules.iter().filter_map(|(_, fmt)| fmt.get_max_width()).max()
+            }
         }
     }
 }
 
+/// index of rule used for each value, the fallback for nulls
+pub(crate) fn choose_rules<T>(
+    rules: &[(Predicate, T)],
+    values: impl Iterator<Item = Option<f64>>,
+) -> Vec<usize> {
+    let fallback = rules.len().saturating_sub(1);
+    values
+        .map(|value| match value {
+            Some(value) => rules.iter().position(|(p, _)| p.matches(value)).unwrap_or(fallback),
+            None => fallback,
+        })
+        .collect()
+}
+
 impl TryInto<NumberFormat> for CellFormat {
     type Error = FormatError;
 
@@ -212,3 +310,12 @@ impl TryInto<BoolFormat> for CellFormat {
         }
     }
 }
+
+/// seconds in one tick of a polars duration
+pub fn duration_unit_seconds(unit: &TimeUnit) -> f64 {
+    match unit {
+        TimeUnit::Nanoseconds => 1e-9,
+        TimeUnit::Microseconds => 1e-6,
+        TimeUnit::Milliseconds => 1e-3,
+    }
+}
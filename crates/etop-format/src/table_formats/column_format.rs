@@ -1,7 +1,12 @@
 use crate::{
-    BinaryFormat, BoolFormat, CellFormat, CellFormatShorthand, FormatError, NumberFormat,
-    StringFormat, UnknownFormat,
+    compute_column_stats, BinaryFormat, BoolFormat, CellFormat, CellFormatShorthand, ColumnStats,
+    FormatError, FormatType, NumberAlign, NumberFormat, StringFormat, UnknownFormat,
+    NON_FINITE_PLACEHOLDER, ORDER_OF_MAGNITUDE_SUFFIXES, PREFIXES,
 };
+#[cfg(test)]
+#[path = "column_format_tests.rs"]
+mod tests;
+
 use polars::prelude::*;
 use unicode_truncate::{Alignment, UnicodeTruncateStr};
 
@@ -16,6 +21,18 @@ pub struct ColumnFormatShorthand {
     pub format: CellFormatShorthand,
     /// alignment
     pub align: ColumnAlign,
+    /// styles of top ranked rows
+    pub rank_highlight: Option<RankHighlight>,
+    /// redaction applied to formatted cells
+    pub redact: Option<RedactStyle>,
+    /// rows whose cells are prefixed with CELL_MARKER
+    pub marked_rows: Vec<usize>,
+    /// align numbers and their units separately, so that units line up
+    pub align_suffixes: bool,
+    /// bar drawn before each number, proportional to its value
+    pub data_bar: Option<DataBarStyle>,
+    /// comparator used when sorting by column, None to sort by its values
+    pub sort_as: Option<SortKind>,
 }
 
 impl ColumnFormatShorthand {
@@ -26,18 +43,30 @@ impl ColumnFormatShorthand {
             display_name: self.display_name,
             format: self.format.finalize(dtype)?,
             align: self.align,
+            rank_highlight: self.rank_highlight,
+            redact: self.redact,
+            marked_rows: self.marked_rows,
+            align_suffixes: self.align_suffixes,
+            data_bar: self.data_bar,
+            sort_as: self.sort_as,
         })
     }
 }
 
 impl Default for ColumnFormatShorthand {
     fn default() -> ColumnFormatShorthand {
-        let format = UnknownFormat { min_width: None, max_width: None };
+        let format = UnknownFormat::new();
         ColumnFormatShorthand {
             name: "".to_string(),
             display_name: "".to_string(),
             format: CellFormatShorthand::Unknown(format),
             align: ColumnAlign::Right,
+            rank_highlight: None,
+            redact: None,
+            marked_rows: vec![],
+            align_suffixes: false,
+            data_bar: None,
+            sort_as: None,
         }
     }
 }
@@ -53,6 +82,204 @@ pub struct ColumnFormat {
     pub format: CellFormat,
     /// alignment
     pub align: ColumnAlign,
+    /// styles of top ranked rows
+    pub rank_highlight: Option<RankHighlight>,
+    /// redaction applied to formatted cells
+    pub redact: Option<RedactStyle>,
+    /// rows whose cells are prefixed with CELL_MARKER
+    pub marked_rows: Vec<usize>,
+    /// align numbers and their units separately, so that units line up
+    pub align_suffixes: bool,
+    /// bar drawn before each number, proportional to its value
+    pub data_bar: Option<DataBarStyle>,
+    /// comparator used when sorting by column, None to sort by its values
+    pub sort_as: Option<SortKind>,
+}
+
+/// glyph prefixed to marked cells, such as annotated cells
+pub const CELL_MARKER: char = '•';
+
+/// how to mask formatted cells, such as private addresses when screen sharing
+///
+/// a leading `0x` is kept, and the masked part is replaced by a fixed `****` so that its length
+/// is not revealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactStyle {
+    /// mask whole value
+    Full,
+    /// keep first n chars
+    KeepPrefix(usize),
+    /// keep first n and last m chars
+    KeepEnds(usize, usize),
+}
+
+const REDACT_MASK: &str = "****";
+
+impl RedactStyle {
+    /// mask value, masking fully if the kept chars would reveal the whole value
+    pub fn redact(&self, value: &str) -> String {
+        let (prefix, value) = match value.strip_prefix("0x") {
+            Some(rest) => ("0x", rest),
+            None => ("", value),
+        };
+        let chars: Vec<char> = value.chars().collect();
+        let (start, end) = match *self {
+            RedactStyle::Full => (0, 0),
+            RedactStyle::KeepPrefix(n) => (n, 0),
+            RedactStyle::KeepEnds(n, m) => (n, m),
+        };
+        if start + end >= chars.len() {
+            return format!("{}{}", prefix, REDACT_MASK)
+        }
+        let start: String = chars[..start].iter().collect();
+        let end: String = chars[chars.len() - end..].iter().collect();
+        format!("{}{}{}{}", prefix, start, REDACT_MASK, end)
+    }
+}
+
+/// width of data bars, in chars
+pub const DATA_BAR_WIDTH: usize = 8;
+
+/// eighth blocks, for drawing the ends of bars
+const EIGHTH_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// chars of data bars, which show the value of each cell as a bar, like spreadsheet data bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBarStyle {
+    /// block chars, with eighth blocks at the ends of bars that grow rightward
+    Blocks,
+    /// `#` chars
+    Ascii,
+}
+
+impl DataBarStyle {
+    /// bar of width chars, filled to the given fraction of its width
+    ///
+    /// if signed, the bar starts from its center, growing leftward for negative fractions and
+    /// rightward for positive fractions
+    pub fn render(&self, fraction: f64, width: usize, signed: bool) -> String {
+        if !signed {
+            return self.fill(fraction.max(0.0), width)
+        }
+        // bars growing leftward end in whole chars, as eighth blocks are left aligned
+        let left = width / 2;
+        let n_negative = ((-fraction).clamp(0.0, 1.0) * left as f64).round() as usize;
+        let full = match self {
+            DataBarStyle::Blocks => "█",
+            DataBarStyle::Ascii => "#",
+        };
+        format!(
+            "{}{}{}",
+            " ".repeat(left - n_negative),
+            full.repeat(n_negative),
+            self.fill(fraction.max(0.0), width - left)
+        )
+    }
+
+    /// left aligned bar filled to fraction of width chars
+    fn fill(&self, fraction: f64, width: usize) -> String {
+        let mut bar = String::with_capacity(width);
+        match self {
+            DataBarStyle::Blocks => {
+                let eighths = (fraction.min(1.0) * (width * 8) as f64).round() as usize;
+                bar.push_str("█".repeat(eighths / 8).as_str());
+                if eighths % 8 > 0 {
+                    bar.push(EIGHTH_BLOCKS[eighths % 8]);
+                }
+            }
+            DataBarStyle::Ascii => {
+                let n = (fraction.min(1.0) * width as f64).round() as usize;
+                bar.push_str("#".repeat(n).as_str());
+            }
+        }
+        let n_chars = bar.chars().count();
+        bar.push_str(" ".repeat(width - n_chars).as_str());
+        bar
+    }
+}
+
+/// fraction of data bar filled by value, relative to the largest magnitude in the column and
+/// negative for negative values
+pub fn data_bar_fraction(value: f64, stats: &ColumnStats) -> f64 {
+    let scale = stats.max.unwrap_or(0.0).abs().max(stats.min.unwrap_or(0.0).abs());
+    if scale == 0.0 || !value.is_finite() {
+        0.0
+    } else {
+        (value / scale).clamp(-1.0, 1.0)
+    }
+}
+
+/// styles applied to the rows holding the top n values of a column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankHighlight {
+    /// number of top ranks highlighted
+    pub n: usize,
+    /// style of each rank, such as a color name, the last style is reused for lower ranks
+    pub styles: Vec<String>,
+}
+
+/// comparator used when sorting by a column, applied through a derived sort key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKind {
+    /// compare text byte by byte
+    Lexical,
+    /// compare as numbers, sorting values that do not parse as nulls
+    Numeric,
+    /// compare runs of digits as numbers, so that `item2` sorts before `item10`
+    Natural,
+    /// compare length of text in bytes
+    ByteLength,
+}
+
+impl SortKind {
+    /// key that sorts in the order of this comparator, named like series
+    pub fn sort_key(&self, series: &Series) -> Result<Series, FormatError> {
+        if *self == SortKind::Numeric && series.dtype().is_numeric() {
+            return Ok(series.cast(&DataType::Float64)?)
+        }
+        let text = series.cast(&DataType::Utf8)?;
+        let text = text.utf8()?;
+        let key = match self {
+            SortKind::Lexical => text.clone().into_series(),
+            SortKind::Numeric => text
+                .into_iter()
+                .map(|v| v.and_then(|v| v.trim().parse::<f64>().ok()))
+                .collect::<Float64Chunked>()
+                .into_series(),
+            SortKind::Natural => {
+                text.into_iter().map(|v| v.map(natural_key)).collect::<Utf8Chunked>().into_series()
+            }
+            SortKind::ByteLength => text
+                .into_iter()
+                .map(|v| v.map(|v| v.len() as u64))
+                .collect::<UInt64Chunked>()
+                .into_series(),
+        };
+        Ok(key.with_name(series.name()))
+    }
+}
+
+/// text with each run of digits prefixed by its length, leading zeros dropped, so that
+/// comparing keys compares the runs as numbers
+fn natural_key(value: &str) -> String {
+    let mut key = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !c.is_ascii_digit() {
+            key.push(c);
+            continue
+        }
+        let mut run = c.to_string();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            run.push(digit);
+        }
+        let run = match run.trim_start_matches('0') {
+            "" => "0",
+            run => run,
+        };
+        key.push_str(format!("{:03}{}", run.len(), run).as_str());
+    }
+    key
 }
 
 /// column alignment
@@ -67,18 +294,16 @@ pub enum ColumnAlign {
 impl ColumnFormat {
     /// get header width
     pub fn header_width(&self) -> usize {
-        self.display_name
-            .split('\n')
-            // .map(unicode_width::UnicodeWidthStr::width)
-            // .map(unicode_width::UnicodeWidthStr::width_cjk)
-            .map(|s| s.chars().count())
-            .max()
-            .unwrap_or(0)
+        self.display_name.split('\n').map(unicode_width::UnicodeWidthStr::width).max().unwrap_or(0)
     }
 
     /// get min width
     pub fn get_min_width(&self) -> usize {
-        self.format.get_min_width().unwrap_or(0)
+        let min_width = self.format.get_min_width().unwrap_or(0);
+        match self.data_bar {
+            Some(_) => min_width.max(DATA_BAR_WIDTH + 2),
+            None => min_width,
+        }
     }
 
     /// get max width
@@ -88,6 +313,10 @@ impl ColumnFormat {
 
     /// format series
     pub fn format(&self, series: &Series) -> Result<Vec<String>, FormatError> {
+        // durations are formatted from their integer ticks, scaled by the cell format
+        if let DataType::Duration(_) = series.dtype() {
+            return self.format(&series.cast(&DataType::Int64)?)
+        }
         let formatted: Result<Vec<String>, FormatError> = match series.dtype() {
             DataType::Binary => {
                 let fmt: BinaryFormat = self.format.clone().try_into()?;
@@ -95,11 +324,35 @@ impl ColumnFormat {
             }
             DataType::Utf8 => {
                 let fmt: StringFormat = self.format.clone().try_into()?;
-                series.utf8()?.into_iter().map(|v| fmt.format_option(v, "")).collect()
+                series.utf8()?.into_iter().map(|v| fmt.format_option(v, &fmt.null_repr)).collect()
+            }
+            dtype if dtype.is_numeric() && matches!(self.format, CellFormat::Conditional(_)) => {
+                self.format_conditional(series)
             }
             dtype if dtype.is_numeric() => {
-                let fmt: NumberFormat = self.format.clone().try_into()?;
-                series.to_float()?.f64()?.into_iter().map(|v| fmt.format_option(v, "")).collect()
+                let mut fmt: NumberFormat = self.format.clone().try_into()?;
+                if self.data_bar.is_some() {
+                    // leave room for bar and the space after it
+                    fmt.min_width = fmt.min_width.saturating_sub(DATA_BAR_WIDTH + 1);
+                    fmt.max_width = fmt.max_width.saturating_sub(DATA_BAR_WIDTH + 1).max(1);
+                }
+                let values = series.to_float()?;
+                let formatted: Result<Vec<String>, FormatError> = values
+                    .f64()?
+                    .into_iter()
+                    .map(|v| match v {
+                        Some(v) if v.is_infinite() => Ok(NON_FINITE_PLACEHOLDER.to_string()),
+                        v => fmt.format_option(v, &fmt.null_repr),
+                    })
+                    .collect();
+                let formatted = match self.align_suffixes {
+                    true => formatted.map(|cells| align_suffixes(cells, &values, &fmt)),
+                    false => formatted,
+                };
+                match self.data_bar {
+                    Some(style) => formatted.and_then(|cells| data_bars(cells, series, style)),
+                    None => formatted,
+                }
             }
             DataType::Boolean => {
                 let fmt: BoolFormat = self.format.clone().try_into()?;
@@ -110,13 +363,31 @@ impl ColumnFormat {
                 return Err(FormatError::UnsupportedDatatype(message));
             }
         };
-        let formatted = formatted?;
+        let mut formatted = formatted?;
+
+        // redact non-null cells
+        if let Some(redact) = self.redact.as_ref() {
+            for (cell, is_null) in formatted.iter_mut().zip(series.is_null().into_iter()) {
+                if !is_null.unwrap_or(false) {
+                    *cell = redact.redact(cell);
+                }
+            }
+        }
+
+        // mark cells, in place of the padding before the value if there is any
+        for row in self.marked_rows.iter() {
+            if let Some(cell) = formatted.get_mut(*row) {
+                let start = cell.len() - cell.trim_start().len();
+                match start {
+                    0 => cell.insert(0, CELL_MARKER),
+                    _ => cell.replace_range(start - 1..start, CELL_MARKER.to_string().as_str()),
+                }
+            }
+        }
 
         let max_width = formatted
             .iter()
-            // .map(|s| unicode_width::UnicodeWidthStr::width(s.as_str()))
-            // .map(|s| unicode_width::UnicodeWidthStr::width_cjk(s.as_str()))
-            .map(|s| s.chars().count())
+            .map(|s| unicode_width::UnicodeWidthStr::width(s.as_str()))
             .max()
             .unwrap_or(0);
 
@@ -134,6 +405,155 @@ impl ColumnFormat {
 
         Ok(formatted)
     }
+
+    /// format each cell with the first rule of a conditional format that matches its value
+    fn format_conditional(&self, series: &Series) -> Result<Vec<String>, FormatError> {
+        let rules = match &self.format {
+            CellFormat::Conditional(rules) => rules,
+            _ => return Err(FormatError::MismatchedFormatType("not conditional".to_string())),
+        };
+        let values = series.to_float()?;
+        let choices = super::cell_format::choose_rules(rules, values.f64()?.into_iter());
+        let mut cells = vec![String::new(); series.len()];
+        for (r, (_, format)) in rules.iter().enumerate() {
+            if !choices.contains(&r) {
+                continue
+            }
+            // marks and redaction are applied once to the combined cells
+            let rule = ColumnFormat {
+                format: format.clone(),
+                redact: None,
+                marked_rows: vec![],
+                ..self.clone()
+            };
+            for ((cell, choice), formatted) in
+                cells.iter_mut().zip(&choices).zip(rule.format(series)?)
+            {
+                if *choice == r {
+                    *cell = formatted;
+                }
+            }
+        }
+        Ok(cells)
+    }
+
+    /// rank of each value, with 1 for the largest value, tied values sharing a rank, and None
+    /// for nulls and non-finite values
+    pub fn ranks(&self, series: &Series) -> Result<Vec<Option<usize>>, FormatError> {
+        if !series.dtype().is_numeric() {
+            let message =
+                format!("cannot rank column {} of type {}", series.name(), series.dtype());
+            return Err(FormatError::UnsupportedDatatype(message));
+        }
+        let values: Vec<Option<f64>> =
+            series.to_float()?.f64()?.into_iter().map(|v| v.filter(|v| v.is_finite())).collect();
+        let mut sorted: Vec<f64> = values.iter().flatten().copied().collect();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        let ranks = values
+            .into_iter()
+            .map(|value| value.map(|v| sorted.partition_point(|x| x.total_cmp(&v).is_gt()) + 1))
+            .collect();
+        Ok(ranks)
+    }
+
+    /// style of each row according to the rank of its value, None outside the top n ranks
+    pub fn rank_styles(&self, series: &Series) -> Result<Vec<Option<String>>, FormatError> {
+        let highlight = match &self.rank_highlight {
+            Some(highlight) if !highlight.styles.is_empty() => highlight,
+            _ => return Ok(vec![None; series.len()]),
+        };
+        let styles = self
+            .ranks(series)?
+            .into_iter()
+            .map(|rank| match rank {
+                Some(rank) if rank <= highlight.n => {
+                    Some(highlight.styles[(rank - 1).min(highlight.styles.len() - 1)].clone())
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(styles)
+    }
+}
+
+/// prefix each cell with a bar of its value, leaving a blank bar for null and non-finite values
+fn data_bars(
+    cells: Vec<String>,
+    series: &Series,
+    style: DataBarStyle,
+) -> Result<Vec<String>, FormatError> {
+    let stats = compute_column_stats(series)?;
+    let signed = stats.min.map(|min| min < 0.0).unwrap_or(false);
+    let values = series.to_float()?;
+    let width = cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0);
+    let cells = cells
+        .into_iter()
+        .zip(values.f64()?)
+        .map(|(cell, value)| {
+            let fraction = value.map(|v| data_bar_fraction(v, &stats)).unwrap_or(0.0);
+            let bar = style.render(fraction, DATA_BAR_WIDTH, signed);
+            format!("{} {}", bar, cell.unicode_pad(width, Alignment::Right, true))
+        })
+        .collect();
+    Ok(cells)
+}
+
+/// right align the numeric part of finite cells and left align the text after it, such as
+/// units, so that both line up across the column
+///
+/// the text after a number is the suffix of its format, along with any SI or order of magnitude
+/// unit, and the aligned cells are padded to the min width of the format with its alignment
+fn align_suffixes(cells: Vec<String>, values: &Series, fmt: &NumberFormat) -> Vec<String> {
+    let is_number: Vec<bool> = match values.f64() {
+        Ok(values) => {
+            values.into_iter().map(|v| v.map(|v| v.is_finite()).unwrap_or(false)).collect()
+        }
+        Err(_) => return cells,
+    };
+    let units: &[&str] = match fmt.format_type {
+        FormatType::SI => &PREFIXES,
+        FormatType::IntegerOrderOfMagnitude | FormatType::FloatOrderOfMagnitude => {
+            &ORDER_OF_MAGNITUDE_SUFFIXES
+        }
+        _ => &[],
+    };
+    let split = |cell: &str| -> (String, String) {
+        let cell = cell.trim();
+        let rest = cell.strip_suffix(fmt.suffix.as_str()).unwrap_or(cell);
+        let unit = units.iter().find(|unit| !unit.is_empty() && rest.ends_with(*unit));
+        let number = match unit {
+            Some(unit) => &rest[..rest.len() - unit.len()],
+            None => rest,
+        };
+        (number.to_string(), cell[number.len()..].to_string())
+    };
+    let parts: Vec<(String, String)> = cells.iter().map(|cell| split(cell)).collect();
+    let numbers = || parts.iter().zip(is_number.iter()).filter(|(_, n)| **n).map(|(p, _)| p);
+    let number_width = numbers().map(|(n, _)| n.chars().count()).max().unwrap_or(0);
+    let suffix_width = numbers().map(|(_, s)| s.chars().count()).max().unwrap_or(0);
+    let alignment = match fmt.align {
+        NumberAlign::Left => Alignment::Left,
+        NumberAlign::Center => Alignment::Center,
+        NumberAlign::Right | NumberAlign::SignedRight => Alignment::Right,
+    };
+    let width = (number_width + suffix_width).max(fmt.min_width);
+    cells
+        .into_iter()
+        .zip(parts)
+        .zip(is_number)
+        .map(|((cell, (number, suffix)), is_number)| match is_number {
+            true => format!(
+                "{:>number_width$}{:<suffix_width$}",
+                number,
+                suffix,
+                number_width = number_width,
+                suffix_width = suffix_width
+            )
+            .unicode_pad(width, alignment, false)
+            .to_string(),
+            false => cell,
+        })
+        .collect()
 }
 
 // builder
@@ -176,6 +596,43 @@ impl ColumnFormat {
         self.format = self.format.max_width(width);
         self
     }
+
+    /// highlight rows holding the top n values with the given styles
+    pub fn rank_highlight(mut self, n: usize, styles: Vec<String>) -> ColumnFormat {
+        self.rank_highlight = Some(RankHighlight { n, styles });
+        self
+    }
+
+    /// align numbers and their units separately, so that units line up
+    pub fn align_suffixes(mut self) -> ColumnFormat {
+        self.align_suffixes = true;
+        self
+    }
+
+    /// prefix cells of given rows with CELL_MARKER
+    pub fn marked_rows(mut self, rows: Vec<usize>) -> ColumnFormat {
+        self.marked_rows = rows;
+        self
+    }
+
+    /// redact formatted cells
+    pub fn redact(mut self, style: RedactStyle) -> ColumnFormat {
+        self.redact = Some(style);
+        self
+    }
+
+    /// draw bar before each number, proportional to its value relative to the largest
+    /// magnitude of the column
+    pub fn data_bar(mut self, style: DataBarStyle) -> ColumnFormat {
+        self.data_bar = Some(style);
+        self
+    }
+
+    /// sort by column using the given comparator
+    pub fn sort_as(mut self, kind: SortKind) -> ColumnFormat {
+        self.sort_as = Some(kind);
+        self
+    }
 }
 
 // builder
@@ -224,6 +681,43 @@ impl ColumnFormatShorthand {
         self
     }
 
+    /// highlight rows holding the top n values with the given styles
+    pub fn rank_highlight(mut self, n: usize, styles: Vec<String>) -> ColumnFormatShorthand {
+        self.rank_highlight = Some(RankHighlight { n, styles });
+        self
+    }
+
+    /// align numbers and their units separately, so that units line up
+    pub fn align_suffixes(mut self) -> ColumnFormatShorthand {
+        self.align_suffixes = true;
+        self
+    }
+
+    /// prefix cells of given rows with CELL_MARKER
+    pub fn marked_rows(mut self, rows: Vec<usize>) -> ColumnFormatShorthand {
+        self.marked_rows = rows;
+        self
+    }
+
+    /// redact formatted cells
+    pub fn redact(mut self, style: RedactStyle) -> ColumnFormatShorthand {
+        self.redact = Some(style);
+        self
+    }
+
+    /// draw bar before each number, proportional to its value relative to the largest
+    /// magnitude of the column
+    pub fn data_bar(mut self, style: DataBarStyle) -> ColumnFormatShorthand {
+        self.data_bar = Some(style);
+        self
+    }
+
+    /// sort by column using the given comparator
+    pub fn sort_as(mut self, kind: SortKind) -> ColumnFormatShorthand {
+        self.sort_as = Some(kind);
+        self
+    }
+
     /// set format
     pub fn set_format<T: Into<CellFormatShorthand>>(mut self, format: T) -> ColumnFormatShorthand {
         self.format = format.into();
@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod column_format_tests {
+    use crate::{
+        data_bar_fraction, BigIntFormat, CellFormat, CellFormatShorthand, ColumnFormatShorthand,
+        ColumnStats, DataBarStyle, FormatType, NumberFormat, Predicate, RedactStyle, SortKind,
+        StringFormat, UnknownFormat, CELL_MARKER,
+    };
+    use polars::prelude::*;
+
+    #[test]
+    fn ranks_with_ties() {
+        let series = Series::new("x", [5.0, 9.0, 7.0, 9.0, 1.0]);
+        let fmt = ColumnFormatShorthand::new().name("x").finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.ranks(&series).unwrap(), vec![Some(4), Some(1), Some(3), Some(1), Some(5)]);
+
+        let series = Series::new("x", [Some(2u64), None, Some(2), Some(3)]);
+        assert_eq!(fmt.ranks(&series).unwrap(), vec![Some(2), None, Some(2), Some(1)]);
+
+        assert!(fmt.ranks(&Series::new("x", ["a", "b"])).is_err());
+    }
+
+    #[test]
+    fn rank_styles() {
+        let podium = vec!["gold".to_string(), "silver".to_string(), "bronze".to_string()];
+        let series = Series::new("x", [5u32, 9, 7, 9, 1]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .rank_highlight(3, podium)
+            .finalize(series.dtype())
+            .unwrap();
+        let styles = fmt.rank_styles(&series).unwrap();
+        let styles: Vec<Option<&str>> = styles.iter().map(|s| s.as_deref()).collect();
+        assert_eq!(styles, vec![None, Some("gold"), Some("bronze"), Some("gold"), None]);
+
+        let plain = ColumnFormatShorthand::new().name("x").finalize(series.dtype()).unwrap();
+        assert_eq!(plain.rank_styles(&series).unwrap(), vec![None; 5]);
+    }
+
+    #[test]
+    fn redact_styles() {
+        let address = "0x1234567890abcdef";
+        assert_eq!(RedactStyle::Full.redact(address), "0x****");
+        assert_eq!(RedactStyle::KeepPrefix(4).redact(address), "0x1234****");
+        assert_eq!(RedactStyle::KeepEnds(4, 2).redact(address), "0x1234****ef");
+        assert_eq!(RedactStyle::Full.redact("alice"), "****");
+        assert_eq!(RedactStyle::KeepPrefix(2).redact("alice"), "al****");
+
+        // kept chars would reveal everything
+        assert_eq!(RedactStyle::KeepEnds(3, 3).redact("0xabcdef"), "0x****");
+        assert_eq!(RedactStyle::KeepPrefix(9).redact("alice"), "****");
+    }
+
+    #[test]
+    fn redact_columns() {
+        let binary = Series::new("x", [Some(vec![0x12u8, 0x34, 0x56]), None]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .redact(RedactStyle::KeepPrefix(2))
+            .finalize(binary.dtype())
+            .unwrap();
+        assert_eq!(fmt.format(&binary).unwrap(), vec!["0x12****", "        "]);
+
+        let string = Series::new("x", ["alice", "bob"]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .redact(RedactStyle::Full)
+            .finalize(string.dtype())
+            .unwrap();
+        assert_eq!(fmt.format(&string).unwrap(), vec!["****", "****"]);
+    }
+
+    #[test]
+    fn marked_cells() {
+        let series = Series::new("x", [1u32, 22, 3]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .marked_rows(vec![1, 7])
+            .finalize(series.dtype())
+            .unwrap();
+        let marked = format!("{}22", CELL_MARKER);
+        assert_eq!(fmt.format(&series).unwrap(), vec!["  1", marked.as_str(), "  3"]);
+    }
+
+    #[test]
+    fn align_suffixes() {
+        let series = Series::new("x", [Some(5.0), Some(120.0), None, Some(12345.0)]);
+        let gwei = NumberFormat::new().precision(0).suffix(" gwei").left_align().min_width(12);
+        let column = |align: bool| {
+            let fmt = ColumnFormatShorthand::new().name("x").set_format(gwei.clone());
+            let fmt = if align { fmt.align_suffixes() } else { fmt };
+            fmt.finalize(series.dtype()).unwrap().format(&series).unwrap()
+        };
+
+        // units are ragged when numbers are padded as a whole
+        assert_eq!(column(false)[0], "5 gwei      ");
+        // aligned cells keep the min width and alignment of the format
+        assert_eq!(
+            column(true),
+            vec!["    5 gwei  ", "  120 gwei  ", "           -", "12345 gwei  "]
+        );
+
+        // numbers line up even when units differ in width
+        let series = Series::new("x", [12u64, 15_000, 2_300_000]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .set_format(NumberFormat::new().integer_oom().precision(1))
+            .align_suffixes()
+            .finalize(series.dtype())
+            .unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["  12 ", "15.0K", " 2.3M"]);
+
+        // digits of suffixes and exponents are not taken for the number
+        let series = Series::new("x", [3.0, 250.0]);
+        let area = NumberFormat::new().precision(0).suffix(" m2");
+        let fmt = ColumnFormatShorthand::new().name("x").set_format(area).align_suffixes();
+        let fmt = fmt.finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["  3 m2", "250 m2"]);
+        let series = Series::new("x", [-1.5, 25_000.0]);
+        let exponent = NumberFormat::new().scientific_notation().precision(1).suffix(" eth");
+        let fmt = ColumnFormatShorthand::new().name("x").set_format(exponent).align_suffixes();
+        let fmt = fmt.finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["-1.5e+00 eth", " 2.5e+04 eth"]);
+    }
+
+    #[test]
+    fn data_bar_fractions() {
+        let stats = ColumnStats { min: Some(0.0), max: Some(10.0), ..Default::default() };
+        assert_eq!(data_bar_fraction(0.0, &stats), 0.0);
+        assert_eq!(data_bar_fraction(2.5, &stats), 0.25);
+        assert_eq!(data_bar_fraction(10.0, &stats), 1.0);
+
+        // negative values scale by the largest magnitude
+        let stats = ColumnStats { min: Some(-20.0), max: Some(10.0), ..Default::default() };
+        assert_eq!(data_bar_fraction(-20.0, &stats), -1.0);
+        assert_eq!(data_bar_fraction(0.0, &stats), 0.0);
+        assert_eq!(data_bar_fraction(10.0, &stats), 0.5);
+
+        assert_eq!(data_bar_fraction(f64::NAN, &stats), 0.0);
+        assert_eq!(data_bar_fraction(1.0, &ColumnStats::default()), 0.0);
+    }
+
+    #[test]
+    fn sort_kinds() {
+        let series =
+            Series::new("id", [Some("item10"), Some("item2"), None, Some("x"), Some("07")]);
+        let sorted = |kind: SortKind| -> Vec<Option<String>> {
+            let key = kind.sort_key(&series).unwrap();
+            assert_eq!(key.name(), "id");
+            let order = key.arg_sort(SortOptions { nulls_last: true, ..Default::default() });
+            let sorted = series.take(&order).unwrap();
+            sorted.utf8().unwrap().into_iter().map(|v| v.map(String::from)).collect()
+        };
+        let expected = |values: [Option<&str>; 5]| -> Vec<Option<String>> {
+            values.iter().map(|v| v.map(String::from)).collect()
+        };
+
+        assert_eq!(
+            sorted(SortKind::Lexical),
+            expected([Some("07"), Some("item10"), Some("item2"), Some("x"), None])
+        );
+        assert_eq!(
+            sorted(SortKind::Natural),
+            expected([Some("07"), Some("item2"), Some("item10"), Some("x"), None])
+        );
+        assert_eq!(
+            sorted(SortKind::ByteLength),
+            expected([Some("x"), Some("07"), Some("item2"), Some("item10"), None])
+        );
+
+        // text that does not parse as a number sorts as null
+        let ids = Series::new("id", ["10", "9", "x", "100"]);
+        let key = SortKind::Numeric.sort_key(&ids).unwrap();
+        assert_eq!(
+            key.f64().unwrap().into_iter().collect::<Vec<_>>(),
+            [Some(10.0), Some(9.0), None, Some(100.0)]
+        );
+
+        // numeric columns sort by value
+        let numbers = Series::new("n", [10u32, 9, 100]);
+        let key = SortKind::Numeric.sort_key(&numbers).unwrap();
+        assert_eq!(
+            key.f64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+            vec![10.0, 9.0, 100.0]
+        );
+    }
+
+    #[test]
+    fn empty_and_null_strings() {
+        let series = Series::new("memo", [Some("gm"), Some(""), None]);
+        let fmt = ColumnFormatShorthand::new().name("memo").finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["gm", " ∅", " -"]);
+
+        let strings = StringFormat::new().empty_repr("\"\"").null_repr("null");
+        let fmt = ColumnFormatShorthand::new().name("memo").set_format(strings);
+        let fmt = fmt.finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["  gm", "  \"\"", "null"]);
+    }
+
+    #[test]
+    fn null_and_nan_numbers() {
+        let series = Series::new("ratio", [Some(1.5), None, Some(f64::NAN)]);
+        let numbers = NumberFormat::new().precision(1);
+        let fmt = ColumnFormatShorthand::new().name("ratio").set_format(numbers);
+        let fmt = fmt.finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["1.5", "  -", "  -"]);
+    }
+
+    #[test]
+    fn big_int_strings() {
+        let series = Series::new("value", [Some("12345678901234567890123"), Some("n/a"), None]);
+        let strings = StringFormat::new().big_int(BigIntFormat::new().decimals(18).precision(2));
+        let fmt = ColumnFormatShorthand::new().name("value").set_format(strings);
+        let fmt = fmt.finalize(series.dtype()).unwrap();
+        assert_eq!(fmt.format(&series).unwrap(), vec!["12,345.68", "      n/a", "        -"]);
+    }
+
+    #[test]
+    fn data_bar_render() {
+        assert_eq!(DataBarStyle::Blocks.render(1.0, 4, false), "████");
+        assert_eq!(DataBarStyle::Blocks.render(0.0, 4, false), "    ");
+        assert_eq!(DataBarStyle::Blocks.render(0.5625, 4, false), "██▎ ");
+        assert_eq!(DataBarStyle::Ascii.render(0.5, 4, false), "##  ");
+
+        // signed bars grow from the center
+        assert_eq!(DataBarStyle::Ascii.render(-1.0, 8, true), "####    ");
+        assert_eq!(DataBarStyle::Ascii.render(-0.5, 8, true), "  ##    ");
+        assert_eq!(DataBarStyle::Ascii.render(0.5, 8, true), "    ##  ");
+    }
+
+    #[test]
+    fn data_bar_cells() {
+        let series = Series::new("x", [Some(10u32), Some(5), Some(0), None]);
+        let fmt = ColumnFormatShorthand::new()
+            .name("x")
+            .set_format(NumberFormat::new().precision(0))
+            .data_bar(DataBarStyle::Ascii)
+            .finalize(series.dtype())
+            .unwrap();
+        assert_eq!(
+            fmt.format(&series).unwrap(),
+            vec!["######## 10", "####      5", "          0", "          -"]
+        );
+        assert_eq!(fmt.get_min_width(), 10);
+    }
+
+    #[test]
+    fn duration_cells() {
+        let ms = Series::new("elapsed", [3_725_000i64, -90_000, 999]);
+        let series = ms.cast(&DataType::Duration(TimeUnit::Milliseconds)).unwrap();
+        let fmt = ColumnFormatShorthand::new().name("elapsed").finalize(series.dtype()).unwrap();
+        let cells: Vec<String> =
+            fmt.format(&series).unwrap().into_iter().map(|s| s.trim().to_string()).collect();
+        assert_eq!(cells, vec!["01:02:05", "-00:01:30", "00:00:01"]);
+
+        let fmt = ColumnFormatShorthand::new()
+            .name("elapsed")
+            .set_format(NumberFormat::new().short_duration().scale(1e-3))
+            .finalize(series.dtype())
+            .unwrap();
+        let cells: Vec<String> =
+            fmt.format(&series).unwrap().into_iter().map(|s| s.trim().to_string()).collect();
+        assert_eq!(cells, vec!["1h 2m", "-1m 30s", "999ms"]);
+    }
+
+    #[test]
+    fn conditional_cells() {
+        let series = Series::new("value", [Some(2_500_000.0), Some(0.125), None, Some(1e6)]);
+        let si = NumberFormat::new().si().precision(2);
+        let fixed = NumberFormat::new().format_type(&FormatType::FixedPoint).precision(3);
+        let rules = vec![(Predicate::Above(1e6), si.into()), (Predicate::Always, fixed.into())];
+        let fmt = ColumnFormatShorthand::new()
+            .name("value")
+            .set_format(CellFormatShorthand::conditional(rules).unwrap())
+            .finalize(series.dtype())
+            .unwrap();
+        let cells: Vec<String> =
+            fmt.format(&series).unwrap().into_iter().map(|s| s.trim().to_string()).collect();
+        assert_eq!(cells, vec!["2.5M", "0.125", "-", "1000000.000"]);
+
+        // rules need a fallback
+        let rules = vec![(Predicate::Above(1e6), NumberFormat::new().into())];
+        assert!(CellFormatShorthand::conditional(rules.clone()).is_err());
+        let fmt = ColumnFormatShorthand::new().set_format(CellFormatShorthand::Conditional(rules));
+        assert!(fmt.finalize(series.dtype()).is_err());
+    }
+
+    #[test]
+    fn unknown_format_widths() {
+        let unknown = UnknownFormat::new().min_width(6).max_width(8);
+        let finalize =
+            |dtype: DataType| CellFormatShorthand::from(unknown.clone()).finalize(&dtype);
+
+        match finalize(DataType::Utf8).unwrap() {
+            CellFormat::String(fmt) => assert_eq!((fmt.min_width, fmt.max_width), (6, 8)),
+            fmt => panic!("expected string format, got {:?}", fmt),
+        }
+        match finalize(DataType::Boolean).unwrap() {
+            CellFormat::Bool(fmt) => assert_eq!((fmt.min_width, fmt.max_width), (6, 8)),
+            fmt => panic!("expected bool format, got {:?}", fmt),
+        }
+        match finalize(DataType::Int64).unwrap() {
+            CellFormat::Number(fmt) => {
+                assert_eq!((fmt.min_width, fmt.max_width), (6, 8));
+                assert_eq!((fmt.format_type, fmt.precision), (FormatType::Decimal, 0));
+            }
+            fmt => panic!("expected number format, got {:?}", fmt),
+        }
+        match finalize(DataType::Float64).unwrap() {
+            CellFormat::Number(fmt) => assert_eq!(fmt.format_type, FormatType::Exponent),
+            fmt => panic!("expected number format, got {:?}", fmt),
+        }
+        assert!(finalize(DataType::List(Box::new(DataType::Int64))).is_err());
+
+        // widths are unset by default, and width sets both
+        let default = UnknownFormat::default();
+        assert_eq!((default.min_width, default.max_width), (None, None));
+        let fixed = UnknownFormat::new().width(4);
+        assert_eq!((fixed.min_width, fixed.max_width), (Some(4), Some(4)));
+    }
+}
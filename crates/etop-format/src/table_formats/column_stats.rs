@@ -0,0 +1,43 @@
+#[cfg(test)]
+#[path = "column_stats_tests.rs"]
+mod tests;
+
+use crate::FormatError;
+use polars::prelude::*;
+
+/// text shown in place of NaN and infinite values
+pub const NON_FINITE_PLACEHOLDER: &str = "-";
+
+/// summary statistics of a numeric column
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnStats {
+    /// smallest finite value
+    pub min: Option<f64>,
+    /// largest finite value
+    pub max: Option<f64>,
+    /// number of null values
+    pub null_count: usize,
+    /// number of NaN or infinite values, which are excluded from min and max
+    pub non_finite_count: usize,
+}
+
+/// compute statistics of numeric column, ignoring NaN and infinite values for min and max
+pub fn compute_column_stats(series: &Series) -> Result<ColumnStats, FormatError> {
+    if !series.dtype().is_numeric() {
+        let message =
+            format!("cannot compute stats of column {} of type {}", series.name(), series.dtype());
+        return Err(FormatError::UnsupportedDatatype(message))
+    }
+    let mut stats = ColumnStats::default();
+    for value in series.to_float()?.f64()?.into_iter() {
+        match value {
+            None => stats.null_count += 1,
+            Some(value) if !value.is_finite() => stats.non_finite_count += 1,
+            Some(value) => {
+                stats.min = Some(stats.min.map_or(value, |min| min.min(value)));
+                stats.max = Some(stats.max.map_or(value, |max| max.max(value)));
+            }
+        }
+    }
+    Ok(stats)
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod column_stats_tests {
+    use crate::{compute_column_stats, ColumnFormatShorthand, ColumnStats, NON_FINITE_PLACEHOLDER};
+    use polars::prelude::*;
+
+    fn mixed() -> Series {
+        Series::new(
+            "ratio",
+            [
+                Some(2.5),
+                Some(f64::NAN),
+                None,
+                Some(-1.0),
+                Some(f64::INFINITY),
+                Some(f64::NEG_INFINITY),
+            ],
+        )
+    }
+
+    #[test]
+    fn stats_ignore_non_finite() {
+        let stats = compute_column_stats(&mixed()).unwrap();
+        assert_eq!(
+            stats,
+            ColumnStats { min: Some(-1.0), max: Some(2.5), null_count: 1, non_finite_count: 3 }
+        );
+
+        let stats = compute_column_stats(&Series::new("x", [f64::NAN, f64::INFINITY])).unwrap();
+        assert_eq!(stats, ColumnStats { non_finite_count: 2, ..Default::default() });
+
+        let stats = compute_column_stats(&Series::new("x", [3u32, 7, 5])).unwrap();
+        assert_eq!((stats.min, stats.max, stats.non_finite_count), (Some(3.0), Some(7.0), 0));
+
+        assert!(compute_column_stats(&Series::new("x", ["a"])).is_err());
+    }
+
+    #[test]
+    fn non_finite_cells() {
+        let series = mixed();
+        let fmt = ColumnFormatShorthand::new().name("ratio").finalize(series.dtype()).unwrap();
+        let cells: Vec<String> =
+            fmt.format(&series).unwrap().into_iter().map(|s| s.trim().to_string()).collect();
+        assert_eq!(cells[1], NON_FINITE_PLACEHOLDER);
+        assert_eq!(cells[2], "-");
+        assert_eq!(cells[4], NON_FINITE_PLACEHOLDER);
+        assert_eq!(cells[5], NON_FINITE_PLACEHOLDER);
+        let width = cells.iter().map(|s| s.chars().count()).max().unwrap();
+        assert_eq!(fmt.format(&series).unwrap()[0].chars().count(), width);
+
+        // non-finite values are not ranked
+        assert_eq!(fmt.ranks(&series).unwrap(), vec![Some(1), None, None, Some(2), None, None]);
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+#[path = "column_widths_tests.rs"]
+mod tests;
+
+/// width requirements of a column sharing the render width with other columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ColumnWidthSpec {
+    /// narrowest width the column can be shown at
+    pub min_width: usize,
+    /// width the column takes when space allows, such as the width of its widest cell
+    pub desired_width: usize,
+}
+
+impl ColumnWidthSpec {
+    /// new spec, with desired width raised to at least min width
+    pub fn new(min_width: usize, desired_width: usize) -> ColumnWidthSpec {
+        ColumnWidthSpec { min_width, desired_width: desired_width.max(min_width) }
+    }
+
+    /// width the column can give up before reaching its min width
+    fn flex(&self) -> usize {
+        self.desired_width.saturating_sub(self.min_width)
+    }
+}
+
+/// distribute available width across columns, giving 0 to dropped columns
+///
+/// columns get their desired widths when they fit, otherwise each shrinks in proportion to how
+/// far its desired width is above its min width, like flexbox. columns are dropped from the
+/// right only when their min widths cannot all fit. available excludes the space between
+/// columns.
+pub fn allocate_widths(available: usize, columns: &[ColumnWidthSpec]) -> Vec<usize> {
+    let columns: Vec<ColumnWidthSpec> =
+        columns.iter().map(|c| ColumnWidthSpec::new(c.min_width, c.desired_width)).collect();
+
+    // keep the leading columns whose min widths fit
+    let mut n_kept = 0;
+    let mut total_min = 0;
+    for column in columns.iter() {
+        if total_min + column.min_width > available {
+            break
+        }
+        total_min += column.min_width;
+        n_kept += 1;
+    }
+    let kept = &columns[..n_kept];
+    let mut widths: Vec<usize> = vec![0; columns.len()];
+
+    let total_desired: usize = kept.iter().map(|c| c.desired_width).sum();
+    if total_desired <= available {
+        for (width, column) in widths.iter_mut().zip(kept) {
+            *width = column.desired_width;
+        }
+        return widths
+    }
+
+    // shrink each column by its share of the deficit, rounding down, then shrink the columns
+    // with the largest rounded off shares by one more
+    let deficit = total_desired - available;
+    let total_flex: usize = kept.iter().map(|c| c.flex()).sum();
+    let mut remainders: Vec<(usize, usize)> = Vec::with_capacity(n_kept);
+    let mut shrunk = 0;
+    for (c, column) in kept.iter().enumerate() {
+        let share = deficit * column.flex();
+        widths[c] = column.desired_width - share / total_flex;
+        shrunk += share / total_flex;
+        remainders.push((share % total_flex, c));
+    }
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, c) in remainders.into_iter().take(deficit - shrunk) {
+        widths[c] -= 1;
+    }
+    widths
+}
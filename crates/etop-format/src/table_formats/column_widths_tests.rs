@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod column_widths_tests {
+    use crate::{allocate_widths, ColumnWidthSpec};
+
+    fn specs(widths: &[(usize, usize)]) -> Vec<ColumnWidthSpec> {
+        widths.iter().map(|(min, desired)| ColumnWidthSpec::new(*min, *desired)).collect()
+    }
+
+    #[test]
+    fn under_constrained() {
+        // columns that fit take their desired widths, leaving the rest unused
+        let columns = specs(&[(3, 10), (5, 5), (2, 8)]);
+        assert_eq!(allocate_widths(30, &columns), vec![10, 5, 8]);
+        assert_eq!(allocate_widths(23, &columns), vec![10, 5, 8]);
+        assert_eq!(allocate_widths(100, &[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn proportional_shrink() {
+        // flex of 8 and 4, deficit of 6 split 4 and 2
+        let columns = specs(&[(2, 10), (4, 4), (4, 8)]);
+        assert_eq!(allocate_widths(16, &columns), vec![6, 4, 6]);
+
+        // fixed columns keep their width
+        let columns = specs(&[(6, 6), (2, 12), (6, 6)]);
+        assert_eq!(allocate_widths(20, &columns), vec![6, 8, 6]);
+
+        // at exactly the sum of min widths, every column is at its min
+        let columns = specs(&[(3, 10), (5, 5), (2, 8)]);
+        assert_eq!(allocate_widths(10, &columns), vec![3, 5, 2]);
+    }
+
+    #[test]
+    fn rounding() {
+        // deficit of 1 over equal flex goes to the leftmost column
+        let columns = specs(&[(1, 5), (1, 5), (1, 5)]);
+        assert_eq!(allocate_widths(14, &columns), vec![4, 5, 5]);
+
+        // deficit of 2 over equal flex goes to the two leftmost columns
+        assert_eq!(allocate_widths(13, &columns), vec![4, 4, 5]);
+
+        // largest rounded off share shrinks first, and every unit of available width is used
+        let columns = specs(&[(0, 3), (0, 7)]);
+        for available in 0..=10 {
+            let widths = allocate_widths(available, &columns);
+            assert_eq!(widths.iter().sum::<usize>(), available);
+            assert!(widths[0] <= 3 && widths[1] <= 7);
+        }
+        assert_eq!(allocate_widths(6, &columns), vec![2, 4]);
+    }
+
+    #[test]
+    fn over_constrained() {
+        // rightmost columns are dropped when min widths do not fit
+        let columns = specs(&[(3, 10), (5, 5), (2, 8)]);
+        assert_eq!(allocate_widths(9, &columns), vec![4, 5, 0]);
+        assert_eq!(allocate_widths(7, &columns), vec![7, 0, 0]);
+        assert_eq!(allocate_widths(2, &columns), vec![0, 0, 0]);
+
+        // kept columns still shrink proportionally into the space left
+        let columns = specs(&[(2, 6), (2, 4), (20, 20)]);
+        assert_eq!(allocate_widths(7, &columns), vec![4, 3, 0]);
+    }
+
+    #[test]
+    fn min_above_desired() {
+        // desired widths below min widths are raised to them
+        let columns =
+            [ColumnWidthSpec { min_width: 6, desired_width: 2 }, ColumnWidthSpec::new(1, 9)];
+        assert_eq!(allocate_widths(20, &columns), vec![6, 9]);
+        assert_eq!(allocate_widths(10, &columns), vec![6, 4]);
+        assert_eq!(ColumnWidthSpec::new(6, 2).desired_width, 6);
+    }
+}
@@ -1,10 +1,133 @@
-use crate::{ColumnFormat, ColumnFormatShorthand, FormatError};
+#[cfg(test)]
+#[path = "dataframe_format_tests.rs"]
+mod tests;
+
+use crate::{
+    allocate_widths, BoolFormat, CellFormat, ColumnFormat, ColumnFormatShorthand, ColumnWidthSpec,
+    FormatError,
+};
 use polars::prelude::*;
+use std::ops::Range;
+use unicode_truncate::{Alignment, UnicodeTruncateStr};
 
 const DEFAULT_TABLE_HEIGHT: usize = 30;
 
+/// min width of boolean columns with a summary, fitting a bar such as `█████ 100%`
+const BOOL_SUMMARY_MIN_WIDTH: usize = 10;
+
+/// most rows a frame can have to be shown transposed
+pub const MAX_TRANSPOSED_ROWS: usize = 5;
+
+/// style of lines drawn between and around columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// columns separated by spaces, without lines
+    None,
+    /// box drawn with `+`, `-`, and `|`
+    Ascii,
+    /// box drawn with box-drawing characters
+    Unicode,
+    /// lines between columns and below the header, without an outer box
+    Minimal,
+}
+
+/// label spanning the headers of related columns, e.g. "gas" above `gas_used` and `gas_price`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnGroup {
+    /// text shown above the member columns
+    pub label: String,
+    /// names of member columns
+    pub columns: Vec<String>,
+}
+
+impl ColumnGroup {
+    /// create column group from label and member column names
+    pub fn new<T: AsRef<str>>(label: T, columns: &[T]) -> ColumnGroup {
+        ColumnGroup {
+            label: label.as_ref().to_string(),
+            columns: columns.iter().map(|c| c.as_ref().to_string()).collect(),
+        }
+    }
+}
+
+/// characters of a border: lines, then the left, inner, and right junctions of the top,
+/// header separator, and bottom rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderChars {
+    /// horizontal line
+    pub horizontal: char,
+    /// vertical line
+    pub vertical: char,
+    /// junctions of top row
+    pub top: [char; 3],
+    /// junctions of header separator row
+    pub middle: [char; 3],
+    /// junctions of bottom row
+    pub bottom: [char; 3],
+}
+
+const ASCII_BORDER: BorderChars = BorderChars {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+'; 3],
+    middle: ['+'; 3],
+    bottom: ['+'; 3],
+};
+
+const UNICODE_BORDER: BorderChars = BorderChars {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+impl BorderStyle {
+    /// characters used to draw style, restricted to ascii if requested
+    pub fn chars(&self, ascii: bool) -> BorderChars {
+        match self {
+            BorderStyle::Ascii => ASCII_BORDER,
+            _ if ascii => ASCII_BORDER,
+            _ => UNICODE_BORDER,
+        }
+    }
+
+    /// whether style draws a box around the table
+    pub fn is_boxed(&self) -> bool {
+        matches!(self, BorderStyle::Ascii | BorderStyle::Unicode)
+    }
+
+    /// name of style, as parsed by from_str
+    pub fn name(&self) -> &'static str {
+        match self {
+            BorderStyle::None => "none",
+            BorderStyle::Ascii => "ascii",
+            BorderStyle::Unicode => "unicode",
+            BorderStyle::Minimal => "minimal",
+        }
+    }
+}
+
+impl std::str::FromStr for BorderStyle {
+    type Err = FormatError;
+
+    /// parse border style from its name, e.g. `unicode`
+    fn from_str(s: &str) -> Result<BorderStyle, FormatError> {
+        match s {
+            "none" => Ok(BorderStyle::None),
+            "ascii" => Ok(BorderStyle::Ascii),
+            "unicode" => Ok(BorderStyle::Unicode),
+            "minimal" => Ok(BorderStyle::Minimal),
+            _ => Err(FormatError::InvalidFormat(format!(
+                "invalid border style: {} (expected none, ascii, unicode, or minimal)",
+                s
+            ))),
+        }
+    }
+}
+
 /// dataframe format
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataFrameFormat {
     /// column formats
     pub column_formats: Option<Vec<ColumnFormatShorthand>>,
@@ -26,6 +149,19 @@ pub struct DataFrameFormat {
     pub render_height: Option<usize>,
     /// max render width
     pub max_render_width: Option<usize>,
+    /// number of data rows scrolled past, header rows stay fixed
+    pub row_offset: usize,
+    /// show each column as a row, if the frame has at most MAX_TRANSPOSED_ROWS rows
+    pub transpose: bool,
+    /// border style, overriding the delimiters and header separator char when given
+    pub border_style: Option<BorderStyle>,
+    /// draw borders with ascii characters only
+    pub ascii_borders: bool,
+    /// number of trailing columns kept when not all columns fit, leading columns are dropped
+    /// from the right to make room for them
+    pub pinned_columns: usize,
+    /// groups of columns labeled by a spanning header row
+    pub column_groups: Vec<ColumnGroup>,
 }
 
 impl Default for DataFrameFormat {
@@ -41,6 +177,12 @@ impl Default for DataFrameFormat {
             include_summary_separator_row: false,
             render_height: None,
             max_render_width: None,
+            row_offset: 0,
+            transpose: false,
+            border_style: None,
+            ascii_borders: false,
+            pinned_columns: 0,
+            column_groups: vec![],
         }
     }
 }
@@ -68,15 +210,170 @@ pub struct DataFrameFormatFinal {
     pub render_height: usize,
     /// max render width
     pub max_render_width: usize,
+    /// number of data rows scrolled past
+    pub row_offset: usize,
+    /// characters of box drawn around table
+    pub border: Option<BorderChars>,
+    /// groups of columns labeled by a spanning header row
+    pub column_groups: Vec<ColumnGroup>,
 }
 
 impl DataFrameFormat {
     /// format dataframe as String
     pub fn format(&self, df: DataFrame) -> Result<String, FormatError> {
+        if self.is_transposed(&df) {
+            let (fmt, df) = self.transpose_frame(&df)?;
+            return fmt.format(df)
+        }
         let fmt = self.finalize(df.clone())?;
         fmt.format(df)
     }
 
+    /// range of dataframe rows that fit into the data region of the table
+    ///
+    /// for a transposed frame, rows are those of the transposed frame
+    pub fn visible_rows(&self, df: &DataFrame) -> Result<Range<usize>, FormatError> {
+        if self.is_transposed(df) {
+            let (fmt, df) = self.transpose_frame(df)?;
+            return fmt.visible_rows(&df)
+        }
+        let fmt = self.finalize(df.clone())?;
+        Ok(fmt.visible_rows(df.height()))
+    }
+
+    /// number of lines above the data rows of the table
+    pub fn header_height(&self, df: &DataFrame) -> Result<usize, FormatError> {
+        if self.is_transposed(df) {
+            let (fmt, df) = self.transpose_frame(df)?;
+            return fmt.header_height(&df)
+        }
+        Ok(self.finalize(df.clone())?.n_header_rows())
+    }
+
+    /// width of each column when given unlimited room, measured over all rows of dataframe
+    pub fn column_widths(&self, df: &DataFrame) -> Result<Vec<(String, usize)>, FormatError> {
+        let unbounded =
+            DataFrameFormat { max_render_width: None, pinned_columns: 0, ..self.clone() };
+        let fmt = unbounded.finalize(df.clone())?;
+        let (widths, _) = fmt.render_columns(df.clone(), 0)?;
+        Ok(fmt.column_formats.iter().map(|c| c.name.clone()).zip(widths).collect())
+    }
+
+    /// display columns spanned by each rendered column in the rows of the table, omitting
+    /// columns that do not fit
+    pub fn cell_spans(&self, df: &DataFrame) -> Result<Vec<(String, Range<usize>)>, FormatError> {
+        if self.is_transposed(df) {
+            let (fmt, df) = self.transpose_frame(df)?;
+            return fmt.cell_spans(&df)
+        }
+        let fmt = self.finalize(df.clone())?;
+        let visible = fmt.visible_rows(df.height());
+        let df = df.slice(visible.start as i64, visible.len());
+        let (used_widths, _) = fmt.render_columns(df, visible.start)?;
+        let delimiter_width = fmt.column_delimiter.chars().count();
+        let mut start = fmt.border_width() / 2;
+        let mut spans = Vec::with_capacity(used_widths.len());
+        for (column_format, width) in fmt.column_formats.iter().zip(used_widths) {
+            spans.push((column_format.name.clone(), start..start + width));
+            start += width + delimiter_width;
+        }
+        Ok(spans)
+    }
+
+    /// lines of the given rows of dataframe, each column at the width it spans in a table
+    /// rendered before with this format, so that the lines can be appended to that table
+    ///
+    /// None if a cell of the rows needs more room than its column spans, or the table has lines
+    /// below its rows, in which case the table needs a full render
+    pub fn format_rows(
+        &self,
+        df: &DataFrame,
+        rows: Range<usize>,
+        spans: &[(String, Range<usize>)],
+    ) -> Result<Option<Vec<String>>, FormatError> {
+        let column_formats = match self.column_formats.as_ref() {
+            Some(column_formats)
+                if !self.is_transposed(df) &&
+                    !self.include_summary_row &&
+                    self.border_style.is_none() =>
+            {
+                column_formats
+            }
+            _ => return Ok(None),
+        };
+        let mut formats = Vec::with_capacity(spans.len());
+        for (name, _) in spans.iter() {
+            match column_formats.iter().find(|c| &c.name == name) {
+                Some(column_format) => formats.push(column_format.clone()),
+                None => return Ok(None),
+            }
+        }
+        let names: Vec<&str> = spans.iter().map(|(name, _)| name.as_str()).collect();
+        let df = df.head(Some(rows.end)).select(names)?;
+        let rows_only = DataFrameFormat {
+            column_formats: Some(formats),
+            include_header_row: false,
+            render_height: Some(rows.len()),
+            max_render_width: None,
+            row_offset: rows.start,
+            pinned_columns: 0,
+            column_groups: vec![],
+            ..self.clone()
+        };
+        let natural = rows_only.cell_spans(&df)?;
+        let fits = natural.len() == spans.len() &&
+            natural
+                .iter()
+                .zip(spans)
+                .all(|((_, natural), (_, span))| natural.len() <= span.len());
+        if !fits {
+            return Ok(None)
+        }
+        let formats = rows_only.column_formats.iter().flatten().zip(spans);
+        let formats = formats.map(|(column, (_, span))| column.clone().width(span.len())).collect();
+        let fmt = DataFrameFormat { column_formats: Some(formats), ..rows_only };
+        Ok(Some(fmt.format(df)?.lines().map(String::from).collect()))
+    }
+
+    /// whether dataframe is shown transposed
+    pub fn is_transposed(&self, df: &DataFrame) -> bool {
+        self.transpose && df.height() <= MAX_TRANSPOSED_ROWS
+    }
+
+    /// transpose dataframe into a `field` column of column names and one column of formatted
+    /// values per row, along with the format for rendering it
+    pub fn transpose_frame(
+        &self,
+        df: &DataFrame,
+    ) -> Result<(DataFrameFormat, DataFrame), FormatError> {
+        let fmt = self.finalize(df.clone())?;
+        let mut fields = Vec::with_capacity(fmt.column_formats.len());
+        let mut rows = vec![Vec::with_capacity(fmt.column_formats.len()); df.height()];
+        for column_format in fmt.column_formats.iter() {
+            fields.push(column_format.display_name.replace('\n', "_"));
+            let cells = column_format.format(df.column(column_format.name.as_str())?)?;
+            for (row, cell) in rows.iter_mut().zip(cells) {
+                row.push(cell.trim().to_string());
+            }
+        }
+
+        let mut columns = vec![Series::new("field", fields)];
+        for (r, row) in rows.into_iter().enumerate() {
+            let name =
+                if df.height() == 1 { "value".to_string() } else { format!("row {}", r + 1) };
+            columns.push(Series::new(name.as_str(), row));
+        }
+        let fmt = DataFrameFormat {
+            column_formats: None,
+            transpose: false,
+            include_summary_row: false,
+            pinned_columns: 0,
+            column_groups: vec![],
+            ..self.clone()
+        };
+        Ok((fmt, DataFrame::new(columns)?))
+    }
+
     /// fill missing format information based on dataframe
     fn finalize(&self, df: DataFrame) -> Result<DataFrameFormatFinal, FormatError> {
         let schema = df.schema();
@@ -106,19 +403,8 @@ impl DataFrameFormat {
             }
         };
 
-        let max_render_width = match self.max_render_width {
-            Some(value) => value,
-            None => {
-                let max_render_width = safe_sum_with_max_on_overflow(
-                    column_formats.iter().map(|c| c.get_max_width()).collect(),
-                );
-                safe_sum_with_max_on_overflow(vec![
-                    max_render_width,
-                    self.column_delimiter.chars().count() * (column_formats.len() - 1),
-                ])
-            }
-        };
-        let fmt = DataFrameFormatFinal {
+        let n_columns = column_formats.len();
+        let mut fmt = DataFrameFormatFinal {
             column_formats,
             column_delimiter: self.column_delimiter.clone(),
             header_separator_delimiter: self.header_separator_delimiter.clone(),
@@ -128,12 +414,66 @@ impl DataFrameFormat {
             include_summary_row: self.include_summary_row,
             include_summary_separator_row: self.include_summary_separator_row,
             render_height: self.render_height.unwrap_or(DEFAULT_TABLE_HEIGHT),
-            max_render_width,
+            max_render_width: 0,
+            row_offset: self.row_offset,
+            border: None,
+            column_groups: self.column_groups.clone(),
         };
+        if let Some(style) = self.border_style {
+            fmt.set_border_style(style, self.ascii_borders);
+        }
+
+        fmt.max_render_width = match self.max_render_width {
+            Some(value) => value,
+            None => {
+                let max_render_width = safe_sum_with_max_on_overflow(
+                    fmt.column_formats.iter().map(|c| c.get_max_width()).collect(),
+                );
+                safe_sum_with_max_on_overflow(vec![
+                    max_render_width,
+                    fmt.column_delimiter.chars().count() * (n_columns - 1),
+                    fmt.border_width(),
+                ])
+            }
+        };
+        if self.pinned_columns > 0 {
+            fmt.fit_pinned_columns(self.pinned_columns);
+        }
         Ok(fmt)
     }
 }
 
+/// spans of column groups over the rendered columns, as (label, columns, width)
+///
+/// a group spans each contiguous run of its members, so that its label follows its columns as
+/// they are moved or hidden, and the width of a span is the sum of the widths of its columns and
+/// the delimiters between them
+pub(crate) fn column_group_spans(
+    groups: &[ColumnGroup],
+    names: &[&str],
+    widths: &[usize],
+    delimiter_width: usize,
+) -> Vec<(String, Range<usize>, usize)> {
+    let group_of = |c: usize| {
+        groups.iter().position(|group| group.columns.iter().any(|column| column == names[c]))
+    };
+    let mut spans = Vec::new();
+    let mut c = 0;
+    while c < widths.len().min(names.len()) {
+        let Some(group) = group_of(c) else {
+            c += 1;
+            continue
+        };
+        let start = c;
+        while c < widths.len().min(names.len()) && group_of(c) == Some(group) {
+            c += 1;
+        }
+        let width = widths[start..c].iter().sum::<usize>() + delimiter_width * (c - start - 1);
+        spans.push((groups[group].label.clone(), start..c, width));
+    }
+    spans
+}
+
 fn safe_sum_with_max_on_overflow(numbers: Vec<usize>) -> usize {
     let mut sum: usize = 0;
     for number in numbers {
@@ -145,8 +485,77 @@ fn safe_sum_with_max_on_overflow(numbers: Vec<usize>) -> usize {
     sum
 }
 
+/// range of rows visible in a table whose header and footer stay fixed while its data scrolls
+///
+/// the offset is clamped so that the last row remains reachable but scrolling stops once it is
+/// visible, and the range is empty if the header and footer leave no room for data
+pub fn visible_row_range(
+    n_rows: usize,
+    render_height: usize,
+    header_height: usize,
+    footer_height: usize,
+    offset: usize,
+) -> Range<usize> {
+    let data_height = render_height.saturating_sub(header_height + footer_height);
+    let start = offset.min(n_rows.saturating_sub(data_height));
+    start..(start + data_height).min(n_rows)
+}
+
 // get number of lines in header
 impl DataFrameFormatFinal {
+    fn set_border_style(&mut self, style: BorderStyle, ascii: bool) {
+        let chars = style.chars(ascii);
+        if style == BorderStyle::None {
+            self.column_delimiter = "  ".to_string();
+            self.include_header_separator_row = false;
+        } else {
+            self.column_delimiter = format!(" {} ", chars.vertical);
+            self.header_separator_delimiter =
+                format!("{}{}{}", chars.horizontal, chars.middle[1], chars.horizontal);
+            self.header_separator_char = chars.horizontal;
+        }
+        self.border = if style.is_boxed() { Some(chars) } else { None };
+    }
+
+    /// width taken by the left and right sides of the box
+    fn border_width(&self) -> usize {
+        4 * (self.border.is_some() as usize)
+    }
+
+    /// min width of column, widening boolean columns to fit their summary
+    fn column_min_width(&self, fmt: &ColumnFormat) -> usize {
+        let min_width = fmt.header_width().max(fmt.get_min_width());
+        if self.include_summary_row && matches!(fmt.format, CellFormat::Bool(_)) {
+            min_width.max(BOOL_SUMMARY_MIN_WIDTH.min(fmt.get_max_width()))
+        } else {
+            min_width
+        }
+    }
+
+    /// keep the trailing n_pinned columns, dropping leading columns from the right until the
+    /// rest fit beside them
+    ///
+    /// if the pinned columns do not all fit, the leftmost pinned columns are kept
+    fn fit_pinned_columns(&mut self, n_pinned: usize) {
+        let n_columns = self.column_formats.len();
+        let n_pinned = n_pinned.min(n_columns);
+        let max_width = self.max_render_width.saturating_sub(self.border_width());
+        let delimiter_width = self.column_delimiter.chars().count();
+        let mut keep = vec![false; n_columns];
+        let mut used_width = 0;
+        for c in (n_columns - n_pinned..n_columns).chain(0..n_columns - n_pinned) {
+            let width = self.column_min_width(&self.column_formats[c]);
+            let width = if used_width > 0 { width + delimiter_width } else { width };
+            if used_width > 0 && used_width + width > max_width {
+                break
+            }
+            used_width += width;
+            keep[c] = true;
+        }
+        let mut keep = keep.into_iter();
+        self.column_formats.retain(|_| keep.next().unwrap_or(false));
+    }
+
     fn n_header_lines(&self) -> usize {
         // TODO: take an n_used_columns parameter, for if only subset of columns used
         self.column_formats
@@ -156,12 +565,34 @@ impl DataFrameFormatFinal {
             .unwrap_or(0)
     }
 
-    fn n_data_rows(&self) -> usize {
-        self.render_height -
-            (self.include_header_row as usize) *
-                (self.n_header_lines() + (self.include_header_separator_row as usize)) -
-            (self.include_summary_row as usize) *
-                (1 + (self.include_summary_separator_row as usize))
+    fn n_header_rows(&self) -> usize {
+        (self.include_header_row as usize) *
+            (self.n_header_lines() +
+                (self.include_header_separator_row as usize) +
+                (self.has_column_groups() as usize)) +
+            (self.border.is_some() as usize)
+    }
+
+    /// whether any column belongs to a group, adding a row of group labels to the header
+    fn has_column_groups(&self) -> bool {
+        self.column_formats
+            .iter()
+            .any(|fmt| self.column_groups.iter().any(|group| group.columns.contains(&fmt.name)))
+    }
+
+    fn n_footer_rows(&self) -> usize {
+        (self.include_summary_row as usize) * (1 + (self.include_summary_separator_row as usize)) +
+            (self.border.is_some() as usize)
+    }
+
+    fn visible_rows(&self, n_rows: usize) -> Range<usize> {
+        visible_row_range(
+            n_rows,
+            self.render_height,
+            self.n_header_rows(),
+            self.n_footer_rows(),
+            self.row_offset,
+        )
     }
 
     fn total_rendered_width(&self, used_widths: &Vec<usize>) -> usize {
@@ -170,20 +601,52 @@ impl DataFrameFormatFinal {
                 self.column_delimiter.chars().count()
     }
 
+    /// wrap row in the sides of the box, if any
+    fn boxed(&self, row: String, total_width: usize) -> String {
+        match self.border {
+            Some(border) => format!(
+                "{} {:<width$} {}",
+                border.vertical,
+                row,
+                border.vertical,
+                width = total_width
+            ),
+            None => row,
+        }
+    }
+
+    /// horizontal line of box, with junctions at column boundaries
+    fn render_border_row(
+        &self,
+        used_widths: &[usize],
+        border: BorderChars,
+        junctions: [char; 3],
+    ) -> String {
+        let segments: Vec<String> = used_widths
+            .iter()
+            .map(|width| border.horizontal.to_string().repeat(width + 2))
+            .collect();
+        format!("{}{}{}", junctions[0], segments.join(&junctions[1].to_string()), junctions[2])
+    }
+
     fn render_header_rows(&self, used_widths: &[usize], total_width: usize) -> Vec<String> {
         let n_header_lines = self.n_header_lines();
         let mut rows: Vec<String> =
             (0..n_header_lines).map(|_| String::with_capacity(total_width)).collect();
+        if self.has_column_groups() {
+            rows.insert(0, self.render_group_row(used_widths, total_width));
+        }
+        let rows_above = rows.len() - n_header_lines;
         for (c, width) in used_widths.iter().enumerate() {
             if c != 0 {
-                for row in rows.iter_mut() {
+                for row in rows.iter_mut().skip(rows_above) {
                     row.push_str(self.column_delimiter.as_str());
                 }
             }
             let name = self.column_formats[c].display_name.as_str();
             let lines: Vec<String> = name.split('\n').map(|s| s.to_string()).collect();
-            let bound = n_header_lines - lines.len();
-            for row in rows.iter_mut().take(bound) {
+            let bound = rows_above + n_header_lines - lines.len();
+            for row in rows.iter_mut().skip(rows_above).take(bound - rows_above) {
                 row.push_str(" ".repeat(*width).as_str());
             }
             for (row, line) in rows.iter_mut().skip(bound).zip(lines) {
@@ -194,6 +657,43 @@ impl DataFrameFormatFinal {
         rows
     }
 
+    /// row of group labels, each centered in a line spanning its member columns
+    fn render_group_row(&self, used_widths: &[usize], total_width: usize) -> String {
+        let names: Vec<&str> = self.column_formats.iter().map(|fmt| fmt.name.as_str()).collect();
+        let delimiter_width = self.column_delimiter.chars().count();
+        let spans = column_group_spans(&self.column_groups, &names, used_widths, delimiter_width);
+        let mut row = String::with_capacity(total_width);
+        let mut c = 0;
+        while c < used_widths.len() {
+            if c != 0 {
+                row.push_str(self.column_delimiter.as_str());
+            }
+            match spans.iter().find(|(_, columns, _)| columns.start == c) {
+                Some((label, columns, width)) => {
+                    // line on either side of label, separated from it by a space
+                    let (label, label_width) = label.unicode_truncate(*width);
+                    let spare = width - label_width;
+                    let fill = self.header_separator_char.to_string();
+                    let left = match spare / 2 {
+                        0 => String::new(),
+                        n => format!("{} ", fill.repeat(n - 1)),
+                    };
+                    let right = match spare - spare / 2 {
+                        0 => String::new(),
+                        n => format!(" {}", fill.repeat(n - 1)),
+                    };
+                    row.push_str(&format!("{}{}{}", left, label, right));
+                    c = columns.end;
+                }
+                None => {
+                    row.push_str(" ".repeat(used_widths[c]).as_str());
+                    c += 1;
+                }
+            }
+        }
+        row
+    }
+
     fn render_header_separator_row(&self, used_widths: &[usize], total_width: usize) -> String {
         let mut row = String::with_capacity(total_width);
         let separator = self.header_separator_char.to_string();
@@ -206,12 +706,24 @@ impl DataFrameFormatFinal {
         row
     }
 
-    fn render_columns(&self, df: DataFrame) -> Result<(Vec<usize>, Vec<Vec<String>>), FormatError> {
+    /// render columns of df, whose first row is row first_row of the full frame
+    ///
+    /// columns share the render width as given by allocate_widths, each shrinking from the width
+    /// of its widest cell toward its min width, and are dropped from the right only when their
+    /// min widths do not fit
+    fn render_columns(
+        &self,
+        df: DataFrame,
+        first_row: usize,
+    ) -> Result<(Vec<usize>, Vec<Vec<String>>), FormatError> {
+        let max_render_width = self.max_render_width.saturating_sub(self.border_width());
+        let delimiter_width = self.column_delimiter.chars().count();
+
         // compute global sizes
         let mut column_min_widths: Vec<usize> = vec![];
         let mut column_max_widths: Vec<usize> = vec![];
         for fmt in self.column_formats.iter() {
-            let min_width = fmt.header_width().max(fmt.get_min_width());
+            let min_width = self.column_min_width(fmt);
             let max_width = fmt.get_max_width();
             if min_width > max_width {
                 let msg = format!("min_width > max_width for column: {}", fmt.display_name);
@@ -221,87 +733,111 @@ impl DataFrameFormatFinal {
             column_max_widths.push(max_width);
         }
 
-        let total_min_width = column_min_widths.iter().sum::<usize>() +
-            self.column_delimiter.chars().count() * (self.column_formats.len() - 1);
-        // let total_max_width = column_max_widths.iter().sum::<usize>();
-
         // compute how many columns to include
-        let n_used_columns = if total_min_width >= self.max_render_width {
-            let mut n_used_columns = 0;
-            let mut used_width = 0;
-            for min_width in column_min_widths.iter() {
-                if used_width > 0 {
-                    used_width += self.column_delimiter.chars().count();
-                }
-                if used_width + min_width <= self.max_render_width {
-                    n_used_columns += 1;
-                    used_width += min_width;
-                } else {
-                    break;
-                }
+        let mut n_used_columns = 0;
+        let mut used_width = 0;
+        for min_width in column_min_widths.iter() {
+            if used_width > 0 {
+                used_width += delimiter_width;
             }
-            n_used_columns
-        } else {
-            self.column_formats.len()
-        };
-        // let column_min_widths = column_min_widths.into_iter().take(n_used_columns);
-        // let column_max_widths = column_max_widths.into_iter().take(n_used_columns);
+            if used_width + min_width > max_render_width {
+                break
+            }
+            n_used_columns += 1;
+            used_width += min_width;
+        }
 
-        // compute used widths
+        // render columns at the width of their widest cell
         let mut columns = Vec::with_capacity(n_used_columns);
+        let mut specs = Vec::with_capacity(n_used_columns);
+        for c in 0..n_used_columns {
+            let (min_width, max_width) = (column_min_widths[c], column_max_widths[c]);
+            let (desired_width, column) =
+                self.render_column(&df, first_row, c, min_width, max_width)?;
+            specs.push(ColumnWidthSpec::new(min_width, desired_width));
+            columns.push(column);
+        }
+
+        // re-render the columns that must shrink to share the render width
+        let available = max_render_width - delimiter_width * n_used_columns.saturating_sub(1);
+        let widths = allocate_widths(available, &specs);
         let mut used_widths = Vec::with_capacity(n_used_columns);
-        let mut spare_room: usize = self.max_render_width -
-            column_min_widths.iter().take(n_used_columns).sum::<usize>() -
-            self.column_delimiter.chars().count() * ((n_used_columns as i64 - 1).max(0) as usize);
-        // println!("COLUMN_MIN_WIDTHS {:?}", column_min_widths);
-        // println!("TOTAL_MIN_WIDTHS {}",
-        // column_min_widths.iter().take(n_used_columns).sum::<usize>());
-        // println!("SPARE_ROOM {}", spare_room);
-        // println!("MAX_RENDER_WIDTH {:?}", self.max_render_width);
-        // println!(
-        //     "MIN_TOTAL_W_DELIM {:?}",
-        //     column_min_widths.iter().take(n_used_columns).sum::<usize>()
-        //         + self.column_delimiter.chars().count()
-        //             * ((n_used_columns as i64 - 1).max(0) as usize)
-        // );
-        // println!();
-
-        for (c, column_format) in self.column_formats.iter().take(n_used_columns).enumerate() {
-            if let (0, _) = df.shape() {
-                used_widths.push(column_min_widths[c]);
-                columns.push(vec![]);
-                continue
+        for (c, (width, spec)) in widths.into_iter().zip(specs).enumerate() {
+            if width < spec.desired_width {
+                let (used_width, column) =
+                    self.render_column(&df, first_row, c, spec.min_width, width)?;
+                columns[c] = column;
+                used_widths.push(used_width);
+            } else {
+                used_widths.push(spec.desired_width);
             }
-
-            let min_width = column_min_widths[c];
-            let max_width = column_max_widths[c].min(min_width + spare_room);
-            let column = column_format
-                .clone()
-                .min_width(min_width)
-                .max_width(max_width)
-                .format(df.column(column_format.name.as_str())?)?;
-            let used_width = column
-                .iter()
-                .map(|s| s.chars().count())
-                // .map(|s| unicode_width::UnicodeWidthStr::width_cjk(s.as_str()))
-                // .map(|s| unicode_width::UnicodeWidthStr::width(s.as_str()))
-                .max()
-                .ok_or(FormatError::EmptyData(format!("empty column: {}", column_format.name)))?;
-            columns.push(column);
-            // println!("NAME {}", column_format.name);
-            // println!("FORMAT {:?}", column_format);
-            // println!("MAX_WIDTH {}", max_width);
-            // println!("MIN_WIDTH {}", min_width);
-            // println!("SPARE_ROOM {}", spare_room);
-            // println!("USED_WIDTH {}", used_width);
-            // println!("NEW_SPARE_ROOM {}", spare_room - (used_width - min_width));
-            // println!();
-            used_widths.push(used_width);
-            spare_room -= used_width - min_width;
         }
         Ok((used_widths, columns))
     }
 
+    /// render column c of df between min_width and max_width, returning its used width
+    fn render_column(
+        &self,
+        df: &DataFrame,
+        first_row: usize,
+        c: usize,
+        min_width: usize,
+        max_width: usize,
+    ) -> Result<(usize, Vec<String>), FormatError> {
+        if let (0, _) = df.shape() {
+            return Ok((min_width, vec![]))
+        }
+        let column_format = &self.column_formats[c];
+        let marked_rows =
+            column_format.marked_rows.iter().filter_map(|r| r.checked_sub(first_row)).collect();
+        let column = column_format
+            .clone()
+            .min_width(min_width)
+            .max_width(max_width)
+            .marked_rows(marked_rows)
+            .format(df.column(column_format.name.as_str())?)?;
+        let used_width = column
+            .iter()
+            .map(|s| unicode_width::UnicodeWidthStr::width(s.as_str()))
+            .max()
+            .ok_or(FormatError::EmptyData(format!("empty column: {}", column_format.name)))?;
+        Ok((used_width, column))
+    }
+
+    /// summary of the displayed rows of each column, a bar of the share of true values for
+    /// boolean columns and blank for others
+    fn render_summary_row(
+        &self,
+        df: &DataFrame,
+        used_widths: &[usize],
+        total_width: usize,
+    ) -> Result<String, FormatError> {
+        let mut row = String::with_capacity(total_width);
+        for (c, width) in used_widths.iter().enumerate() {
+            if c != 0 {
+                row.push_str(self.column_delimiter.as_str());
+            }
+            let series = df.column(self.column_formats[c].name.as_str())?;
+            let summary = match series.dtype() {
+                DataType::Boolean => {
+                    // size bar to leave room for the text after it, dropping the note of nulls
+                    // if there is no room for it
+                    let values: Vec<Option<bool>> = series.bool()?.into_iter().collect();
+                    let text_width = BoolFormat::ratio_bar(values.clone(), 0).chars().count();
+                    let values = match text_width < *width {
+                        true => values,
+                        false => values.into_iter().filter(|v| v.is_some()).collect(),
+                    };
+                    let text_width = BoolFormat::ratio_bar(values.clone(), 0).chars().count();
+                    BoolFormat::ratio_bar(values, width.saturating_sub(text_width).max(1))
+                }
+                _ => String::new(),
+            };
+            row.push_str(summary.unicode_pad(*width, Alignment::Left, true).as_ref());
+        }
+        Ok(row)
+    }
+
     fn assemble_rows(&self, columns: Vec<Vec<String>>, rows: &mut Vec<String>, total_width: usize) {
         let n_data_rows = match columns.first() {
             Some(column) => column.len(),
@@ -321,28 +857,55 @@ impl DataFrameFormatFinal {
     }
 
     pub(crate) fn format(&self, df: DataFrame) -> Result<String, FormatError> {
-        // clip
-        let n_data_rows = self.n_data_rows();
-        let df = df.clone().slice(0, n_data_rows);
+        // clip to the rows scrolled into view
+        let visible = self.visible_rows(df.height());
+        let df = df.slice(visible.start as i64, visible.len());
 
         // render columns
-        let (used_widths, columns) = self.render_columns(df)?;
+        let (used_widths, columns) = self.render_columns(df.clone(), visible.start)?;
         let total_width = self.total_rendered_width(&used_widths);
 
         // assemble rows
         let mut rows = Vec::with_capacity(self.render_height);
+        if let Some(border) = self.border {
+            rows.push(self.render_border_row(&used_widths, border, border.top));
+        }
         if self.include_header_row {
             for row in self.render_header_rows(&used_widths, total_width) {
-                rows.push(row);
+                rows.push(self.boxed(row, total_width));
             }
             if self.include_header_separator_row {
-                rows.push(self.render_header_separator_row(&used_widths, total_width));
+                match self.border {
+                    Some(border) => {
+                        rows.push(self.render_border_row(&used_widths, border, border.middle))
+                    }
+                    None => rows.push(self.render_header_separator_row(&used_widths, total_width)),
+                }
             }
         };
-        self.assemble_rows(columns, &mut rows, total_width);
+        let mut data_rows = Vec::new();
+        self.assemble_rows(columns, &mut data_rows, total_width);
+        for row in data_rows {
+            rows.push(self.boxed(row, total_width));
+        }
         if self.include_summary_row {
-            todo!("summary row")
+            if self.include_summary_separator_row {
+                match self.border {
+                    Some(border) => {
+                        rows.push(self.render_border_row(&used_widths, border, border.middle))
+                    }
+                    None => rows.push(self.render_header_separator_row(&used_widths, total_width)),
+                }
+            }
+            let row = self.render_summary_row(&df, &used_widths, total_width)?;
+            rows.push(self.boxed(row, total_width));
         }
+        if let Some(border) = self.border {
+            rows.push(self.render_border_row(&used_widths, border, border.bottom));
+        }
+
+        // terminal too short for full header
+        rows.truncate(self.render_height);
 
         Ok(rows.join("\n"))
     }
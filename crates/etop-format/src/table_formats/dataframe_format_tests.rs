@@ -0,0 +1,351 @@
+#[cfg(test)]
+mod dataframe_format_tests {
+    use super::super::column_group_spans;
+    use crate::{
+        visible_row_range, BorderStyle, ColumnFormatShorthand, ColumnGroup, DataFrameFormat,
+        NumberFormat,
+    };
+    use polars::prelude::*;
+
+    #[test]
+    fn visible_rows() {
+        // 10 rows, 6 lines of table, 2 header lines
+        assert_eq!(visible_row_range(10, 6, 2, 0, 0), 0..4);
+        assert_eq!(visible_row_range(10, 6, 2, 0, 3), 3..7);
+
+        // last row reachable, but scrolling stops once it is visible
+        assert_eq!(visible_row_range(10, 6, 2, 0, 6), 6..10);
+        assert_eq!(visible_row_range(10, 6, 2, 0, 9), 6..10);
+        assert_eq!(visible_row_range(10, 6, 2, 1, 9), 7..10);
+
+        // fewer rows than fit
+        assert_eq!(visible_row_range(3, 6, 2, 0, 2), 0..3);
+        assert_eq!(visible_row_range(0, 6, 2, 0, 0), 0..0);
+
+        // only header fits
+        assert!(visible_row_range(10, 2, 2, 0, 5).is_empty());
+        assert!(visible_row_range(10, 1, 2, 1, 5).is_empty());
+    }
+
+    #[test]
+    fn frozen_header() {
+        let df = df!("x" => [1u32, 2, 3, 4, 5]).unwrap();
+        let fmt = |height: usize, offset: usize| DataFrameFormat {
+            render_height: Some(height),
+            row_offset: offset,
+            ..Default::default()
+        };
+        let lines = |height: usize, offset: usize| -> Vec<String> {
+            let s = fmt(height, offset).format(df.clone()).unwrap();
+            s.lines().map(|line| line.trim().to_string()).collect()
+        };
+
+        assert_eq!(lines(4, 0), vec!["x", "─", "1", "2"]);
+        assert_eq!(lines(4, 2), vec!["x", "─", "3", "4"]);
+        assert_eq!(lines(4, 10), vec!["x", "─", "4", "5"]);
+        assert_eq!(fmt(4, 10).visible_rows(&df).unwrap(), 3..5);
+        assert_eq!(lines(2, 1), vec!["x", "─"]);
+        assert_eq!(lines(1, 1), vec!["x"]);
+    }
+
+    fn transposed(df: &DataFrame) -> Vec<Vec<String>> {
+        let fmt = DataFrameFormat {
+            column_formats: Some(vec![
+                ColumnFormatShorthand::new().name("block_number"),
+                ColumnFormatShorthand::new()
+                    .name("gas_used")
+                    .newline_underscores()
+                    .set_format(NumberFormat::new().commas().precision(0)),
+            ]),
+            transpose: true,
+            ..Default::default()
+        };
+        assert!(fmt.is_transposed(df));
+        let s = fmt.format(df.clone()).unwrap();
+        s.lines()
+            .map(|line| line.split('│').map(|cell| cell.trim().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn transpose_one_row() {
+        let df = df!("block_number" => [18_000_000u32], "gas_used" => [1234u64]).unwrap();
+        let lines = transposed(&df);
+        assert_eq!(lines[0], vec!["field", "value"]);
+        assert_eq!(lines[2], vec!["block_number", "18000000"]);
+        assert_eq!(lines[3], vec!["gas_used", "1,234"]);
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn transpose_three_rows() {
+        let df = df!("block_number" => [1u32, 2, 3], "gas_used" => [10u64, 2000, 30]).unwrap();
+        let lines = transposed(&df);
+        assert_eq!(lines[0], vec!["field", "row 1", "row 2", "row 3"]);
+        assert_eq!(lines[2], vec!["block_number", "1", "2", "3"]);
+        assert_eq!(lines[3], vec!["gas_used", "10", "2,000", "30"]);
+        assert_eq!(lines.len(), 4);
+
+        // frames with many rows are not transposed
+        let df = df!("x" => (0..10u32).collect::<Vec<_>>()).unwrap();
+        let fmt = DataFrameFormat { transpose: true, ..Default::default() };
+        assert!(!fmt.is_transposed(&df));
+        assert_eq!(fmt.format(df).unwrap().lines().next().unwrap().trim(), "x");
+    }
+
+    fn bordered(style: BorderStyle, ascii: bool) -> String {
+        let df = df!("name" => ["a", "bb"], "n" => [1u32, 22]).unwrap();
+        let fmt = DataFrameFormat {
+            border_style: Some(style),
+            ascii_borders: ascii,
+            ..Default::default()
+        };
+        fmt.format(df).unwrap()
+    }
+
+    #[test]
+    fn border_styles() {
+        let none = ["name   n", "   a   1", "  bb  22"];
+        assert_eq!(bordered(BorderStyle::None, false), none.join("\n"));
+
+        let minimal = ["name │  n", "─────┼───", "   a │  1", "  bb │ 22"];
+        assert_eq!(bordered(BorderStyle::Minimal, false), minimal.join("\n"));
+
+        let ascii = [
+            "+------+----+",
+            "| name |  n |",
+            "+------+----+",
+            "|    a |  1 |",
+            "|   bb | 22 |",
+            "+------+----+",
+        ];
+        assert_eq!(bordered(BorderStyle::Ascii, false), ascii.join("\n"));
+
+        let unicode = [
+            "┌──────┬────┐",
+            "│ name │  n │",
+            "├──────┼────┤",
+            "│    a │  1 │",
+            "│   bb │ 22 │",
+            "└──────┴────┘",
+        ];
+        assert_eq!(bordered(BorderStyle::Unicode, false), unicode.join("\n"));
+
+        // unicode degrades to ascii
+        assert_eq!(bordered(BorderStyle::Unicode, true), ascii.join("\n"));
+        let minimal = ["name |  n", "-----+---", "   a |  1", "  bb | 22"];
+        assert_eq!(bordered(BorderStyle::Minimal, true), minimal.join("\n"));
+    }
+
+    #[test]
+    fn border_width_and_height() {
+        let df = df!("a" => [1u32, 2, 3, 4], "b" => [5u32, 6, 7, 8]).unwrap();
+        let fmt = |width: usize, height: usize| DataFrameFormat {
+            border_style: Some(BorderStyle::Unicode),
+            max_render_width: Some(width),
+            render_height: Some(height),
+            ..Default::default()
+        };
+
+        // sides of box take 4 columns, so both columns fit in 9 but only the first in 8
+        let header = |width: usize| {
+            fmt(width, 10).format(df.clone()).unwrap().lines().nth(1).map(String::from)
+        };
+        assert_eq!(header(9).unwrap(), "│ a │ b │");
+        assert_eq!(header(8).unwrap(), "│ a │");
+
+        // top, header, separator, and bottom rows leave room for 2 data rows
+        let lines: Vec<String> =
+            fmt(20, 6).format(df.clone()).unwrap().lines().map(String::from).collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[5], "└───┴───┘");
+        assert_eq!(fmt(20, 6).visible_rows(&df).unwrap(), 0..2);
+    }
+
+    #[test]
+    fn pinned_columns() {
+        let df = df!("a" => [1u32], "b" => [2u32], "c" => [3u32], "d" => [4u32]).unwrap();
+        let header = |width: usize, pinned: usize| {
+            let fmt = DataFrameFormat {
+                column_delimiter: " ".to_string(),
+                max_render_width: Some(width),
+                pinned_columns: pinned,
+                ..Default::default()
+            };
+            fmt.format(df.clone()).unwrap().lines().next().unwrap().to_string()
+        };
+
+        // trailing columns are dropped without pinning
+        assert_eq!(header(5, 0), "a b c");
+        assert_eq!(header(7, 0), "a b c d");
+
+        // pinned columns take room from the leading columns
+        assert_eq!(header(5, 1), "a b d");
+        assert_eq!(header(5, 2), "a c d");
+        assert_eq!(header(7, 2), "a b c d");
+
+        // leftmost pinned columns are kept when they do not all fit
+        assert_eq!(header(3, 3), "b c");
+        assert_eq!(header(1, 3), "b");
+    }
+
+    #[test]
+    fn shared_width() {
+        // wide columns shrink in proportion to their widest cells instead of crowding out the
+        // columns to their right, which are only dropped once their headers do not fit
+        let df = df!(
+            "a" => ["aaaaaaaaaaaaaaa"],
+            "b" => ["bbbbbbbb"],
+            "c" => [1u32],
+        )
+        .unwrap();
+        let data_row = |width: usize| -> String {
+            let fmt = DataFrameFormat { max_render_width: Some(width), ..Default::default() };
+            fmt.format(df.clone()).unwrap().lines().last().unwrap().to_string()
+        };
+        assert_eq!(data_row(40), "aaaaaaaaaaaaaaa  │  bbbbbbbb  │  1");
+        assert_eq!(data_row(24), "aaaaa...  │  bb...  │  1");
+        assert_eq!(data_row(12), "a...  │  ...");
+        assert_eq!(data_row(5), "aa...");
+    }
+
+    #[test]
+    fn column_widths() {
+        let df = df!("a" => [1u32, 22_222], "name" => ["x", "y"]).unwrap();
+        let fmt = DataFrameFormat {
+            max_render_width: Some(3),
+            pinned_columns: 1,
+            render_height: Some(3),
+            ..Default::default()
+        };
+
+        // widths cover rows and columns that do not fit
+        let widths = fmt.column_widths(&df).unwrap();
+        assert_eq!(widths, vec![("a".to_string(), 5), ("name".to_string(), 4)]);
+    }
+
+    #[test]
+    fn cell_spans() {
+        let df = df!("a" => [1u32, 22_222], "name" => ["x", "y"]).unwrap();
+        let fmt = DataFrameFormat { column_delimiter: " | ".to_string(), ..Default::default() };
+        let spans = fmt.cell_spans(&df).unwrap();
+        assert_eq!(spans, vec![("a".to_string(), 0..5), ("name".to_string(), 8..12)]);
+        let render = fmt.format(df.clone()).unwrap();
+        let row = render.lines().nth(3).unwrap();
+        assert_eq!(&row[spans[0].1.clone()], "22222");
+
+        // boxed rows are offset by the side of the box
+        let fmt = DataFrameFormat { border_style: Some(BorderStyle::Ascii), ..fmt };
+        assert_eq!(fmt.cell_spans(&df).unwrap()[0].1, 2..7);
+    }
+
+    #[test]
+    fn format_rows() {
+        let df = df!("a" => [1u32, 22, 3, 4_444_444], "name" => ["x", "y", "z", "w"]).unwrap();
+        let columns = ["a", "name"].iter().map(|n| ColumnFormatShorthand::new().name(*n)).collect();
+        let fmt = DataFrameFormat {
+            column_formats: Some(columns),
+            render_height: Some(10),
+            ..Default::default()
+        };
+        let spans = fmt.cell_spans(&df.head(Some(2))).unwrap();
+        let render = fmt.format(df.head(Some(2))).unwrap();
+
+        // appended rows line up with the rows above
+        let lines = fmt.format_rows(&df, 2..3, &spans).unwrap().unwrap();
+        assert_eq!(format!("{}\n{}", render, lines[0]), fmt.format(df.head(Some(3))).unwrap());
+
+        // rows wider than their columns need a full render
+        assert_eq!(fmt.format_rows(&df, 3..4, &spans).unwrap(), None);
+    }
+
+    #[test]
+    fn group_spans() {
+        let groups = [
+            ColumnGroup::new("gas", &["gas_used", "gas_limit"]),
+            ColumnGroup::new("fee", &["fee"]),
+        ];
+        let names = ["n", "gas_used", "gas_limit", "fee", "gas_price"];
+        let widths = [1, 8, 9, 3, 9];
+
+        // span covers member widths and the delimiters between them
+        let spans = column_group_spans(&groups, &names, &widths, 3);
+        assert_eq!(spans, vec![("gas".to_string(), 1..3, 20), ("fee".to_string(), 3..4, 3)]);
+
+        // members split apart get separate spans, and hidden members are skipped
+        let names = ["gas_used", "n", "gas_limit"];
+        let spans = column_group_spans(&groups, &names, &[8, 1, 9], 3);
+        assert_eq!(spans, vec![("gas".to_string(), 0..1, 8), ("gas".to_string(), 2..3, 9)]);
+        assert!(column_group_spans(&groups, &["n"], &[1], 3).is_empty());
+    }
+
+    #[test]
+    fn group_header_row() {
+        let df = df!("n" => [1u32], "gas_used" => [10u32], "gas_limit" => [20u32]).unwrap();
+        let fmt = DataFrameFormat {
+            column_delimiter: " ".to_string(),
+            header_separator_delimiter: " ".to_string(),
+            column_groups: vec![ColumnGroup::new("gas", &["gas_used", "gas_limit"])],
+            render_height: Some(4),
+            ..Default::default()
+        };
+        let lines: Vec<String> =
+            fmt.format(df.clone()).unwrap().lines().map(String::from).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  ────── gas ───────",
+                "n gas_used gas_limit",
+                "─ ──────── ─────────",
+                "1       10        20",
+            ]
+        );
+        assert_eq!(fmt.header_height(&df).unwrap(), 3);
+    }
+
+    #[test]
+    fn bool_summary_row() {
+        let df = df!(
+            "n" => [1u32, 2, 3, 4],
+            "success" => [Some(true), Some(false), Some(true), None],
+        )
+        .unwrap();
+        let fmt = |offset: usize| DataFrameFormat {
+            include_summary_row: true,
+            include_summary_separator_row: true,
+            render_height: Some(6),
+            row_offset: offset,
+            ..Default::default()
+        };
+        let lines = |offset: usize| -> Vec<String> {
+            let s = fmt(offset).format(df.clone()).unwrap();
+            s.lines().map(|line| line.trim_end().to_string()).collect()
+        };
+
+        // share covers only the visible rows
+        let top = lines(0);
+        assert_eq!(top.len(), 6);
+        assert_eq!(
+            top[2..],
+            ["1  │        true", "2  │       false", "───┼────────────", "   │  ███░░░ 50%"]
+        );
+
+        // note of nulls is dropped if it does not fit
+        assert_eq!(lines(2)[5], "   │  █████ 100%");
+        assert_eq!(fmt(0).header_height(&df).unwrap(), 2);
+    }
+
+    #[test]
+    fn wide_characters_aligned() {
+        let df = df!("name" => ["日本", "🦀", "ab"], "n" => [1u32, 22, 333]).unwrap();
+        let fmt = DataFrameFormat::default();
+        let lines: Vec<String> = fmt.format(df).unwrap().lines().map(String::from).collect();
+        assert_eq!(
+            lines,
+            vec!["name  │    n", "──────┼─────", "日本  │    1", "  🦀  │   22", "  ab  │  333"]
+        );
+        let widths: Vec<usize> =
+            lines.iter().map(|line| unicode_width::UnicodeWidthStr::width(line.as_str())).collect();
+        assert!(widths.iter().all(|width| *width == widths[0]));
+    }
+}
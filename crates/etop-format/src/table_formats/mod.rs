@@ -1,9 +1,13 @@
 mod cell_format;
 mod column_format;
+mod column_stats;
+mod column_widths;
 mod dataframe_format;
 mod unknown_format;
 
 pub use cell_format::*;
 pub use column_format::*;
+pub use column_stats::*;
+pub use column_widths::*;
 pub use dataframe_format::*;
 pub use unknown_format::*;
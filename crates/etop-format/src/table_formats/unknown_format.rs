@@ -1,7 +1,7 @@
 use crate::{BinaryFormat, BoolFormat, NumberFormat, StringFormat};
 
-/// unknown format
-#[derive(Debug, Clone)]
+/// unknown format, whose concrete format is chosen from the dtype of the column by finalize
+#[derive(Debug, Clone, Default)]
 pub struct UnknownFormat {
     /// min width
     pub min_width: Option<usize>,
@@ -10,6 +10,16 @@ pub struct UnknownFormat {
 }
 
 impl UnknownFormat {
+    /// create new unknown format
+    pub fn new() -> UnknownFormat {
+        UnknownFormat::default()
+    }
+
+    /// set min width and max width
+    pub fn width(self, width: usize) -> UnknownFormat {
+        self.min_width(width).max_width(width)
+    }
+
     /// min width
     pub fn min_width(mut self, width: usize) -> UnknownFormat {
         self.min_width = Some(width);
@@ -1,3 +1,4 @@
+use etop_core::{KeyAction, NavAction, PromptContext};
 use polars::prelude::*;
 
 // #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -40,6 +41,84 @@ pub enum Action {
     RequestQuery(etop_core::DatasetQuery),
     ReceiveQuery(etop_core::DatasetQuery, DataFrame),
     ReceiveQueries(Vec<(etop_core::DatasetQuery, DataFrame)>),
+    FetchFailed(String),
     Log(String),
     RerenderTable,
+    ToggleInspect,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    ScrollLeft,
+    ScrollRight,
+    PageUp,
+    PageDown,
+    ToggleScrollbar,
+    ToggleRedact,
+    ToggleTranspose,
+    ToggleZebra,
+    AdjustPrecision(i32),
+    ToggleRawUnits,
+    CycleNumberFormat,
+    ToggleHistogram,
+    ToggleTopValues,
+    ToggleMetrics,
+    ToggleLegend,
+    ToggleLockWidths,
+    OpenPrompt(PromptContext),
+    PromptInput(char),
+    PromptBackspace,
+    PromptComplete,
+    PromptSubmit,
+    PromptCancel,
+    NextView,
+    PrevView,
+    ToggleSplit,
+    SwitchPane,
+    ViewsChanged(Vec<String>, usize),
+    Navigate(NavAction),
+}
+
+impl From<KeyAction> for Action {
+    fn from(action: KeyAction) -> Action {
+        match action {
+            KeyAction::Quit => Action::Quit,
+            KeyAction::PreviousWindow => Action::PreviousWindow,
+            KeyAction::LiveWindow => Action::LiveWindow,
+            KeyAction::DecrementBlock => Action::DecrementBlock,
+            KeyAction::IncrementBlock => Action::IncrementBlock,
+            KeyAction::DecrementWindow => Action::DecrementWindow,
+            KeyAction::IncrementWindow => Action::IncrementWindow,
+            KeyAction::ToggleInspect => Action::ToggleInspect,
+            KeyAction::CursorUp => Action::CursorUp,
+            KeyAction::CursorDown => Action::CursorDown,
+            KeyAction::CursorLeft => Action::CursorLeft,
+            KeyAction::CursorRight => Action::CursorRight,
+            KeyAction::ScrollLeft => Action::ScrollLeft,
+            KeyAction::ScrollRight => Action::ScrollRight,
+            KeyAction::PageUp => Action::PageUp,
+            KeyAction::PageDown => Action::PageDown,
+            KeyAction::ToggleScrollbar => Action::ToggleScrollbar,
+            KeyAction::Refresh => Action::Refresh,
+            KeyAction::ToggleRedact => Action::ToggleRedact,
+            KeyAction::ToggleTranspose => Action::ToggleTranspose,
+            KeyAction::ToggleZebra => Action::ToggleZebra,
+            KeyAction::IncreasePrecision => Action::AdjustPrecision(1),
+            KeyAction::DecreasePrecision => Action::AdjustPrecision(-1),
+            KeyAction::ToggleRawUnits => Action::ToggleRawUnits,
+            KeyAction::CycleNumberFormat => Action::CycleNumberFormat,
+            KeyAction::ToggleHistogram => Action::ToggleHistogram,
+            KeyAction::ToggleTopValues => Action::ToggleTopValues,
+            KeyAction::ToggleMetrics => Action::ToggleMetrics,
+            KeyAction::ToggleLegend => Action::ToggleLegend,
+            KeyAction::ToggleLockWidths => Action::ToggleLockWidths,
+            KeyAction::SortColumn => Action::OpenPrompt(PromptContext::Column),
+            KeyAction::Filter => Action::OpenPrompt(PromptContext::Filter),
+            KeyAction::CommandPalette => Action::OpenPrompt(PromptContext::Command),
+            KeyAction::NextView => Action::NextView,
+            KeyAction::PrevView => Action::PrevView,
+            KeyAction::ToggleSplit => Action::ToggleSplit,
+            KeyAction::SwitchPane => Action::SwitchPane,
+        }
+    }
 }
@@ -1,9 +1,16 @@
 use color_eyre::eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
-use etop_core::{EtopState, Window, WindowSize};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use etop_core::{
+    spawn_fetches, DataSource, DatasetQuery, EtopError, EtopState, KeyBindings, NavAction, NavMode,
+    Panel, Prompt, PromptContext, RefreshMode, RefreshSchedule, RetryPolicy, RetryingSource, Views,
+    VimKeys, Window, WindowSize,
+};
 use ratatui::prelude::Rect;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
 
 use crate::{
@@ -12,6 +19,15 @@ use crate::{
     tui,
 };
 
+const LAYOUT_FILE: &str = "layouts.json";
+const KEYBINDINGS_FILE: &str = "keybindings.json";
+/// rows above the table, used by the header
+const HEADER_HEIGHT: usize = 2;
+/// rows below the table, used by the status line
+const FOOTER_HEIGHT: usize = 1;
+/// columns right of the table, used by the scrollbar when shown
+const SCROLLBAR_WIDTH: usize = 1;
+
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Mode {
     #[default]
@@ -26,11 +42,24 @@ pub struct App {
     pub should_suspend: bool,
     pub mode: Mode,
     pub last_tick_key_events: Vec<KeyEvent>,
-    pub data: EtopState,
+    pub views: Views,
+    pub keybindings: KeyBindings,
+    pub nav_mode: NavMode,
+    pub vim_keys: VimKeys,
+    pub source: Option<Arc<dyn DataSource>>,
+    pub refresh: RefreshSchedule,
+    pub last_refresh: Instant,
 }
 
 impl App {
-    pub fn new(tick_rate: f64, frame_rate: f64, data: Option<EtopState>) -> Result<Self> {
+    pub fn new(
+        tick_rate: f64,
+        frame_rate: f64,
+        views: Option<Views>,
+        nav_mode: NavMode,
+        retry: RetryPolicy,
+        refresh: RefreshMode,
+    ) -> Result<Self> {
         let header = Header::new();
         let body = Body::new();
 
@@ -40,15 +69,24 @@ impl App {
             live: false,
             size: WindowSize::Block(100),
         };
-        let data = match data {
-            Some(data) => data,
-            None => EtopState {
-                dataset: "transactions by to_address".to_string(),
-                window,
-                ..Default::default()
-            },
+        let mut views = match views {
+            Some(views) => views,
+            None => {
+                let data = EtopState {
+                    dataset: "transactions by to_address".to_string(),
+                    window,
+                    ..Default::default()
+                };
+                Views { views: vec![data], current: 0, refresh_all: false, split: None }
+            }
         };
 
+        let source = views.current().rpc_source.clone().map(|source| {
+            Arc::new(RetryingSource { source, policy: retry }) as Arc<dyn DataSource>
+        });
+        let chain_id = source.as_ref().and_then(|source| source.chain_id());
+        views.views.iter_mut().for_each(|view| view.chain_id = chain_id);
+
         Ok(Self {
             tick_rate,
             frame_rate,
@@ -57,7 +95,13 @@ impl App {
             should_suspend: false,
             mode: Mode::Home,
             last_tick_key_events: Vec::new(),
-            data,
+            views,
+            keybindings: KeyBindings::default().for_nav_mode(nav_mode),
+            nav_mode,
+            vim_keys: VimKeys::default(),
+            source,
+            refresh: RefreshSchedule::new(refresh),
+            last_refresh: Instant::now(),
         })
     }
 
@@ -77,10 +121,24 @@ impl App {
             component.init()?;
         }
 
+        // load keybindings, falling back to defaults if they are invalid
+        let keybindings_path = crate::utils::get_config_dir().join(KEYBINDINGS_FILE);
+        match KeyBindings::load(keybindings_path) {
+            Ok(keybindings) => self.keybindings = keybindings.for_nav_mode(self.nav_mode),
+            Err(e) => self.views.current_mut().messages.push(format!("{:?}", e)),
+        }
+
+        // restore layouts of views from previous session
+        let layout_path = crate::utils::get_config_dir().join(LAYOUT_FILE);
+        for view in self.views.views.iter_mut() {
+            let _ = view.load_layout(&layout_path);
+        }
+
         //  initialize
         // action_tx.clone().send(Action::LoadDataset(self.data.dataset.clone()))?;
+        action_tx.clone().send(Action::ViewsChanged(self.views.names(), self.views.current))?;
         action_tx.clone().send(Action::UpdateData)?;
-        if self.data.rpc_source.is_some() {
+        if self.views.current().rpc_source.is_some() {
             action_tx.clone().send(Action::BeginBlockSubscription)?;
         }
 
@@ -91,16 +149,25 @@ impl App {
                     tui::Event::Tick => action_tx.send(Action::Tick)?,
                     tui::Event::Render => action_tx.send(Action::Render)?,
                     tui::Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
-                    tui::Event::Key(key) => match key.code {
-                        KeyCode::Backspace => action_tx.send(Action::PreviousWindow)?,
-                        KeyCode::Char('l') => action_tx.send(Action::LiveWindow)?,
-                        KeyCode::Char('q') => action_tx.send(Action::Quit)?,
-                        KeyCode::Char('[') => action_tx.send(Action::DecrementBlock)?,
-                        KeyCode::Char(']') => action_tx.send(Action::IncrementBlock)?,
-                        KeyCode::Char('{') => action_tx.send(Action::DecrementWindow)?,
-                        KeyCode::Char('}') => action_tx.send(Action::IncrementWindow)?,
+                    tui::Event::Key(key) if self.views.current().prompt.is_some() => match key.code
+                    {
+                        KeyCode::Char(c) => action_tx.send(Action::PromptInput(c))?,
+                        KeyCode::Backspace => action_tx.send(Action::PromptBackspace)?,
+                        KeyCode::Tab => action_tx.send(Action::PromptComplete)?,
+                        KeyCode::Enter => action_tx.send(Action::PromptSubmit)?,
+                        KeyCode::Esc => action_tx.send(Action::PromptCancel)?,
                         _ => {}
                     },
+                    tui::Event::Key(key) => {
+                        let chord = key_chord(key);
+                        if self.nav_mode == NavMode::Vim && self.vim_keys.handles(&chord) {
+                            if let Some(nav) = self.vim_keys.key(&chord) {
+                                action_tx.send(Action::Navigate(nav))?
+                            }
+                        } else if let Some(action) = self.keybindings.action(&chord) {
+                            action_tx.send(action.into())?
+                        }
+                    }
                     _ => {}
                 }
                 for component in self.components.iter_mut() {
@@ -121,7 +188,7 @@ impl App {
                     // // etop setup
                     Action::BeginBlockSubscription => {
                         let action_tx = action_tx.clone();
-                        let data = self.data.clone();
+                        let data = self.views.current().clone();
                         tokio::spawn(async move {
                             let rpc_source = match data.rpc_source {
                                 Some(rpc_source) => rpc_source,
@@ -142,22 +209,26 @@ impl App {
                         });
                     }
                     Action::CheckBlockSet => {
-                        if self.data.window.end_block.is_none() {
+                        if self.views.current().window.end_block.is_none() {
                             let _ = action_tx.send(Action::LiveWindow);
                         }
                     }
                     Action::UpdateData => {
-                        let action_tx = action_tx.clone();
-                        let data = self.data.clone();
-                        let queries = self.data.create_missing_queries().unwrap();
-                        if let Ok(queries) = self.data.create_missing_queries() {
-                            tokio::spawn(async move {
-                                for query in queries.into_iter() {
-                                    if let Ok(df) = data.query(query.clone()).await {
-                                        let _result =
-                                            action_tx.send(Action::ReceiveQuery(query, df));
+                        // inputs shared by several views are queried once for all of them
+                        let queries = self.views.create_missing_queries().unwrap_or_default();
+                        let queries = self.hold_over_row_limit(queries);
+                        // fetch in background, the last frame stays on screen until results arrive
+                        if let Some(source) = self.source.clone() {
+                            self.views.start_fetch(queries.len());
+                            self.refresh.fetches_started(queries.len());
+                            let action_tx = action_tx.clone();
+                            spawn_fetches(source, queries.clone(), move |query, result| {
+                                let _ = match result {
+                                    Ok(df) => action_tx.send(Action::ReceiveQuery(query, df)),
+                                    Err(e) => {
+                                        action_tx.send(Action::FetchFailed(format!("{:?}", e)))
                                     }
-                                }
+                                };
                             });
                         };
 
@@ -165,48 +236,216 @@ impl App {
                         // won't do it
                         if queries.is_empty() {
                             // cache a rendering of new data
-                            let (render_width, render_height) =
-                                term_size::dimensions().unwrap_or((80, 20));
-                            if let Ok(s) = self.data.format_window(render_height + 1, render_width)
-                            {
-                                self.data.cache_df_render = Some(s);
-                            }
+                            self.cache_window();
+                            // a scheduled refresh is observed once all of its data is received
+                            self.refresh.finish(self.views.current().cache_df.as_ref());
                         }
                     }
                     //
                     // // etop data updates
-                    Action::Log(message) => self.data.messages.push(message),
+                    Action::Log(message) => self.views.current_mut().messages.push(message),
                     Action::BlockSeen(seen_block) => {
-                        self.data.see_block(seen_block);
-                        if self.data.window.live {
-                            let _ = action_tx.send(Action::UpdateData);
-                        }
+                        self.views.see_block(seen_block);
+                        self.refresh_if_due(&action_tx);
+                    }
+                    Action::Refresh => {
+                        self.last_refresh = Instant::now();
+                        let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::IncrementBlock => {
-                        self.data.increment_block(1);
+                        self.views.current_mut().increment_block(1);
                         let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::DecrementBlock => {
-                        self.data.window.live = false;
-                        self.data.decrement_block(1);
+                        self.views.current_mut().window.live = false;
+                        self.views.current_mut().decrement_block(1);
                         let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::IncrementWindow => {
-                        self.data.increment_window(1);
+                        self.views.current_mut().increment_window(1);
                         let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::DecrementWindow => {
-                        self.data.window.live = false;
-                        self.data.decrement_window(1);
+                        self.views.current_mut().window.live = false;
+                        self.views.current_mut().decrement_window(1);
                         let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::LiveWindow => {
-                        self.data.enable_live_mode();
+                        self.views.current_mut().enable_live_mode();
+                        let _ = action_tx.send(Action::UpdateData);
+                    }
+                    Action::ToggleInspect => self.views.current_mut().toggle_inspect(),
+                    Action::CursorUp => {
+                        self.views.current_mut().move_selected_cell(-1, 0);
+                        self.cache_window();
+                    }
+                    Action::CursorDown => {
+                        self.views.current_mut().move_selected_cell(1, 0);
+                        self.cache_window();
+                    }
+                    Action::CursorLeft => self.views.current_mut().move_selected_cell(0, -1),
+                    Action::CursorRight => self.views.current_mut().move_selected_cell(0, 1),
+                    Action::ScrollLeft => {
+                        self.views.current_mut().scroll_columns(-1);
+                        self.cache_window();
+                    }
+                    Action::ScrollRight => {
+                        self.views.current_mut().scroll_columns(1);
+                        self.cache_window();
+                    }
+                    Action::PageUp => {
+                        self.views.current_mut().scroll_pages(-1);
+                        self.cache_window();
+                    }
+                    Action::PageDown => {
+                        self.views.current_mut().scroll_pages(1);
+                        self.cache_window();
+                    }
+                    Action::ToggleScrollbar => {
+                        let layout = &mut self.views.current_mut().layout;
+                        layout.scrollbar = !layout.scrollbar;
+                        self.cache_window();
+                    }
+                    Action::ToggleTranspose => {
+                        let view = self.views.current_mut();
+                        view.layout.transpose = !view.layout.transpose;
+                        view.scroll_offset = 0;
+                        view.selected_cell = None;
+                        self.cache_window();
+                    }
+                    Action::ToggleZebra => {
+                        let layout = &mut self.views.current_mut().layout;
+                        layout.zebra = !layout.zebra;
+                    }
+                    Action::ToggleRawUnits => {
+                        let view = self.views.current_mut();
+                        if let Some(column) = view.selected_column() {
+                            if let Err(e) = view.toggle_raw_units(&column) {
+                                view.messages.push(format!("{:?}", e));
+                            }
+                            self.cache_window();
+                        }
+                    }
+                    Action::CycleNumberFormat => {
+                        let view = self.views.current_mut();
+                        if let Some(column) = view.selected_column() {
+                            if let Err(e) = view.cycle_number_format(&column) {
+                                view.messages.push(format!("{:?}", e));
+                            }
+                            self.cache_window();
+                        }
+                    }
+                    Action::ToggleHistogram => {
+                        let view = self.views.current_mut();
+                        if let Some(column) = view.selected_column() {
+                            view.toggle_panel(Panel::Histogram(column));
+                        }
+                    }
+                    Action::ToggleTopValues => {
+                        let view = self.views.current_mut();
+                        if let Some(column) = view.selected_column() {
+                            view.toggle_panel(Panel::TopValues(column));
+                        }
+                    }
+                    Action::ToggleMetrics => {
+                        self.views.current_mut().toggle_panel(Panel::MetricsDashboard)
+                    }
+                    Action::ToggleLegend => self.views.current_mut().toggle_panel(Panel::Legend),
+                    Action::ToggleLockWidths => {
+                        let view = self.views.current_mut();
+                        if view.locked_widths.is_some() {
+                            view.unlock_widths();
+                        } else if let Err(e) = view.lock_widths() {
+                            view.messages.push(format!("{:?}", e));
+                        }
+                        self.cache_window();
+                    }
+                    Action::AdjustPrecision(delta) => {
+                        let view = self.views.current_mut();
+                        if let Some(column) = view.selected_column() {
+                            if let Err(e) = view.adjust_precision(&column, delta) {
+                                view.messages.push(format!("{:?}", e));
+                            }
+                            self.cache_window();
+                        }
+                    }
+                    Action::ToggleRedact => {
+                        let redact_all = !self.views.current().redact_all;
+                        for view in self.views.views.iter_mut() {
+                            view.redact_all = redact_all;
+                        }
+                        self.cache_window();
+                    }
+                    Action::Navigate(nav) => match nav {
+                        NavAction::Search => {
+                            self.views.current_mut().open_prompt(PromptContext::Search)
+                        }
+                        NavAction::Command => {
+                            self.views.current_mut().open_prompt(PromptContext::Command)
+                        }
+                        nav => {
+                            self.views.current_mut().navigate(&nav);
+                            self.cache_window();
+                        }
+                    },
+                    Action::OpenPrompt(context) => self.views.current_mut().open_prompt(context),
+                    Action::PromptInput(c) => {
+                        if let Some(prompt) = self.views.current_mut().prompt.as_mut() {
+                            prompt.push(c)
+                        }
+                    }
+                    Action::PromptBackspace => {
+                        if let Some(prompt) = self.views.current_mut().prompt.as_mut() {
+                            prompt.backspace()
+                        }
+                    }
+                    Action::PromptComplete => self.views.current_mut().complete_prompt(),
+                    Action::PromptCancel => self.views.current_mut().close_prompt(),
+                    Action::PromptSubmit => {
+                        let view = self.views.current_mut();
+                        let held = matches!(
+                            view.prompt,
+                            Some(Prompt { context: PromptContext::RowLimit(_), .. })
+                        );
+                        match view.submit_prompt() {
+                            Ok(()) => {}
+                            Err(e) => view.messages.push(format!("{:?}", e)),
+                        };
+                        // resume fetches held over the row limit
+                        if held {
+                            let _ = action_tx.send(Action::UpdateData);
+                        }
+                        let _ = action_tx.send(Action::RerenderTable);
+                    }
+                    Action::NextView => {
+                        self.views.next_view();
+                        let _ = action_tx
+                            .send(Action::ViewsChanged(self.views.names(), self.views.current));
+                        let _ = action_tx.send(Action::UpdateData);
+                    }
+                    Action::PrevView => {
+                        self.views.prev_view();
+                        let _ = action_tx
+                            .send(Action::ViewsChanged(self.views.names(), self.views.current));
+                        let _ = action_tx.send(Action::UpdateData);
+                    }
+                    Action::ToggleSplit => {
+                        if let Err(e) = self.views.toggle_split() {
+                            self.views.current_mut().messages.push(format!("{:?}", e));
+                        }
+                        let _ = action_tx
+                            .send(Action::ViewsChanged(self.views.names(), self.views.current));
+                        let _ = action_tx.send(Action::UpdateData);
+                    }
+                    Action::SwitchPane => {
+                        self.views.switch_focus();
+                        let _ = action_tx
+                            .send(Action::ViewsChanged(self.views.names(), self.views.current));
                         let _ = action_tx.send(Action::UpdateData);
                     }
                     Action::RequestQuery(query) => {
                         let action_tx = action_tx.clone();
-                        let data = self.data.clone();
+                        let data = self.views.current().clone();
                         tokio::spawn(async move {
                             if let Ok(df) = data.query(query.clone()).await {
                                 let _result = action_tx.send(Action::ReceiveQuery(query, df));
@@ -214,23 +453,21 @@ impl App {
                         });
                     }
                     Action::ReceiveQuery(query, df) => {
-                        let _result = self.data.warehouse.add_dataset(query.dataset(), df);
+                        let _result = self.views.receive_query(query, df);
+                        self.views.complete_fetch();
+                        self.refresh.fetch_received();
 
                         // cache a rendering of new data
-                        let (render_width, render_height) =
-                            term_size::dimensions().unwrap_or((80, 20));
-                        if let Ok(s) = self.data.format_window(render_height + 1, render_width) {
-                            self.data.cache_df_render = Some(s);
-                        }
+                        self.cache_window();
 
                         let _ = action_tx.send(Action::UpdateData);
                     }
+                    Action::FetchFailed(error) => {
+                        self.views.fail_fetch(error);
+                        self.refresh.abandon();
+                    }
                     Action::RerenderTable => {
-                        let (render_width, render_height) =
-                            term_size::dimensions().unwrap_or((80, 20));
-                        if let Ok(s) = self.data.format_window(render_height + 1, render_width) {
-                            self.data.cache_df_render = Some(s);
-                        }
+                        self.cache_window();
                     }
                     // Action::ReceiveQueries(results) => {
                     //     for (query, df) in results.into_iter() {
@@ -243,21 +480,18 @@ impl App {
                     // // low-level controls
                     Action::Tick => {
                         self.last_tick_key_events.drain(..);
+                        self.refresh_if_due(&action_tx);
                     }
                     Action::Quit => self.should_quit = true,
                     Action::Suspend => self.should_suspend = true,
                     Action::Resume => self.should_suspend = false,
                     Action::Resize(w, h) => {
-                        let (render_width, render_height) =
-                            term_size::dimensions().unwrap_or((80, 20));
-                        if let Ok(s) = self.data.format_window(render_height + 1, render_width) {
-                            self.data.cache_df_render = Some(s);
-                        }
+                        self.cache_window();
 
                         tui.resize(Rect::new(0, 0, w, h))?;
                         tui.draw(|f| {
                             for component in self.components.iter_mut() {
-                                let r = component.draw(f, f.size(), self.data.clone());
+                                let r = component.draw(f, f.size(), self.views.current().clone());
                                 if let Err(e) = r {
                                     action_tx
                                         .send(Action::Error(format!("Failed to draw: {:?}", e)))
@@ -269,7 +503,7 @@ impl App {
                     Action::Render => {
                         tui.draw(|f| {
                             for component in self.components.iter_mut() {
-                                let r = component.draw(f, f.size(), self.data.clone());
+                                let r = component.draw(f, f.size(), self.views.current().clone());
                                 if let Err(e) = r {
                                     action_tx
                                         .send(Action::Error(format!("Failed to draw: {:?}", e)))
@@ -299,6 +533,84 @@ impl App {
             }
         }
         tui.exit()?;
+        if std::fs::create_dir_all(crate::utils::get_config_dir()).is_ok() {
+            for view in self.views.views.iter() {
+                let _ = view.save_layout(&layout_path);
+            }
+        }
         Ok(())
     }
+
+    /// refresh data of live view if due under the refresh mode
+    fn refresh_if_due(&mut self, action_tx: &mpsc::UnboundedSender<Action>) {
+        if self.views.current().window.live && self.refresh.is_due(self.last_refresh.elapsed()) {
+            self.last_refresh = Instant::now();
+            self.refresh.begin(self.views.current().cache_df.clone());
+            let _ = action_tx.send(Action::UpdateData);
+        }
+    }
+
+    /// queries to fetch now, none if they would exceed max_rows of current view, in which case
+    /// the user is asked to proceed or narrow the window
+    fn hold_over_row_limit(&mut self, queries: Vec<DatasetQuery>) -> Vec<DatasetQuery> {
+        let view = self.views.current_mut();
+        if let Some(Prompt { context: PromptContext::RowLimit(_), .. }) = view.prompt {
+            return vec![]
+        }
+        match view.check_row_limit(&queries) {
+            Err(EtopError::RowLimitExceeded(rows, _)) => {
+                view.open_prompt(PromptContext::RowLimit(rows));
+                vec![]
+            }
+            _ => queries,
+        }
+    }
+
+    /// cache a rendering of current view sized to the rows between header and status line
+    fn cache_window(&mut self) {
+        let (render_width, render_height) = term_size::dimensions().unwrap_or((80, 20));
+        let render_height = render_height.saturating_sub(HEADER_HEIGHT + FOOTER_HEIGHT);
+        let render_width = match self.views.current().layout.scrollbar {
+            true => render_width.saturating_sub(SCROLLBAR_WIDTH),
+            false => render_width,
+        };
+        let _ = self.views.cache_windows(render_height, render_width);
+    }
+}
+
+/// chord of key event, in the form used by keybindings, e.g. `ctrl+c`
+fn key_chord(key: KeyEvent) -> String {
+    let name = match key.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::BackTab => "backtab".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        _ => "".to_string(),
+    };
+    let mut chord = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        chord.push_str("ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        chord.push_str("alt+");
+    }
+    // shift is already part of chars and of backtab
+    let shifted = matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab);
+    if key.modifiers.contains(KeyModifiers::SHIFT) && !shifted {
+        chord.push_str("shift+");
+    }
+    chord.push_str(&name);
+    chord
 }
@@ -1,11 +1,16 @@
 use super::Component;
-use crate::{action::Action, tui::Frame};
+use crate::{action::Action, tui::Frame, widget::table_lines};
 use color_eyre::eyre::Result;
-use etop_core::EtopState;
+use etop_core::{EtopState, FetchState, SplitPanes, PANE_SEPARATOR};
 use ratatui::{prelude::*, widgets::*};
 
+/// frames of spinner shown while fetching
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
 #[derive(Debug, Clone, PartialEq)]
-pub struct Body {}
+pub struct Body {
+    ticks: usize,
+}
 
 impl Default for Body {
     fn default() -> Self {
@@ -15,12 +20,15 @@ impl Default for Body {
 
 impl Body {
     pub fn new() -> Self {
-        Self {}
+        Self { ticks: 0 }
     }
 }
 
 impl Component for Body {
-    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::Tick = action {
+            self.ticks = self.ticks.wrapping_add(1);
+        }
         Ok(None)
     }
 
@@ -33,12 +41,97 @@ impl Component for Body {
 
         let color = Color::Gray;
 
-        let s = data.cache_df_render.unwrap_or("".to_string());
+        let status = match data.prompt.as_ref() {
+            Some(prompt) => Some(prompt.render()),
+            None => data.inspect_status().or_else(|| match &data.fetch.state {
+                FetchState::Idle => None,
+                FetchState::Loading => {
+                    Some(format!("{} fetching…", SPINNER[self.ticks % SPINNER.len()]))
+                }
+                FetchState::Error(error) => Some(format!("fetch failed: {}", error)),
+            }),
+        };
+        let rects = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+            .split(rect);
+        let (rect, status_rect) = (rects[0], rects[1]);
+
+        // scrollbar in right margin
+        let rect = if data.layout.scrollbar {
+            let rects = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+                .split(rect);
+            let bar: Vec<Line<'_>> = data
+                .scroll_bar(rects[1].height as usize)
+                .into_iter()
+                .map(|c| Line::from(c.to_string()))
+                .collect();
+            f.render_widget(Paragraph::new(bar).style(Style::default().fg(color)), rects[1]);
+            rects[0]
+        } else {
+            rect
+        };
 
         let style = Style::default().fg(color);
-        let content = Paragraph::new(s).style(style);
-        f.render_widget(content, rect);
+        let lines = match data.render_panel() {
+            Some(Ok(panel)) => panel.lines().map(|line| Line::from(line.to_string())).collect(),
+            Some(Err(e)) => vec![Line::from(format!("{:?}", e))],
+            None => match (data.cache_split.as_ref(), data.cache_panes.as_ref()) {
+                (Some(split), _) => {
+                    draw_split(f, rect, split, style);
+                    vec![]
+                }
+                (None, Some(panes)) => {
+                    panes.lines().map(|line| Line::from(line.to_string())).collect()
+                }
+                (None, None) => table_lines(&data),
+            },
+        };
+        if !lines.is_empty() {
+            f.render_widget(Paragraph::new(lines).style(style), rect);
+        }
+
+        if data.cache_df.is_some() {
+            let indicator = Paragraph::new(data.scroll_indicator())
+                .style(Style::default().fg(color))
+                .alignment(Alignment::Right);
+            f.render_widget(indicator, status_rect);
+        }
+
+        match status {
+            Some(status) => {
+                let style = Style::default().fg(Color::Rgb(255, 255, 255)).bold();
+                f.render_widget(Paragraph::new(status).style(style), status_rect);
+            }
+            None => {
+                let style = Style::default().fg(color).dim();
+                f.render_widget(Paragraph::new(data.status_line()).style(style), status_rect);
+            }
+        }
 
         Ok(())
     }
 }
+
+/// draw each view of a split in its own region, titled above its table
+fn draw_split(f: &mut Frame<'_>, rect: Rect, split: &SplitPanes, style: Style) {
+    let (left_width, right_width) = etop_core::split_widths(rect.width as usize, split.ratio);
+    let separator_width = PANE_SEPARATOR.chars().count();
+    let rects = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Length(left_width as u16),
+            Constraint::Length(separator_width as u16),
+            Constraint::Length(right_width as u16),
+        ])
+        .split(rect);
+    for ((title, view), rect) in split.panes.iter().zip([rects[0], rects[2]]) {
+        let mut lines = vec![Line::styled(title.as_str(), style.bold())];
+        lines.extend(table_lines(view));
+        f.render_widget(Paragraph::new(lines).style(style), rect);
+    }
+    let separator = vec![Line::from(PANE_SEPARATOR); rects[1].height as usize];
+    f.render_widget(Paragraph::new(separator).style(style), rects[1]);
+}
@@ -5,7 +5,10 @@ use etop_core::EtopState;
 use ratatui::{prelude::*, widgets::*};
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Header {}
+pub struct Header {
+    views: Vec<String>,
+    current: usize,
+}
 
 impl Default for Header {
     fn default() -> Self {
@@ -15,12 +18,16 @@ impl Default for Header {
 
 impl Header {
     pub fn new() -> Self {
-        Self {}
+        Self { views: vec![], current: 0 }
     }
 }
 
 impl Component for Header {
-    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ViewsChanged(views, current) = action {
+            self.views = views;
+            self.current = current;
+        }
         Ok(None)
     }
 
@@ -49,12 +56,20 @@ impl Component for Header {
         let block = Block::default().title(title).style(style);
         f.render_widget(block, inner_rects[0]);
 
-        let s = get_block_window_string(&data);
+        let s = data.meta_banner().render_meta();
         let style = Style::default().fg(color).bold();
         let title = block::Title::from(s.dim()).alignment(Alignment::Right);
         let block = Block::default().title(title).style(style);
         f.render_widget(block, inner_rects[1]);
 
+        if self.views.len() > 1 {
+            let tabs = Tabs::new(self.views.clone())
+                .select(self.current)
+                .style(Style::default().fg(color).dim())
+                .highlight_style(Style::default().fg(color).bold());
+            f.render_widget(tabs, inner_rects2[0]);
+        }
+
         let s = get_current_block_string(&data);
         let style = Style::default().fg(color).bold();
         let title = block::Title::from(s.dim()).alignment(Alignment::Right);
@@ -65,20 +80,6 @@ impl Component for Header {
     }
 }
 
-fn get_block_window_string(data: &EtopState) -> String {
-    match (data.window.start_block, data.window.end_block) {
-        (Some(start_block), Some(end_block)) => {
-            if start_block == end_block {
-                format!("showing block {}", end_block)
-            } else {
-                format!("showing blocks {} to {}", start_block, end_block)
-            }
-        }
-        (_, Some(end_block)) => format!("block {}", end_block),
-        (_, None) => "-".to_string(),
-    }
-}
-
 fn get_current_block_string(data: &EtopState) -> String {
     match data.latest_block {
         Some(block) => format!("latest block: {}", block),
@@ -8,22 +8,29 @@
 pub mod action;
 pub mod app;
 pub mod components;
+pub mod testing;
 pub mod tui;
 pub mod utils;
+pub mod widget;
 
 use crate::{
     app::App,
     utils::{initialize_logging, initialize_panic_handler},
 };
 use color_eyre::eyre::Result;
-use etop_core::EtopState;
+use etop_core::{NavMode, RefreshMode, RetryPolicy, Views};
 
-pub async fn tokio_main(data: Option<EtopState>) -> Result<()> {
+pub async fn tokio_main(
+    views: Option<Views>,
+    nav_mode: NavMode,
+    retry: RetryPolicy,
+    refresh: RefreshMode,
+) -> Result<()> {
     initialize_logging()?;
     initialize_panic_handler()?;
     let tick_rate = 1.0;
     let frame_rate = 10.0;
-    let mut app = App::new(tick_rate, frame_rate, data)?;
+    let mut app = App::new(tick_rate, frame_rate, views, nav_mode, retry, refresh)?;
     app.run().await?;
     Ok(())
 }
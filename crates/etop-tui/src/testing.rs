@@ -0,0 +1,71 @@
+//! utilities for testing rendered tables against stored snapshots
+//!
+//! snapshots are plain text files, written instead of compared when the
+//! `ETOP_UPDATE_SNAPSHOTS` environment variable is set, which is also needed to create them
+
+#[cfg(test)]
+#[path = "testing_tests.rs"]
+mod tests;
+
+use crate::widget::EtopTable;
+use etop_core::EtopState;
+use ratatui::{backend::TestBackend, Terminal};
+use std::path::Path;
+
+/// environment variable that makes assert_snapshot write snapshots instead of comparing them
+pub const UPDATE_SNAPSHOTS_VAR: &str = "ETOP_UPDATE_SNAPSHOTS";
+
+/// render table of view into a buffer of given size, as text with one line per buffer row
+pub fn render_snapshot(view: &EtopState, width: u16, height: u16) -> String {
+    let mut terminal = match Terminal::new(TestBackend::new(width, height)) {
+        Ok(terminal) => terminal,
+        Err(e) => return format!("{:?}", e),
+    };
+    if let Err(e) = terminal.draw(|f| f.render_widget(EtopTable::new(view), f.size())) {
+        return format!("{:?}", e)
+    }
+    let buffer = terminal.backend().buffer();
+    let lines: Vec<String> = (0..buffer.area.height)
+        .map(|y| (0..buffer.area.width).map(|x| buffer.get(x, y).symbol.as_str()).collect())
+        .collect();
+    normalize_ansi(&lines.join("\n"))
+}
+
+/// strip ansi escape codes, carriage returns, and trailing whitespace of each line
+pub fn normalize_ansi(text: &str) -> String {
+    let text = strip_ansi_escapes::strip_str(text);
+    let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+/// assert that rendered text matches the snapshot stored at path, after normalizing both
+///
+/// snapshots are only written when UPDATE_SNAPSHOTS_VAR is set, and a missing snapshot fails
+/// otherwise, so that a snapshot that was never committed cannot pass unnoticed
+pub fn assert_snapshot<P: AsRef<Path>>(actual: &str, path: P) {
+    let path = path.as_ref();
+    let actual = normalize_ansi(actual);
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("could not create snapshot directory");
+        }
+        std::fs::write(path, format!("{}\n", actual)).expect("could not write snapshot");
+        return
+    }
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "could not read snapshot {}: {}, set {} to write it",
+            path.display(),
+            e,
+            UPDATE_SNAPSHOTS_VAR
+        ),
+    };
+    pretty_assertions::assert_eq!(
+        normalize_ansi(&expected),
+        actual,
+        "snapshot {} differs, set {} to update it",
+        path.display(),
+        UPDATE_SNAPSHOTS_VAR
+    );
+}
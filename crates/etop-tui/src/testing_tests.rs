@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod testing_tests {
+    use crate::testing::{assert_snapshot, normalize_ansi, render_snapshot};
+    use etop_core::EtopState;
+    use polars::prelude::*;
+
+    fn snapshot_path(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(name)
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_ansi("\x1b[1mbold\x1b[0m  \r\nplain\n\n"), "bold\nplain");
+    }
+
+    #[test]
+    fn sample_frame() {
+        let df = df!(
+            "block_number" => [18_000_000u32, 18_000_001, 18_000_002],
+            "gas_used" => [12_500_000u64, 29_999_000, 0],
+            "success" => [true, false, true],
+        )
+        .unwrap();
+        let mut view = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        view.warehouse.data.insert("test_blocks".to_string(), df);
+        let render = render_snapshot(&view, 40, 10);
+        assert_snapshot(&render, snapshot_path("sample_frame.txt"));
+    }
+
+    #[test]
+    #[should_panic(expected = "could not read snapshot")]
+    fn missing_snapshot() {
+        assert_snapshot("text", snapshot_path("missing_snapshot.txt"));
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+#[path = "tui_tests.rs"]
+mod tests;
+
 use std::{
     ops::{Deref, DerefMut},
     time::Duration,
@@ -36,8 +40,70 @@ pub enum Event {
     Resize(u16, u16),
 }
 
+/// terminal whose raw mode and alternate screen can be undone
+pub trait TerminalControl {
+    /// return terminal to cooked mode on the main screen
+    fn restore(&mut self) -> Result<()>;
+}
+
+/// the process terminal, driven through crossterm
+#[derive(Debug, Default)]
+pub struct CrosstermTerminal;
+
+impl TerminalControl for CrosstermTerminal {
+    fn restore(&mut self) -> Result<()> {
+        restore_terminal()
+    }
+}
+
+/// leave alternate screen, show cursor, and disable raw mode, if raw mode is on
+///
+/// safe to call from a panic hook, before any panic message is printed
+pub fn restore_terminal() -> Result<()> {
+    if crossterm::terminal::is_raw_mode_enabled()? {
+        crossterm::execute!(std::io::stderr(), LeaveAlternateScreen, cursor::Show)?;
+        crossterm::terminal::disable_raw_mode()?;
+    }
+    Ok(())
+}
+
+/// restores terminal when dropped, so that early returns and unwinding leave a usable shell
+#[derive(Debug)]
+pub struct TerminalGuard<T: TerminalControl = CrosstermTerminal> {
+    terminal: Option<T>,
+}
+
+impl<T: TerminalControl> TerminalGuard<T> {
+    /// guard terminal that has entered raw mode
+    pub fn new(terminal: T) -> Self {
+        Self { terminal: Some(terminal) }
+    }
+
+    /// guard that restores nothing
+    pub fn disarmed() -> Self {
+        Self { terminal: None }
+    }
+
+    /// restore terminal now, later restores and the drop do nothing
+    pub fn restore(&mut self) -> Result<()> {
+        match self.terminal.take() {
+            Some(mut terminal) => terminal.restore(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: TerminalControl> Drop for TerminalGuard<T> {
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            log::error!("Unable to restore terminal: {:?}", e);
+        }
+    }
+}
+
 pub struct Tui {
     pub terminal: ratatui::Terminal<Backend<std::io::Stderr>>,
+    pub guard: TerminalGuard,
     pub task: JoinHandle<()>,
     pub cancellation_token: CancellationToken,
     pub event_rx: UnboundedReceiver<Event>,
@@ -54,7 +120,17 @@ impl Tui {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let cancellation_token = CancellationToken::new();
         let task = tokio::spawn(async {});
-        Ok(Self { terminal, task, cancellation_token, event_rx, event_tx, frame_rate, tick_rate })
+        let guard = TerminalGuard::disarmed();
+        Ok(Self {
+            terminal,
+            guard,
+            task,
+            cancellation_token,
+            event_rx,
+            event_tx,
+            frame_rate,
+            tick_rate,
+        })
     }
 
     pub fn tick_rate(&mut self, tick_rate: f64) {
@@ -147,6 +223,7 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
+        self.guard = TerminalGuard::new(CrosstermTerminal);
         crossterm::execute!(std::io::stderr(), EnterAlternateScreen, cursor::Hide)?;
         self.start();
         Ok(())
@@ -156,10 +233,9 @@ impl Tui {
         self.stop()?;
         if crossterm::terminal::is_raw_mode_enabled()? {
             self.flush()?;
-            crossterm::execute!(std::io::stderr(), LeaveAlternateScreen, cursor::Show)?;
-            crossterm::terminal::disable_raw_mode()?;
         }
-        Ok(())
+        self.guard.restore()?;
+        restore_terminal()
     }
 
     pub fn cancel(&self) {
@@ -199,6 +275,8 @@ impl DerefMut for Tui {
 
 impl Drop for Tui {
     fn drop(&mut self) {
-        self.exit().unwrap();
+        if let Err(e) = self.exit() {
+            log::error!("Unable to exit Terminal: {:?}", e);
+        }
     }
 }
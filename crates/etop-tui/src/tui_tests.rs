@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tui_tests {
+    use crate::tui::{TerminalControl, TerminalGuard};
+    use color_eyre::eyre::Result;
+    use std::{cell::Cell, rc::Rc};
+
+    struct MockTerminal {
+        restores: Rc<Cell<usize>>,
+    }
+
+    impl TerminalControl for MockTerminal {
+        fn restore(&mut self) -> Result<()> {
+            self.restores.set(self.restores.get() + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn guard_restores_on_drop() {
+        let restores = Rc::new(Cell::new(0));
+        let guard = TerminalGuard::new(MockTerminal { restores: restores.clone() });
+        assert_eq!(restores.get(), 0);
+        drop(guard);
+        assert_eq!(restores.get(), 1);
+
+        // explicit restore is not repeated by the drop
+        let mut guard = TerminalGuard::new(MockTerminal { restores: restores.clone() });
+        guard.restore().unwrap();
+        drop(guard);
+        assert_eq!(restores.get(), 2);
+
+        // restores while unwinding from a panic
+        let unwound = restores.clone();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _guard = TerminalGuard::new(MockTerminal { restores: unwound });
+            panic!("crash while terminal is raw");
+        }));
+        assert!(result.is_err());
+        assert_eq!(restores.get(), 3);
+
+        drop(TerminalGuard::<MockTerminal>::disarmed());
+        assert_eq!(restores.get(), 3);
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+#[path = "widget_tests.rs"]
+mod tests;
+
+use etop_core::{EtopState, RowStyle};
+use ratatui::{prelude::*, widgets::*};
+
+/// background of shaded rows when zebra striping
+const ZEBRA_BACKGROUND: Color = Color::Rgb(38, 38, 38);
+
+/// background of the row of the inspected cell
+const SELECTED_BACKGROUND: Color = Color::Rgb(70, 70, 70);
+
+/// background of the inspected cell, over that of its row
+const FOCUSED_BACKGROUND: Color = Color::Rgb(110, 110, 110);
+
+/// table of an etop view, for embedding in other ratatui apps
+///
+/// rendered with `frame.render_widget(EtopTable::new(&view), area)`, or with
+/// `frame.render_stateful_widget` to keep scroll position and selection in an `EtopTableState`
+#[derive(Debug, Clone)]
+pub struct EtopTable<'a> {
+    view: &'a EtopState,
+    style: Style,
+}
+
+impl<'a> EtopTable<'a> {
+    /// table of view
+    pub fn new(view: &'a EtopState) -> EtopTable<'a> {
+        EtopTable { view, style: Style::default().fg(Color::Gray) }
+    }
+
+    /// set base style of table
+    pub fn style(mut self, style: Style) -> EtopTable<'a> {
+        self.style = style;
+        self
+    }
+}
+
+/// scroll position and selection of an `EtopTable`, in place of those of its view
+///
+/// rendering clamps scroll_offset so that the last row stays reachable, and scrolls just enough
+/// to keep the selected cell in view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EtopTableState {
+    /// number of rows scrolled past
+    pub scroll_offset: usize,
+    /// (row, column) of selected cell
+    pub selected_cell: Option<(usize, usize)>,
+}
+
+impl StatefulWidget for EtopTable<'_> {
+    type State = EtopTableState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut EtopTableState) {
+        let mut view = self.view.clone();
+        view.scroll_offset = state.scroll_offset;
+        view.selected_cell = state.selected_cell;
+        let (height, width) = (area.height as usize, area.width as usize);
+        let mut result = view.cache_window(height, width);
+        if result.is_ok() && view.selected_cell.is_some() {
+            let scroll_offset = view.scroll_offset;
+            view.move_selected_cell(0, 0);
+            if view.scroll_offset != scroll_offset {
+                result = view.cache_window(height, width);
+            }
+        }
+        let paragraph = match result {
+            Ok(()) => {
+                state.scroll_offset = view.scroll_offset;
+                state.selected_cell = view.selected_cell;
+                Paragraph::new(table_lines(&view))
+            }
+            Err(e) => Paragraph::new(format!("{:?}", e)),
+        };
+        paragraph.style(self.style).render(area, buf);
+    }
+}
+
+impl Widget for EtopTable<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = EtopTableState {
+            scroll_offset: self.view.scroll_offset,
+            selected_cell: self.view.selected_cell,
+        };
+        StatefulWidget::render(self, area, buf, &mut state)
+    }
+}
+
+/// lines of the cached table of view, with data rows styled by their row style
+pub(crate) fn table_lines(view: &EtopState) -> Vec<Line<'_>> {
+    let render = match view.cache_df_render.as_ref() {
+        Some(render) => render.as_str(),
+        None => return vec![],
+    };
+
+    // data rows are between the header and the footer
+    let n_data_lines = view.cache_visible_rows.len();
+    render
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let row = i.checked_sub(view.cache_header_lines).filter(|row| *row < n_data_lines);
+            let row_style = match row {
+                Some(row) => view.row_style(row),
+                None => RowStyle::Plain,
+            };
+            // each line is styled as a whole, so styles cannot bleed into other cells
+            match row_style {
+                RowStyle::Plain => Line::from(line),
+                RowStyle::Zebra => Line::styled(line, Style::default().bg(ZEBRA_BACKGROUND)),
+                RowStyle::Rank(style) => match style.parse::<Color>() {
+                    Ok(row_color) => Line::styled(line, Style::default().fg(row_color).bold()),
+                    Err(_) => Line::from(line),
+                },
+                RowStyle::Data(style) => match style.parse::<Color>() {
+                    Ok(row_color) => Line::styled(line, Style::default().fg(row_color)),
+                    Err(_) => Line::from(line),
+                },
+                RowStyle::Selected => {
+                    let style = Style::default().bg(SELECTED_BACKGROUND).bold();
+                    match row.and_then(|row| view.selected_cell_range(row)) {
+                        Some(range) => Line::from(vec![
+                            Span::styled(&line[..range.start], style),
+                            Span::styled(&line[range.clone()], style.bg(FOCUSED_BACKGROUND)),
+                            Span::styled(&line[range.end..], style),
+                        ]),
+                        None => Line::styled(line, style),
+                    }
+                }
+            }
+        })
+        .collect()
+}
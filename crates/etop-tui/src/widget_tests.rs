@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod widget_tests {
+    use crate::widget::{EtopTable, EtopTableState};
+    use etop_core::EtopState;
+    use polars::prelude::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn view() -> EtopState {
+        let df = df!("n" => (0u32..10).collect::<Vec<_>>()).unwrap();
+        let mut view = EtopState { dataset: "test_blocks".to_string(), ..Default::default() };
+        view.warehouse.data.insert("test_blocks".to_string(), df);
+        view
+    }
+
+    fn lines(buffer: &Buffer) -> Vec<String> {
+        let area = buffer.area;
+        (0..area.height)
+            .map(|y| {
+                let line: String =
+                    (0..area.width).map(|x| buffer.get(x, y).symbol.clone()).collect();
+                line.trim().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn render_widget() {
+        let view = view();
+        let mut terminal = Terminal::new(TestBackend::new(6, 5)).unwrap();
+        terminal.draw(|f| f.render_widget(EtopTable::new(&view), f.size())).unwrap();
+        assert_eq!(lines(terminal.backend().buffer()), vec!["n", "─", "0", "1", "2"]);
+    }
+
+    #[test]
+    fn render_stateful_widget() {
+        let view = view();
+        let mut terminal = Terminal::new(TestBackend::new(6, 5)).unwrap();
+        let mut state = EtopTableState { scroll_offset: 4, selected_cell: None };
+        terminal
+            .draw(|f| f.render_stateful_widget(EtopTable::new(&view), f.size(), &mut state))
+            .unwrap();
+        assert_eq!(lines(terminal.backend().buffer()), vec!["n", "─", "4", "5", "6"]);
+
+        // offset is clamped to keep the last row reachable
+        state.scroll_offset = 100;
+        terminal
+            .draw(|f| f.render_stateful_widget(EtopTable::new(&view), f.size(), &mut state))
+            .unwrap();
+        assert_eq!(lines(terminal.backend().buffer()), vec!["n", "─", "7", "8", "9"]);
+        assert_eq!(state.scroll_offset, 7);
+
+        // table scrolls to the selected cell
+        state.selected_cell = Some((1, 0));
+        terminal
+            .draw(|f| f.render_stateful_widget(EtopTable::new(&view), f.size(), &mut state))
+            .unwrap();
+        assert_eq!(lines(terminal.backend().buffer()), vec!["n", "─", "1", "2", "3"]);
+        assert_eq!(state.scroll_offset, 1);
+    }
+}